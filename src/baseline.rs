@@ -0,0 +1,299 @@
+//! Performance baselines, used to detect regressions in the duration
+//! of the rounds of a test. The [TestResults] of each test can be
+//! saved as a named baseline, and the results of a later run compared
+//! against it.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use eyre::Context;
+use humantime::format_duration;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    reporting::format_table,
+    specification::TestId,
+    test::{RoundResults, TestResults},
+    util::create_dir_if_not_exists,
+};
+
+/// Name of the file that the comparison against a baseline is
+/// written to, in the out directory.
+pub const BASELINE_COMPARISON_FILE_NAME: &str = "baseline-comparison.txt";
+
+/// The tolerance for regressions in the durations of a test compared
+/// to the baseline, as a fraction of the baseline duration (e.g.
+/// `0.1` allows the duration to increase by 10%).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaselineTolerance {
+    /// (Optional) A warning is reported if a duration regresses
+    /// beyond this fraction. Default: `0.1`.
+    #[serde(default = "default_warn_tolerance")]
+    pub warn: f64,
+    /// (Optional) The test fails if a duration regresses beyond this
+    /// fraction. Default: `0.25`.
+    #[serde(default = "default_fail_tolerance")]
+    pub fail: f64,
+}
+
+fn default_warn_tolerance() -> f64 {
+    0.1
+}
+
+fn default_fail_tolerance() -> f64 {
+    0.25
+}
+
+impl Default for BaselineTolerance {
+    fn default() -> Self {
+        Self {
+            warn: default_warn_tolerance(),
+            fail: default_fail_tolerance(),
+        }
+    }
+}
+
+/// Path to the file storing the baseline `name` for the test `id`.
+fn baseline_path(baselines_dir: &Path, name: &str, id: &str) -> PathBuf {
+    baselines_dir.join(name).join(format!("{}.ron", id))
+}
+
+/// Save the `results` of the test `id` to the baseline `name` in
+/// `baselines_dir`, replacing any previously saved results.
+pub fn save_baseline(
+    baselines_dir: &Path,
+    name: &str,
+    id: &str,
+    results: &TestResults,
+) -> eyre::Result<()> {
+    create_dir_if_not_exists(baselines_dir.join(name))?;
+    let path = baseline_path(baselines_dir, name, id);
+    std::fs::write(
+        &path,
+        ron::ser::to_string_pretty(results, Default::default())?,
+    )
+    .wrap_err_with(|| eyre::eyre!("Error writing baseline {:?}", path))
+}
+
+/// Load the results of the test `id` from the baseline `name` in
+/// `baselines_dir`. Returns `None` if the baseline does not contain
+/// results for the test.
+pub fn load_baseline(
+    baselines_dir: &Path,
+    name: &str,
+    id: &str,
+) -> eyre::Result<Option<TestResults>> {
+    let path = baseline_path(baselines_dir, name, id);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let results_str = std::fs::read_to_string(&path)
+        .wrap_err_with(|| eyre::eyre!("Error reading baseline {:?}", path))?;
+    let results = ron::from_str(&results_str)
+        .wrap_err_with(|| eyre::eyre!("Error parsing baseline {:?}", path))?;
+    Ok(Some(results))
+}
+
+/// The status of a [Comparison].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonStatus {
+    /// The duration is within the warning tolerance.
+    Ok,
+    /// The duration regressed beyond [BaselineTolerance::warn].
+    Warn,
+    /// The duration regressed beyond [BaselineTolerance::fail].
+    Fail,
+    /// The round is missing from either the baseline or the current
+    /// results.
+    Missing,
+}
+
+impl std::fmt::Display for ComparisonStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok => f.write_str("ok"),
+            Self::Warn => f.write_str("WARN"),
+            Self::Fail => f.write_str("FAIL"),
+            Self::Missing => f.write_str("missing"),
+        }
+    }
+}
+
+/// The comparison of a duration in the results of a test against the
+/// baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub id: TestId,
+    /// The index of the round in the test (starting at 1).
+    pub round: usize,
+    /// The name of the duration being compared.
+    pub metric: &'static str,
+    pub baseline: Option<Duration>,
+    pub current: Option<Duration>,
+    pub status: ComparisonStatus,
+}
+
+impl Comparison {
+    /// The relative change in the duration compared to the baseline.
+    pub fn change(&self) -> Option<f64> {
+        match (self.baseline, self.current) {
+            (Some(baseline), Some(current)) if baseline > Duration::from_secs(0) => {
+                Some(current.as_secs_f64() / baseline.as_secs_f64() - 1.0)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Compare the `current` results of the test `id` against the
+/// `baseline` results, round by round.
+pub fn compare_results(
+    id: &str,
+    baseline: &TestResults,
+    current: &TestResults,
+    tolerance: &BaselineTolerance,
+) -> Vec<Comparison> {
+    // The durations compared for each round, and how to obtain them
+    // from the [RoundResults].
+    let metrics: [(&'static str, fn(&RoundResults) -> Duration); 2] = [
+        ("round", |results| results.total_round_duration),
+        ("aggregation", |results| results.aggregation_duration),
+    ];
+    let n_rounds = baseline
+        .round_results
+        .len()
+        .max(current.round_results.len());
+
+    (0..n_rounds)
+        .flat_map(|i| {
+            let baseline_round = baseline.round_results.get(i);
+            let current_round = current.round_results.get(i);
+            metrics.iter().map(move |(metric, duration)| {
+                let baseline = baseline_round.map(duration);
+                let current = current_round.map(duration);
+                let mut comparison = Comparison {
+                    id: id.to_string(),
+                    round: i + 1,
+                    metric: *metric,
+                    baseline,
+                    current,
+                    status: ComparisonStatus::Missing,
+                };
+
+                if baseline.is_some() && current.is_some() {
+                    let change = comparison.change().unwrap_or(0.0);
+                    comparison.status = if change > tolerance.fail {
+                        ComparisonStatus::Fail
+                    } else if change > tolerance.warn {
+                        ComparisonStatus::Warn
+                    } else {
+                        ComparisonStatus::Ok
+                    };
+                }
+
+                comparison
+            })
+        })
+        .collect()
+}
+
+/// Format the `comparisons` as a table with aligned columns.
+pub fn format_comparison_table(comparisons: &[Comparison]) -> String {
+    let format_optional = |duration: Option<Duration>| {
+        duration.map_or_else(
+            || "-".to_string(),
+            |duration| {
+                // Round to milliseconds to keep the table readable.
+                format_duration(Duration::from_millis(duration.as_millis() as u64)).to_string()
+            },
+        )
+    };
+
+    let header = [
+        "test", "round", "metric", "baseline", "current", "change", "status",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect::<Vec<String>>();
+    let rows: Vec<Vec<String>> = std::iter::once(header)
+        .chain(comparisons.iter().map(|comparison| {
+            vec![
+                comparison.id.clone(),
+                comparison.round.to_string(),
+                comparison.metric.to_string(),
+                format_optional(comparison.baseline),
+                format_optional(comparison.current),
+                comparison.change().map_or_else(
+                    || "-".to_string(),
+                    |change| format!("{:+.1}%", change * 100.0),
+                ),
+                comparison.status.to_string(),
+            ]
+        }))
+        .collect();
+
+    format_table(&rows)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{compare_results, format_comparison_table, BaselineTolerance, ComparisonStatus};
+    use crate::{
+        round_outcome::RoundOutcome,
+        test::{RoundResults, TestResults},
+    };
+
+    fn results(rounds: &[(u64, u64)]) -> TestResults {
+        TestResults {
+            round_results: rounds
+                .iter()
+                .map(|(round_millis, aggregation_millis)| RoundResults {
+                    outcome: RoundOutcome::Finish,
+                    total_round_duration: Duration::from_millis(*round_millis),
+                    aggregation_duration: Duration::from_millis(*aggregation_millis),
+                    timing: Default::default(),
+                    contributions: Default::default(),
+                    replacements: Default::default(),
+                    transcript_verification: None,
+                    resources: Default::default(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_compare_results() {
+        let baseline = results(&[(10000, 1000), (10000, 1000)]);
+        let current = results(&[(10500, 1200), (9000, 2000), (10000, 1000)]);
+        let comparisons = compare_results("1", &baseline, &current, &BaselineTolerance::default());
+
+        let statuses: Vec<(usize, &str, ComparisonStatus)> = comparisons
+            .iter()
+            .map(|comparison| (comparison.round, comparison.metric, comparison.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, "round", ComparisonStatus::Ok),
+                (1, "aggregation", ComparisonStatus::Warn),
+                (2, "round", ComparisonStatus::Ok),
+                (2, "aggregation", ComparisonStatus::Fail),
+                (3, "round", ComparisonStatus::Missing),
+                (3, "aggregation", ComparisonStatus::Missing),
+            ]
+        );
+
+        let table = format_comparison_table(&comparisons);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert!(lines[0].starts_with("test  round  metric"));
+        assert!(lines[4].contains("+100.0%"));
+        assert!(lines[4].ends_with("FAIL"));
+    }
+}
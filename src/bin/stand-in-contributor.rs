@@ -0,0 +1,96 @@
+//! A stand-in for the `setup1-contributor`, used to test the
+//! integration test harness itself.
+
+use aleo_setup_integration_test::{
+    contributor::ContributorKey,
+    stand_in::{
+        address_from_seed, unique_seed, Connection, ParticipantType, Request, Response,
+        CONTRIBUTION_DURATION, POLL_INTERVAL,
+    },
+};
+
+use eyre::Context;
+use structopt::StructOpt;
+
+use std::{fs::File, path::PathBuf};
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "stand-in-contributor")]
+enum Options {
+    /// Generate a contributor key file.
+    Generate {
+        /// Unused by the stand-in.
+        #[structopt(long = "passphrase")]
+        _passphrase: String,
+        #[structopt(long = "keys-path")]
+        keys_path: PathBuf,
+    },
+    /// Join the ceremony and contribute.
+    Contribute {
+        /// Unused by the stand-in.
+        #[structopt(long = "passphrase")]
+        _passphrase: String,
+        #[structopt(long = "api-url")]
+        api_url: String,
+        #[structopt(long = "keys-path")]
+        keys_path: PathBuf,
+    },
+}
+
+fn generate(keys_path: PathBuf) -> eyre::Result<()> {
+    let seed = format!("{}-{:?}", unique_seed(), keys_path);
+    let key = ContributorKey {
+        encrypted_seed: seed.clone(),
+        address: address_from_seed(&seed),
+    };
+
+    let key_file = File::create(&keys_path)
+        .wrap_err_with(|| eyre::eyre!("Error creating key file {:?}", keys_path))?;
+    serde_json::to_writer_pretty(key_file, &key)?;
+
+    println!("Generated contributor key with address {}", key.address);
+    Ok(())
+}
+
+fn contribute(api_url: String, keys_path: PathBuf) -> eyre::Result<()> {
+    let key_file = File::open(&keys_path)
+        .wrap_err_with(|| eyre::eyre!("Error opening key file {:?}", keys_path))?;
+    let key: ContributorKey = serde_json::from_reader(key_file)?;
+
+    println!("Contributing as {}.contributor", key.address);
+
+    // Keep contributing until the process is terminated. If the
+    // connection to the coordinator is lost, join the ceremony again.
+    loop {
+        let mut connection = Connection::join(&api_url, ParticipantType::Contributor, &key.address);
+
+        if let Err(error) = contribute_connection(&mut connection) {
+            println!("Lost connection to the coordinator: {}", error);
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+fn contribute_connection(connection: &mut Connection) -> eyre::Result<()> {
+    loop {
+        match connection.request(&Request::Next)? {
+            Response::Contribute { chunk } => {
+                println!("Contributing to chunk {}", chunk);
+                std::thread::sleep(CONTRIBUTION_DURATION);
+                connection.request(&Request::Contributed { chunk })?;
+                println!("Contributed to chunk {}", chunk);
+            }
+            Response::Wait => std::thread::sleep(POLL_INTERVAL),
+            unexpected => return Err(eyre::eyre!("Unexpected response: {}", unexpected)),
+        }
+    }
+}
+
+fn main() -> eyre::Result<()> {
+    match Options::from_args() {
+        Options::Generate { keys_path, .. } => generate(keys_path),
+        Options::Contribute {
+            api_url, keys_path, ..
+        } => contribute(api_url, keys_path),
+    }
+}
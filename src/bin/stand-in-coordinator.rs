@@ -0,0 +1,815 @@
+//! A stand-in for the `aleo-setup-coordinator`, used to test the
+//! integration test harness itself. It runs a simplified ceremony
+//! with the stand-in contributors and verifiers, printing the log
+//! lines expected by the integration test and writing a `state.json`
+//! and the aggregated output for each round to the transcript. The
+//! state of the ceremony is persisted (as json) to the configured
+//! `sqlite_file`, so that it can be recovered when the stand-in is
+//! restarted.
+
+use aleo_setup_integration_test::{
+    stand_in::{
+        aggregated_round_output, number_of_chunks, ParticipantType, Request, Response,
+        AGGREGATED_ROUND_FILE, AGGREGATION_DURATION, POLL_INTERVAL,
+    },
+    AleoPublicKey, Environment,
+};
+
+use eyre::Context;
+use serde::{Deserialize, Serialize};
+use structopt::StructOpt;
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Time that the queue needs to remain unchanged before the next
+/// round will be started, so that all the contributors started by
+/// the integration test have a chance to join.
+const QUEUE_SETTLE_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "stand-in-coordinator")]
+struct Options {
+    /// The coordinator `config.toml` file.
+    #[structopt(long = "config")]
+    config: PathBuf,
+}
+
+/// The subset of the `aleo-setup-coordinator` configuration file used
+/// by the stand-in.
+#[derive(Debug, Deserialize)]
+struct Configuration {
+    listen_address: SocketAddr,
+    sqlite_file: PathBuf,
+    setup: Environment,
+    replacement_contributors: Vec<AleoPublicKey>,
+    environment_parameters: EnvironmentParameters,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnvironmentParameters {
+    minimum_contributors_per_round: usize,
+    maximum_contributors_per_round: usize,
+    /// In seconds.
+    contributor_seen_timeout: i64,
+    /// In seconds.
+    participant_lock_timeout: i64,
+}
+
+/// The format of the `state.json` written to the transcript for each
+/// round.
+#[derive(Debug, Serialize)]
+struct RoundState {
+    #[serde(rename = "roundHeight")]
+    round_height: u64,
+    #[serde(rename = "contributorIds")]
+    contributor_ids: Vec<String>,
+    #[serde(rename = "verifierIds")]
+    verifier_ids: Vec<String>,
+    chunks: Vec<ChunkState>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChunkState {
+    #[serde(rename = "chunkId")]
+    chunk_id: String,
+    contributions: BTreeMap<String, ContributionState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ContributionState {
+    #[serde(rename = "contributorId")]
+    contributor_id: String,
+    #[serde(rename = "verifierId")]
+    verifier_id: Option<String>,
+    verified: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Phase {
+    /// Waiting for participants to join before starting the next
+    /// round.
+    Waiting,
+    /// The round is running.
+    Running,
+    /// The round is being aggregated.
+    Aggregating,
+}
+
+/// The tasks for a contributor in the current round.
+#[derive(Debug, Default)]
+struct ContributorTasks {
+    pending: VecDeque<u64>,
+    in_progress: Option<u64>,
+}
+
+impl ContributorTasks {
+    fn remaining(&self) -> Vec<u64> {
+        self.in_progress
+            .iter()
+            .chain(self.pending.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+/// The state of the stand-in ceremony which is persisted to storage,
+/// and recovered when the stand-in is restarted. The participants
+/// connected to the ceremony are not persisted because they need to
+/// connect to the restarted stand-in again.
+#[derive(Debug, Serialize, Deserialize)]
+struct Storage {
+    phase: Phase,
+    round: u64,
+    round_verifiers: Vec<AleoPublicKey>,
+    /// The remaining tasks for each contributor in the current round
+    /// (including tasks in progress).
+    contributors: HashMap<AleoPublicKey, Vec<u64>>,
+    round_contributors: Vec<AleoPublicKey>,
+    /// The pending verifications (including verifications in
+    /// flight).
+    pending_verifications: VecDeque<(u64, AleoPublicKey)>,
+    contributions: BTreeMap<u64, Vec<ContributionState>>,
+}
+
+/// The state of the stand-in ceremony.
+struct Ceremony {
+    storage_path: PathBuf,
+    transcript_dir: PathBuf,
+    chunks: u64,
+    minimum_contributors: usize,
+    maximum_contributors: usize,
+    /// Participants which have not been seen for longer than this are
+    /// dropped from the round.
+    seen_timeout: Duration,
+    /// Participants holding a lock (a task or verification in
+    /// progress) which have not been seen for longer than this are
+    /// dropped from the round.
+    lock_timeout: Duration,
+    replacement_addresses: Vec<AleoPublicKey>,
+    phase: Phase,
+    round: u64,
+    /// Contributors waiting to join the next round.
+    queue: Vec<AleoPublicKey>,
+    last_queue_change: Instant,
+    /// Connected replacement contributors which are not yet
+    /// participating in the round.
+    replacements: Vec<AleoPublicKey>,
+    verifiers: Vec<AleoPublicKey>,
+    round_verifiers: Vec<AleoPublicKey>,
+    /// The contributors participating in the current round.
+    contributors: HashMap<AleoPublicKey, ContributorTasks>,
+    /// The contributors which are part of the current round
+    /// (including replacements, excluding dropped contributors).
+    round_contributors: Vec<AleoPublicKey>,
+    pending_verifications: VecDeque<(u64, AleoPublicKey)>,
+    in_flight_verifications: HashMap<AleoPublicKey, Vec<(u64, AleoPublicKey)>>,
+    /// Verifiers which have been dropped from the current round.
+    dropped_verifiers: Vec<AleoPublicKey>,
+    contributions: BTreeMap<u64, Vec<ContributionState>>,
+    /// The time that each connected participant last made a request.
+    last_seen: HashMap<AleoPublicKey, Instant>,
+}
+
+impl Ceremony {
+    fn new(configuration: &Configuration, transcript_dir: PathBuf) -> Self {
+        let parameters = &configuration.environment_parameters;
+        let timeout = |seconds: i64| Duration::from_secs(seconds.max(0) as u64);
+        Self {
+            storage_path: configuration.sqlite_file.clone(),
+            transcript_dir,
+            chunks: number_of_chunks(configuration.setup),
+            minimum_contributors: configuration
+                .environment_parameters
+                .minimum_contributors_per_round,
+            maximum_contributors: configuration
+                .environment_parameters
+                .maximum_contributors_per_round,
+            seen_timeout: timeout(parameters.contributor_seen_timeout),
+            lock_timeout: timeout(parameters.participant_lock_timeout),
+            replacement_addresses: configuration.replacement_contributors.clone(),
+            phase: Phase::Waiting,
+            round: 0,
+            queue: Vec::new(),
+            last_queue_change: Instant::now(),
+            replacements: Vec::new(),
+            verifiers: Vec::new(),
+            round_verifiers: Vec::new(),
+            contributors: HashMap::new(),
+            round_contributors: Vec::new(),
+            pending_verifications: VecDeque::new(),
+            in_flight_verifications: HashMap::new(),
+            dropped_verifiers: Vec::new(),
+            contributions: BTreeMap::new(),
+            last_seen: HashMap::new(),
+        }
+    }
+
+    /// Recover the state of the ceremony from storage (if it exists).
+    /// Tasks and verifications which were in progress are assigned
+    /// again, and a round which was being aggregated is aggregated
+    /// again.
+    fn recover(&mut self) -> eyre::Result<()> {
+        if !self.storage_path.exists() {
+            return Ok(());
+        }
+
+        let storage_str = std::fs::read_to_string(&self.storage_path)
+            .wrap_err_with(|| eyre::eyre!("Error reading storage {:?}", self.storage_path))?;
+        let storage: Storage = serde_json::from_str(&storage_str)
+            .wrap_err_with(|| eyre::eyre!("Error parsing storage {:?}", self.storage_path))?;
+
+        self.phase = match storage.phase {
+            Phase::Aggregating => Phase::Running,
+            phase => phase,
+        };
+        self.round = storage.round;
+        self.round_verifiers = storage.round_verifiers;
+        self.contributors = storage
+            .contributors
+            .into_iter()
+            .map(|(address, remaining)| {
+                let tasks = ContributorTasks {
+                    pending: remaining.into_iter().collect(),
+                    in_progress: None,
+                };
+                (address, tasks)
+            })
+            .collect();
+        self.round_contributors = storage.round_contributors;
+        self.pending_verifications = storage.pending_verifications;
+        self.contributions = storage.contributions;
+
+        println!("Recovered ceremony in round {} from storage", self.round);
+        Ok(())
+    }
+
+    /// Persist the state of the ceremony to storage.
+    fn save(&self) -> eyre::Result<()> {
+        let storage = Storage {
+            phase: self.phase,
+            round: self.round,
+            round_verifiers: self.round_verifiers.clone(),
+            contributors: self
+                .contributors
+                .iter()
+                .map(|(address, tasks)| (address.clone(), tasks.remaining()))
+                .collect(),
+            round_contributors: self.round_contributors.clone(),
+            pending_verifications: self
+                .in_flight_verifications
+                .values()
+                .flatten()
+                .chain(self.pending_verifications.iter())
+                .cloned()
+                .collect(),
+            contributions: self.contributions.clone(),
+        };
+
+        // Write to a temporary file first so that the storage is
+        // never left partially written if the stand-in is killed.
+        let temp_path = self.storage_path.with_extension("tmp");
+        std::fs::write(&temp_path, serde_json::to_string(&storage)?)?;
+        std::fs::rename(&temp_path, &self.storage_path)?;
+        Ok(())
+    }
+
+    fn join(&mut self, participant_type: ParticipantType, address: AleoPublicKey) {
+        // A contributor in the current round is joining again after
+        // the stand-in was restarted.
+        if participant_type == ParticipantType::Contributor
+            && self.contributors.contains_key(&address)
+        {
+            println!("{}.contributor has rejoined round {}", address, self.round);
+            return;
+        }
+
+        println!("{}.{} has joined the queue", address, participant_type);
+        match participant_type {
+            ParticipantType::Contributor => {
+                if self.replacement_addresses.contains(&address) {
+                    self.replacements.push(address);
+                } else {
+                    self.queue.push(address);
+                    self.last_queue_change = Instant::now();
+                }
+            }
+            ParticipantType::Verifier => self.verifiers.push(address),
+        }
+    }
+
+    fn next(&mut self, participant_type: ParticipantType, address: &AleoPublicKey) -> Response {
+        if self.phase != Phase::Running {
+            return Response::Wait;
+        }
+
+        match participant_type {
+            ParticipantType::Contributor => match self.contributors.get_mut(address) {
+                Some(tasks) if tasks.in_progress.is_none() => match tasks.pending.pop_front() {
+                    Some(chunk) => {
+                        tasks.in_progress = Some(chunk);
+                        Response::Contribute { chunk }
+                    }
+                    None => Response::Wait,
+                },
+                _ => Response::Wait,
+            },
+            ParticipantType::Verifier if self.dropped_verifiers.contains(address) => Response::Wait,
+            ParticipantType::Verifier => match self.pending_verifications.pop_front() {
+                Some((chunk, contributor)) => {
+                    self.in_flight_verifications
+                        .entry(address.clone())
+                        .or_default()
+                        .push((chunk, contributor.clone()));
+                    Response::Verify { chunk, contributor }
+                }
+                None => Response::Wait,
+            },
+        }
+    }
+
+    fn contributed(&mut self, address: &AleoPublicKey, chunk: u64) -> eyre::Result<()> {
+        let tasks = self
+            .contributors
+            .get_mut(address)
+            .ok_or_else(|| eyre::eyre!("{} is not a contributor in this round", address))?;
+
+        if tasks.in_progress != Some(chunk) {
+            return Err(eyre::eyre!(
+                "{} is not contributing to chunk {}",
+                address,
+                chunk
+            ));
+        }
+        tasks.in_progress = None;
+
+        self.contributions
+            .entry(chunk)
+            .or_default()
+            .push(ContributionState {
+                contributor_id: format!("{}.contributor", address),
+                verifier_id: None,
+                verified: false,
+            });
+        self.pending_verifications
+            .push_back((chunk, address.clone()));
+
+        println!(
+            "{}.contributor added a contribution to chunk {}",
+            address, chunk
+        );
+        Ok(())
+    }
+
+    fn verified(
+        &mut self,
+        verifier: &AleoPublicKey,
+        chunk: u64,
+        contributor: &AleoPublicKey,
+    ) -> eyre::Result<()> {
+        let in_flight = self
+            .in_flight_verifications
+            .entry(verifier.clone())
+            .or_default();
+        let position = in_flight
+            .iter()
+            .position(|(c, a)| *c == chunk && a == contributor)
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "{} is not verifying the contribution to chunk {}",
+                    verifier,
+                    chunk
+                )
+            })?;
+        in_flight.swap_remove(position);
+
+        let contributor_id = format!("{}.contributor", contributor);
+        if let Some(contribution) = self
+            .contributions
+            .get_mut(&chunk)
+            .and_then(|c| c.iter_mut().find(|c| c.contributor_id == contributor_id))
+        {
+            contribution.verifier_id = Some(format!("{}.verifier", verifier));
+            contribution.verified = true;
+        }
+
+        println!(
+            "{}.verifier verified chunk {} contribution from {}.contributor",
+            verifier, chunk, contributor
+        );
+        Ok(())
+    }
+
+    /// Called when the connection to a participant is closed.
+    fn disconnected(&mut self, participant_type: ParticipantType, address: &AleoPublicKey) {
+        match participant_type {
+            ParticipantType::Contributor => {
+                self.queue.retain(|a| a != address);
+                self.replacements.retain(|a| a != address);
+                self.last_queue_change = Instant::now();
+
+                let remaining = match self.contributors.get(address) {
+                    Some(tasks) if self.phase == Phase::Running => tasks.remaining(),
+                    _ => Vec::new(),
+                };
+
+                if !remaining.is_empty() {
+                    self.drop_contributor(address, remaining);
+                }
+            }
+            ParticipantType::Verifier => {
+                self.verifiers.retain(|a| a != address);
+
+                // The verifier may already have been dropped from
+                // the round after a timeout.
+                if self.phase != Phase::Running {
+                    self.in_flight_verifications.remove(address);
+                } else if !self.dropped_verifiers.contains(address) {
+                    self.drop_verifier(address);
+                }
+            }
+        }
+    }
+
+    /// Drop the participants which have not been seen for longer than
+    /// the timeouts from the current round. Returns whether any
+    /// participants were dropped.
+    fn check_timeouts(&mut self) -> bool {
+        if self.phase != Phase::Running {
+            return false;
+        }
+
+        let seen_timeout = self.seen_timeout;
+        let lock_timeout = self.lock_timeout;
+        let timed_out = |last_seen: Option<&Instant>, has_lock: bool| match last_seen {
+            Some(last_seen) => {
+                last_seen.elapsed() > seen_timeout
+                    || (has_lock && last_seen.elapsed() > lock_timeout)
+            }
+            None => false,
+        };
+
+        let timed_out_contributors: Vec<(AleoPublicKey, Vec<u64>)> = self
+            .contributors
+            .iter()
+            .filter(|(address, tasks)| {
+                !tasks.remaining().is_empty()
+                    && timed_out(self.last_seen.get(*address), tasks.in_progress.is_some())
+            })
+            .map(|(address, tasks)| (address.clone(), tasks.remaining()))
+            .collect();
+        let mut dropped = false;
+        for (address, remaining) in timed_out_contributors {
+            println!(
+                "{}.contributor has not been seen for longer than the timeout",
+                address
+            );
+            self.drop_contributor(&address, remaining);
+            dropped = true;
+            if self.phase != Phase::Running {
+                return dropped;
+            }
+        }
+
+        let timed_out_verifiers: Vec<AleoPublicKey> = self
+            .round_verifiers
+            .iter()
+            .filter(|address| {
+                let has_lock = self
+                    .in_flight_verifications
+                    .get(*address)
+                    .map_or(false, |in_flight| !in_flight.is_empty());
+                timed_out(self.last_seen.get(*address), has_lock)
+            })
+            .cloned()
+            .collect();
+        for address in timed_out_verifiers {
+            println!(
+                "{}.verifier has not been seen for longer than the timeout",
+                address
+            );
+            self.drop_verifier(&address);
+            dropped = true;
+        }
+
+        dropped
+    }
+
+    /// Drop a verifier from the current round, assigning its
+    /// verifications in progress to the other verifiers.
+    fn drop_verifier(&mut self, address: &AleoPublicKey) {
+        println!("Dropping {}.verifier from the ceremony", address);
+        self.round_verifiers.retain(|a| a != address);
+        self.dropped_verifiers.push(address.clone());
+        let in_flight = self
+            .in_flight_verifications
+            .remove(address)
+            .unwrap_or_default();
+        self.pending_verifications.extend(in_flight);
+    }
+
+    /// Drop a contributor with `remaining` chunks from the current
+    /// round, assigning their tasks to a replacement contributor if
+    /// one is available.
+    fn drop_contributor(&mut self, address: &AleoPublicKey, remaining: Vec<u64>) {
+        println!("Dropping {}.contributor from the ceremony", address);
+        self.contributors.remove(address);
+        self.round_contributors.retain(|a| a != address);
+
+        if !self.replacements.is_empty() {
+            let replacement = self.replacements.remove(0);
+            println!(
+                "Assigning {} tasks from dropped contributor {}.contributor to replacement contributor {}.contributor",
+                remaining.len(),
+                address,
+                replacement
+            );
+            self.round_contributors.push(replacement.clone());
+            self.contributors.insert(
+                replacement,
+                ContributorTasks {
+                    pending: remaining.into_iter().collect(),
+                    in_progress: None,
+                },
+            );
+        } else if self.contributors.is_empty() {
+            self.round -= 1;
+            println!(
+                "No contributors remaining to reset and complete the current round. \
+                Rolling back to round {} to wait and accept new participants.",
+                self.round
+            );
+            self.phase = Phase::Waiting;
+            self.pending_verifications.clear();
+            self.in_flight_verifications.clear();
+            self.contributions.clear();
+        }
+    }
+
+    /// Whether the next round is ready to be started.
+    fn ready_to_start(&self) -> bool {
+        self.phase == Phase::Waiting
+            && !self.verifiers.is_empty()
+            && self.queue.len() >= self.minimum_contributors
+            && self.last_queue_change.elapsed() >= QUEUE_SETTLE_DURATION
+    }
+
+    fn start_round(&mut self) -> eyre::Result<()> {
+        self.round += 1;
+        self.phase = Phase::Running;
+
+        let n_contributors = self.queue.len().min(self.maximum_contributors);
+        let round_contributors: Vec<AleoPublicKey> = self.queue.drain(..n_contributors).collect();
+        let chunks = self.chunks;
+        self.contributors = round_contributors
+            .iter()
+            .map(|address| {
+                let tasks = ContributorTasks {
+                    pending: (0..chunks).collect(),
+                    in_progress: None,
+                };
+                (address.clone(), tasks)
+            })
+            .collect();
+        self.round_contributors = round_contributors;
+        self.round_verifiers = self.verifiers.clone();
+        self.dropped_verifiers.clear();
+        self.pending_verifications.clear();
+        self.in_flight_verifications.clear();
+        self.contributions.clear();
+
+        self.write_state()?;
+        println!("Advanced ceremony to round {}", self.round);
+        Ok(())
+    }
+
+    /// Whether all the contributions in the current round have been
+    /// made and verified.
+    fn round_complete(&self) -> bool {
+        self.phase == Phase::Running
+            && !self.contributors.is_empty()
+            && self
+                .contributors
+                .values()
+                .all(|tasks| tasks.remaining().is_empty())
+            && self.pending_verifications.is_empty()
+            && self.in_flight_verifications.values().all(Vec::is_empty)
+    }
+
+    /// Write the `state.json` for the current round to the
+    /// transcript.
+    fn write_state(&self) -> eyre::Result<()> {
+        let state = RoundState {
+            round_height: self.round,
+            contributor_ids: self
+                .round_contributors
+                .iter()
+                .map(|address| format!("{}.contributor", address))
+                .collect(),
+            verifier_ids: self
+                .round_verifiers
+                .iter()
+                .map(|address| format!("{}.verifier", address))
+                .collect(),
+            chunks: (0..self.chunks)
+                .map(|chunk| ChunkState {
+                    chunk_id: chunk.to_string(),
+                    contributions: self
+                        .contributions
+                        .get(&chunk)
+                        .into_iter()
+                        .flatten()
+                        .enumerate()
+                        .map(|(i, contribution)| ((i + 1).to_string(), contribution.clone()))
+                        .collect(),
+                })
+                .collect(),
+        };
+
+        let round_dir = self.transcript_dir.join(format!("round_{}", self.round));
+        std::fs::create_dir_all(&round_dir)
+            .wrap_err_with(|| eyre::eyre!("Error creating round directory {:?}", round_dir))?;
+        std::fs::write(
+            round_dir.join("state.json"),
+            serde_json::to_string_pretty(&state)?,
+        )?;
+        Ok(())
+    }
+
+    /// Write the aggregated output for the current round to the
+    /// transcript, see [aggregated_round_output()]. Expects the
+    /// `state.json` for the round to have already been written.
+    fn write_aggregated_output(&self) -> eyre::Result<()> {
+        let previous_output = if self.round > 1 {
+            let previous_round_dir = self
+                .transcript_dir
+                .join(format!("round_{}", self.round - 1));
+            std::fs::read_to_string(previous_round_dir.join(AGGREGATED_ROUND_FILE))?
+        } else {
+            String::new()
+        };
+
+        let round_dir = self.transcript_dir.join(format!("round_{}", self.round));
+        let state = std::fs::read_to_string(round_dir.join("state.json"))?;
+        std::fs::write(
+            round_dir.join(AGGREGATED_ROUND_FILE),
+            aggregated_round_output(&previous_output, &state),
+        )?;
+        Ok(())
+    }
+}
+
+/// Handle a single request from a participant, `participant` is
+/// `None` until the participant has joined the ceremony.
+fn handle_request(
+    ceremony: &mut Ceremony,
+    request: Request,
+    participant: &mut Option<(ParticipantType, AleoPublicKey)>,
+) -> eyre::Result<Response> {
+    match (request, participant.clone()) {
+        (
+            Request::Join {
+                participant_type,
+                address,
+            },
+            None,
+        ) => {
+            ceremony.join(participant_type, address.clone());
+            *participant = Some((participant_type, address));
+            Ok(Response::Ok)
+        }
+        (Request::Next, Some((participant_type, address))) => {
+            Ok(ceremony.next(participant_type, &address))
+        }
+        (Request::Contributed { chunk }, Some((ParticipantType::Contributor, address))) => {
+            ceremony.contributed(&address, chunk)?;
+            Ok(Response::Ok)
+        }
+        (Request::Verified { chunk, contributor }, Some((ParticipantType::Verifier, address))) => {
+            ceremony.verified(&address, chunk, &contributor)?;
+            Ok(Response::Ok)
+        }
+        (request, participant) => Err(eyre::eyre!(
+            "Unexpected request {} from participant {:?}",
+            request,
+            participant
+        )),
+    }
+}
+
+/// Handle the connection to a single participant.
+fn handle_connection(stream: TcpStream, ceremony: Arc<Mutex<Ceremony>>) -> eyre::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut participant: Option<(ParticipantType, AleoPublicKey)> = None;
+
+    for line in BufReader::new(stream).lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+
+        let response = line.parse::<Request>().and_then(|request| {
+            let mut ceremony = ceremony.lock().expect("error obtaining lock");
+            let response = handle_request(&mut ceremony, request, &mut participant)?;
+            if let Some((_, address)) = &participant {
+                ceremony.last_seen.insert(address.clone(), Instant::now());
+            }
+            ceremony.save()?;
+            Ok(response)
+        });
+
+        match response {
+            Ok(response) => {
+                if writeln!(writer, "{}", response).is_err() {
+                    break;
+                }
+            }
+            Err(error) => {
+                println!("Error while handling request {:?}: {}", line, error);
+                break;
+            }
+        }
+    }
+
+    if let Some((participant_type, address)) = participant {
+        let mut ceremony = ceremony.lock().expect("error obtaining lock");
+        ceremony.last_seen.remove(&address);
+        ceremony.disconnected(participant_type, &address);
+        ceremony.save()?;
+    }
+
+    Ok(())
+}
+
+fn main() -> eyre::Result<()> {
+    let options = Options::from_args();
+
+    let configuration_str = std::fs::read_to_string(&options.config)
+        .wrap_err("Error while reading coordinator config file")?;
+    let configuration: Configuration =
+        toml::from_str(&configuration_str).wrap_err("Error while parsing coordinator config")?;
+
+    let transcript_dir = match configuration.setup {
+        Environment::Development => PathBuf::from("transcript/development"),
+        _ => PathBuf::from("transcript"),
+    };
+
+    let mut ceremony = Ceremony::new(&configuration, transcript_dir);
+    ceremony.recover()?;
+    let ceremony = Arc::new(Mutex::new(ceremony));
+
+    let listener = TcpListener::bind(configuration.listen_address)
+        .wrap_err_with(|| eyre::eyre!("Error binding to {}", configuration.listen_address))?;
+
+    let listener_ceremony = ceremony.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let connection_ceremony = listener_ceremony.clone();
+            std::thread::spawn(move || {
+                if let Err(error) = handle_connection(stream, connection_ceremony) {
+                    println!("Error while handling connection: {}", error);
+                }
+            });
+        }
+    });
+
+    println!("Coordinator has booted up");
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let mut state = ceremony.lock().expect("error obtaining lock");
+        if state.check_timeouts() {
+            state.save()?;
+        }
+
+        if state.ready_to_start() {
+            state.start_round()?;
+            state.save()?;
+        } else if state.round_complete() {
+            let round = state.round;
+            state.phase = Phase::Aggregating;
+            state.save()?;
+            drop(state);
+
+            println!("Starting aggregation on round {}", round);
+            std::thread::sleep(AGGREGATION_DURATION);
+            println!("Round {} is aggregated", round);
+
+            let mut state = ceremony.lock().expect("error obtaining lock");
+            state.write_state()?;
+            state.write_aggregated_output()?;
+            state.phase = Phase::Waiting;
+            state.last_queue_change = Instant::now();
+            state.save()?;
+            println!("Round {} is finished", round);
+            println!("Waiting for participants to join round {}", round + 1);
+        }
+    }
+}
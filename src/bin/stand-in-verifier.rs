@@ -0,0 +1,61 @@
+//! A stand-in for the `setup1-verifier`, used to test the integration
+//! test harness itself.
+
+use aleo_setup_integration_test::stand_in::{
+    address_from_seed, Connection, ParticipantType, Request, Response, POLL_INTERVAL,
+    VERIFICATION_DURATION,
+};
+
+use eyre::Context;
+use structopt::StructOpt;
+
+use std::path::PathBuf;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "stand-in-verifier")]
+struct Options {
+    #[structopt(long = "api-url")]
+    api_url: String,
+    /// Path to the file containing the verifier's view key.
+    #[structopt(long = "view-key")]
+    view_key: PathBuf,
+}
+
+fn verify_connection(connection: &mut Connection) -> eyre::Result<()> {
+    loop {
+        match connection.request(&Request::Next)? {
+            Response::Verify { chunk, contributor } => {
+                println!(
+                    "Verifying contribution from {}.contributor to chunk {}",
+                    contributor, chunk
+                );
+                std::thread::sleep(VERIFICATION_DURATION);
+                connection.request(&Request::Verified { chunk, contributor })?;
+            }
+            Response::Wait => std::thread::sleep(POLL_INTERVAL),
+            unexpected => return Err(eyre::eyre!("Unexpected response: {}", unexpected)),
+        }
+    }
+}
+
+fn main() -> eyre::Result<()> {
+    let options = Options::from_args();
+
+    let view_key = std::fs::read_to_string(&options.view_key)
+        .wrap_err_with(|| eyre::eyre!("Error reading view key {:?}", options.view_key))?;
+    let address = address_from_seed(view_key.trim());
+
+    println!("Verifying as {}.verifier", address);
+
+    // Keep verifying until the process is terminated. If the
+    // connection to the coordinator is lost, join the ceremony again.
+    loop {
+        let mut connection =
+            Connection::join(&options.api_url, ParticipantType::Verifier, &address);
+
+        if let Err(error) = verify_connection(&mut connection) {
+            println!("Lost connection to the coordinator: {}", error);
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! A `verify-transcript` binary for the stand-ins, used to test the
+//! integration test harness itself. Verifies the transcript written
+//! by the `stand-in-coordinator` up to and including the specified
+//! round. See [aleo_setup_integration_test::transcript].
+
+use aleo_setup_integration_test::{
+    stand_in::{aggregated_round_output, AGGREGATED_ROUND_FILE},
+    Environment,
+};
+
+use eyre::Context;
+use serde::Deserialize;
+use structopt::StructOpt;
+
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "stand-in-verify-transcript")]
+struct Options {
+    /// Unused by the stand-in.
+    #[structopt(long = "environment")]
+    _environment: Environment,
+    /// The directory containing the transcript.
+    #[structopt(long = "transcript-dir")]
+    transcript_dir: PathBuf,
+    /// The last round of the transcript to verify.
+    #[structopt(long = "round")]
+    round: u64,
+}
+
+/// The subset of the round `state.json` checked by the stand-in.
+#[derive(Debug, Deserialize)]
+struct RoundState {
+    #[serde(rename = "roundHeight")]
+    round_height: u64,
+    chunks: Vec<ChunkState>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkState {
+    #[serde(rename = "chunkId")]
+    chunk_id: String,
+    contributions: std::collections::BTreeMap<String, ContributionState>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContributionState {
+    verified: bool,
+}
+
+/// Verify a single round of the transcript, returning its aggregated
+/// output.
+fn verify_round(transcript_dir: &Path, round: u64, previous_output: &str) -> eyre::Result<String> {
+    let round_dir = transcript_dir.join(format!("round_{}", round));
+
+    let state_str = std::fs::read_to_string(round_dir.join("state.json"))
+        .wrap_err_with(|| eyre::eyre!("Error reading state for round {}", round))?;
+    let state: RoundState = serde_json::from_str(&state_str)
+        .wrap_err_with(|| eyre::eyre!("Error parsing state for round {}", round))?;
+
+    if state.round_height != round {
+        return Err(eyre::eyre!(
+            "Round {} state has unexpected round height {}",
+            round,
+            state.round_height
+        ));
+    }
+
+    for chunk in &state.chunks {
+        if chunk.contributions.is_empty() {
+            return Err(eyre::eyre!(
+                "Chunk {} in round {} has no contributions",
+                chunk.chunk_id,
+                round
+            ));
+        }
+
+        if let Some((id, _)) = chunk
+            .contributions
+            .iter()
+            .find(|(_, contribution)| !contribution.verified)
+        {
+            return Err(eyre::eyre!(
+                "Contribution {} to chunk {} in round {} has not been verified",
+                id,
+                chunk.chunk_id,
+                round
+            ));
+        }
+    }
+
+    let output = std::fs::read_to_string(round_dir.join(AGGREGATED_ROUND_FILE))
+        .wrap_err_with(|| eyre::eyre!("Error reading aggregated output for round {}", round))?;
+
+    if output != aggregated_round_output(previous_output, &state_str) {
+        return Err(eyre::eyre!(
+            "Aggregated output for round {} does not match the transcript",
+            round
+        ));
+    }
+
+    Ok(output)
+}
+
+fn main() -> eyre::Result<()> {
+    let options = Options::from_args();
+
+    let mut previous_output = String::new();
+    for round in 1..=options.round {
+        previous_output = verify_round(&options.transcript_dir, round, &previous_output)?;
+        println!("Round {} is valid", round);
+    }
+
+    println!("Transcript up to round {} is valid", options.round);
+    Ok(())
+}
@@ -0,0 +1,15 @@
+//! A stand-in for the `view-key` binary from `setup1-cli-tools`, used
+//! to test the integration test harness itself. Prints a new
+//! verifier view key to `stdout`, followed by the address that the
+//! `stand-in-verifier` uses with the view key.
+
+use aleo_setup_integration_test::stand_in::{address_from_seed, unique_seed};
+
+fn main() {
+    let view_key = format!(
+        "AViewKey1{}",
+        &address_from_seed(&unique_seed()).as_ref()[5..]
+    );
+    println!("{}", view_key);
+    println!("{}", address_from_seed(&view_key));
+}
@@ -24,6 +24,13 @@ where
     MessageWaiter::spawn(
         move |message, mut state| {
             let _guard = span.enter();
+            if matches!(
+                message,
+                CeremonyMessage::Shutdown(_) | CeremonyMessage::RoundRestarted(_)
+            ) {
+                return WaiterClosureResult::Join(WaiterJoinCondition::Shutdown);
+            }
+
             if let CeremonyMessage::SuccessfulContribution {
                 contributor: _,
                 chunk: _,
@@ -1,12 +1,15 @@
 //! This module contains functions for running multiple integration
 //! tests.
 
-use std::{net::SocketAddr, path::PathBuf, str::FromStr};
+use std::{net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
 
 use serde::Deserialize;
 
 use crate::{
+    fuzz::FuzzConfig,
     git::RemoteGitRepo,
+    process::ProcessesConfig,
+    retention::RetentionConfig,
     test::{Repo, StateMonitorOptions, TestRound},
     Environment,
 };
@@ -76,6 +79,86 @@ pub struct Config {
     /// See [SingleTestOptions::aleo_setup_repo] for useage examples.
     #[serde(default = "default_aleo_setup_coordinator_repo")]
     pub aleo_setup_coordinator_repo: Repo,
+
+    /// (Optional) Directory containing the stand-in binaries built
+    /// from this crate (see [crate::stand_in]), e.g.
+    /// `"target/debug"`. If specified, these are used instead of the
+    /// `aleo-setup` and `aleo-setup-coordinator` binaries, and those
+    /// repositories will not be cloned or built. Useful for testing
+    /// the integration test itself. Default: `None`.
+    #[serde(default)]
+    pub stand_ins: Option<PathBuf>,
+
+    /// (Optional) The maximum number of tests to run at the same
+    /// time. When greater than `1`, each test is given its own free
+    /// ports for the coordinator and the state monitor, and the log
+    /// output for all tests is written to `integration-test.log` in
+    /// [Config::out_dir]. Default: `1`.
+    #[serde(default = "default_parallelism")]
+    pub parallelism: usize,
+
+    /// (Optional) Path to write a JUnit XML report to, containing a
+    /// test case for each test in the specification. Default: `None`.
+    #[serde(default)]
+    pub junit: Option<PathBuf>,
+
+    /// (Optional) The interval at which the CPU, memory and disk
+    /// usage of the processes started during each test are sampled
+    /// (see [crate::resources]). Default: `"1s"`.
+    #[serde(default = "default_resource_sample_interval", with = "humantime_serde")]
+    pub resource_sample_interval: Duration,
+
+    /// (Optional) Directory where the performance baselines are
+    /// stored, see [crate::baseline]. Default: `"baselines"`.
+    #[serde(default = "default_baselines_dir")]
+    pub baselines_dir: PathBuf,
+
+    /// (Optional) Save the results of each test which passes to the
+    /// baseline with this name in [Config::baselines_dir], replacing
+    /// any previously saved results for the test. Default: `None`.
+    #[serde(default)]
+    pub save_baseline: Option<String>,
+
+    /// (Optional) Compare the round and aggregation durations of each
+    /// test which passes against the baseline with this name in
+    /// [Config::baselines_dir]. Tests which regress beyond their
+    /// `baseline_tolerance` fail. Default: `None`.
+    #[serde(default)]
+    pub compare_baseline: Option<String>,
+
+    /// (Optional) The number of times to retry a test which fails,
+    /// for tests which don't specify their own `retries`. A test
+    /// which only passes on a retry is reported as flaky. Default:
+    /// `0`.
+    #[serde(default)]
+    pub retries: u32,
+
+    /// (Optional) Stop running tests after the first test fails (after
+    /// any retries). Tests which are already running in parallel are
+    /// allowed to finish, and the tests which have not started are
+    /// reported as skipped. Default: `false`.
+    #[serde(default)]
+    pub fail_fast: bool,
+
+    /// (Optional) What to keep of the out directory of each test after
+    /// the tests have run, and whether to create compressed bundles
+    /// of their logs. Default: keep everything, without bundles.
+    #[serde(default)]
+    pub retention: RetentionConfig,
+
+    /// (Optional) Extra environment variables, arguments and log
+    /// filters for the processes of the components of the ceremony,
+    /// for every test. Each test's `processes` are applied on top of
+    /// these. Default: none.
+    #[serde(default)]
+    pub processes: ProcessesConfig,
+
+    /// (Optional) The environment, coordinator overrides and number
+    /// of chunks of the ceremony that the tests generated with
+    /// `--fuzz` are run with. Default: the `Development` environment
+    /// without overrides.
+    #[serde(default)]
+    pub fuzz: FuzzConfig,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -146,6 +229,21 @@ fn default_build() -> bool {
     true
 }
 
+/// Default value for [Config::parallelism].
+fn default_parallelism() -> usize {
+    1
+}
+
+/// Default value for [Config::resource_sample_interval].
+fn default_resource_sample_interval() -> Duration {
+    Duration::from_secs(1)
+}
+
+/// Default value for [Config::baselines_dir].
+fn default_baselines_dir() -> PathBuf {
+    PathBuf::from("baselines")
+}
+
 /// Default value for [Config::install_prerequisites].
 fn default_install_prerequisites() -> bool {
     true
@@ -7,6 +7,7 @@ use crate::{
     process::MonitorProcessMessage,
     process::{
         default_parse_exit_status, fallible_monitor, run_monitor_process, MonitorProcessJoin,
+        ProcessConfig,
     },
     test::ContributorStartConfig,
     AleoPublicKey, CeremonyMessage, ContributorRef, Environment,
@@ -14,7 +15,7 @@ use crate::{
 
 use eyre::Context;
 use mpmc_bus::{Receiver, Sender};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use std::{
     fs::{File, OpenOptions},
@@ -22,7 +23,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct ContributorKey {
     #[serde(rename = "encryptedSeed")]
     pub encrypted_seed: String,
@@ -31,10 +32,11 @@ pub struct ContributorKey {
 }
 
 /// Use `setup1-contributor` to generate the contributor key file used
-/// in [run_contributor()].
+/// in [run_contributor()], encrypted with `passphrase`.
 pub fn generate_contributor_key(
     contributor_bin_path: impl AsRef<Path> + std::fmt::Debug,
     key_file_path: impl AsRef<Path>,
+    passphrase: &str,
 ) -> eyre::Result<ContributorKey> {
     tracing::info!("Generating contributor key.");
 
@@ -45,7 +47,7 @@ pub fn generate_contributor_key(
 
     subprocess::Exec::cmd(contributor_bin_path.as_ref())
         .arg("generate")
-        .args(&["--passphrase", "test"])
+        .args(&["--passphrase", passphrase])
         .args(&["--keys-path", keys_file_path_str])
         .join()
         .map_err(eyre::Error::from)
@@ -72,7 +74,7 @@ impl Contributor {
     /// The id used to reference this contributor by the coordinator,
     /// and within the ceremony transcript.
     pub fn id_on_coordinator(&self) -> String {
-        format!("{}.contributor", self.address)
+        self.as_contributor_ref().id_on_coordinator()
     }
 
     /// Obtains the [ContributorRef] referring to this [Contributor].
@@ -96,6 +98,8 @@ pub struct ContributorConfig {
     pub contributor_bin_path: PathBuf,
     /// The path to the key file used by this contributor.
     pub key_file_path: PathBuf,
+    /// The passphrase that the key file was encrypted with.
+    pub passphrase: String,
     /// What type of ceremony will be performed.
     pub environment: Environment,
     /// The url to connect to the coordinator.
@@ -111,6 +115,9 @@ pub struct ContributorConfig {
     pub drop: Option<DropContributorConfig>,
     /// When this contributor is configured to start during the round.
     pub start: ContributorStartConfig,
+    /// Extra environment variables, arguments and the log filter for
+    /// the contributor process.
+    pub process: ProcessConfig,
 }
 
 /// Allows the threads created by [run_contributor()] to be joined.
@@ -121,6 +128,11 @@ pub struct ContributorJoin {
 }
 
 impl ContributorJoin {
+    /// The id of the contributor process, see [MonitorProcessJoin::pid()].
+    pub fn pid(&self) -> Option<u32> {
+        self.monitor_process_join.pid()
+    }
+
     /// Joins the threads created by [run_contributor()].
     fn join(self) -> std::thread::Result<()> {
         self.monitor_process_join.join()?;
@@ -161,12 +173,11 @@ pub fn run_contributor(
 
     let exec = subprocess::Exec::cmd(&config.contributor_bin_path.canonicalize()?)
         .cwd(&config.out_dir)
-        .env("RUST_BACKTRACE", "1")
-        .env("RUST_LOG", "debug,hyper=warn")
         .arg("contribute")
-        .args(&["--passphrase", "test"])
+        .args(&["--passphrase", &config.passphrase])
         .args(&["--api-url", &config.coordinator_api_url])
         .args(&["--keys-path", &keys_file_path_string]);
+    let exec = config.process.apply(exec, Some("debug,hyper=warn"));
 
     let log_file_path = config.out_dir.join("contributor.log");
 
@@ -198,7 +209,7 @@ pub fn run_contributor(
                 .expect("Error receiving message from ceremony")
             {
                 CeremonyMessage::Shutdown(_) => break,
-                CeremonyMessage::RoundFinished(round) => {
+                CeremonyMessage::RoundFinished(round) | CeremonyMessage::RoundRestarted(round) => {
                     tracing::debug!(
                         "Finished contributing to round {}, terminating process.",
                         round
@@ -2,30 +2,45 @@
 //! rocket server.
 
 use std::{
+    collections::{BTreeSet, HashMap},
     fs::{File, OpenOptions},
     io::{BufRead, BufReader, Write},
     net::SocketAddr,
     num::{NonZeroU16, NonZeroU64, NonZeroU8, NonZeroUsize},
     path::{Path, PathBuf},
     str::FromStr,
+    sync::{Arc, Mutex},
+    thread::JoinHandle,
+    time::Duration,
 };
 
 use eyre::Context;
-use mpmc_bus::{Receiver, Sender};
+use mpmc_bus::{Receiver, Sender, TryRecvError};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use subprocess::Exec;
 
 use crate::{
     contributor::Contributor,
+    coordinator_fault::CoordinatorSignal,
+    join::MultiJoinable,
     process::{
         default_parse_exit_status, fallible_monitor, run_monitor_process, MonitorProcessJoin,
+        MonitorProcessMessage, ProcessConfig,
     },
     verifier::Verifier,
     AleoPublicKey, CeremonyMessage, ContributorRef, Environment, ParticipantRef, ShutdownReason,
     VerifierRef,
 };
 
+/// The default value for `minimum_contributors_per_round` in the
+/// configuration generated for the coordinator.
+pub const DEFAULT_MINIMUM_CONTRIBUTORS_PER_ROUND: usize = 1;
+
+/// The default value for `maximum_contributors_per_round` in the
+/// configuration generated for the coordinator.
+pub const DEFAULT_MAXIMUM_CONTRIBUTORS_PER_ROUND: usize = 5;
+
 /// The format of the configuration json configuration file, used with
 /// the `--config` command line option for `aleo-setup-coordinator`.
 #[derive(Debug, Serialize)]
@@ -128,6 +143,235 @@ pub struct TwitterSettings {
     pub consumer_secret: String,
 }
 
+impl CoordinatorTomlConfiguration {
+    /// Check that the configuration is consistent.
+    fn validate(&self) -> eyre::Result<()> {
+        self.environment_parameters.validate()
+    }
+}
+
+impl EnvironmentParameters {
+    /// The default parameters used in the generated coordinator
+    /// configuration.
+    fn default_parameters() -> Self {
+        Self {
+            minimum_contributors_per_round: NonZeroUsize::new(
+                DEFAULT_MINIMUM_CONTRIBUTORS_PER_ROUND,
+            )
+            .unwrap(),
+            maximum_contributors_per_round: NonZeroUsize::new(
+                DEFAULT_MAXIMUM_CONTRIBUTORS_PER_ROUND,
+            )
+            .unwrap(),
+            contributor_seen_timeout: 3600,
+            participant_lock_timeout: 900,
+            queue_seen_timeout: 3600,
+            disable_reliability_zeroing: true,
+        }
+    }
+
+    /// Check that the parameters are consistent.
+    fn validate(&self) -> eyre::Result<()> {
+        if self.minimum_contributors_per_round > self.maximum_contributors_per_round {
+            return Err(eyre::eyre!(
+                "Invalid coordinator configuration. `minimum_contributors_per_round` ({}) \
+                    should not exceed `maximum_contributors_per_round` ({}).",
+                self.minimum_contributors_per_round,
+                self.maximum_contributors_per_round,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Overrides for the `aleo-setup-coordinator` configuration generated
+/// by the integration test. Any value which is not specified uses the
+/// integration test's default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CoordinatorConfigOverrides {
+    /// Overrides for [RuntimeParameters].
+    pub runtime_parameters: RuntimeParametersOverrides,
+    /// Overrides for [EnvironmentParameters].
+    pub environment_parameters: EnvironmentParametersOverrides,
+    /// Overrides for [VerifierSettings].
+    pub verifier_settings: VerifierSettingsOverrides,
+    /// Overrides for [ReliabilityCheckSettings].
+    pub reliability_check: ReliabilityCheckSettingsOverrides,
+}
+
+/// Overrides for [RuntimeParameters].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuntimeParametersOverrides {
+    pub operator_update_loop_delay: Option<NonZeroU64>,
+    pub rayon_global_pool_threads: Option<NonZeroU16>,
+}
+
+/// Overrides for [EnvironmentParameters].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnvironmentParametersOverrides {
+    pub minimum_contributors_per_round: Option<NonZeroUsize>,
+    pub maximum_contributors_per_round: Option<NonZeroUsize>,
+    pub contributor_seen_timeout: Option<i64>,
+    pub participant_lock_timeout: Option<i64>,
+    pub queue_seen_timeout: Option<i64>,
+    pub disable_reliability_zeroing: Option<bool>,
+}
+
+/// Overrides for [VerifierSettings].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VerifierSettingsOverrides {
+    pub assigned_tasks_cache_ttl: Option<NonZeroU64>,
+    pub assigned_tasks_cache_records_cap: Option<NonZeroUsize>,
+}
+
+/// Overrides for [ReliabilityCheckSettings].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReliabilityCheckSettingsOverrides {
+    pub is_enabled: Option<bool>,
+    pub accept_threshold: Option<NonZeroU8>,
+    pub maximum_score: Option<u8>,
+    pub estimation_interval: Option<u8>,
+    pub number_of_challenges: Option<u8>,
+    pub challenge_size: Option<u64>,
+    pub total_size: Option<u8>,
+    pub batch_size: Option<u8>,
+}
+
+/// Replace `value` with `override_value` if it is `Some`.
+fn apply_override<T: Copy>(value: &mut T, override_value: Option<T>) {
+    if let Some(override_value) = override_value {
+        *value = override_value;
+    }
+}
+
+impl CoordinatorConfigOverrides {
+    /// The environment parameters of the generated coordinator
+    /// configuration, with these overrides applied.
+    fn environment_parameters(&self) -> EnvironmentParameters {
+        let mut parameters = EnvironmentParameters::default_parameters();
+        self.apply_environment_parameters(&mut parameters);
+        parameters
+    }
+
+    /// Check that the generated coordinator configuration will be
+    /// consistent with these overrides applied.
+    pub fn validate(&self) -> eyre::Result<()> {
+        self.environment_parameters().validate()
+    }
+
+    /// The `minimum_contributors_per_round` and
+    /// `maximum_contributors_per_round` of the generated coordinator
+    /// configuration, with these overrides applied.
+    pub fn contributors_per_round(&self) -> (usize, usize) {
+        let parameters = self.environment_parameters();
+        (
+            parameters.minimum_contributors_per_round.get(),
+            parameters.maximum_contributors_per_round.get(),
+        )
+    }
+
+    /// The `contributor_seen_timeout` and `participant_lock_timeout`
+    /// of the generated coordinator configuration, with these
+    /// overrides applied.
+    pub fn participant_timeouts(&self) -> (Duration, Duration) {
+        let parameters = self.environment_parameters();
+        let timeout = |seconds: i64| Duration::from_secs(seconds.max(0) as u64);
+        (
+            timeout(parameters.contributor_seen_timeout),
+            timeout(parameters.participant_lock_timeout),
+        )
+    }
+
+    /// Apply the environment parameter overrides to
+    /// `environment_config`.
+    fn apply_environment_parameters(&self, environment_config: &mut EnvironmentParameters) {
+        let environment = &self.environment_parameters;
+        apply_override(
+            &mut environment_config.minimum_contributors_per_round,
+            environment.minimum_contributors_per_round,
+        );
+        apply_override(
+            &mut environment_config.maximum_contributors_per_round,
+            environment.maximum_contributors_per_round,
+        );
+        apply_override(
+            &mut environment_config.contributor_seen_timeout,
+            environment.contributor_seen_timeout,
+        );
+        apply_override(
+            &mut environment_config.participant_lock_timeout,
+            environment.participant_lock_timeout,
+        );
+        apply_override(
+            &mut environment_config.queue_seen_timeout,
+            environment.queue_seen_timeout,
+        );
+        apply_override(
+            &mut environment_config.disable_reliability_zeroing,
+            environment.disable_reliability_zeroing,
+        );
+    }
+
+    /// Apply these overrides to the `toml_config`.
+    fn apply(&self, toml_config: &mut CoordinatorTomlConfiguration) {
+        let runtime = &self.runtime_parameters;
+        let runtime_config = &mut toml_config.runtime_parameters;
+        apply_override(
+            &mut runtime_config.operator_update_loop_delay,
+            runtime.operator_update_loop_delay,
+        );
+        apply_override(
+            &mut runtime_config.rayon_global_pool_threads,
+            runtime.rayon_global_pool_threads,
+        );
+
+        self.apply_environment_parameters(&mut toml_config.environment_parameters);
+
+        let verifier = &self.verifier_settings;
+        let verifier_config = &mut toml_config.verifier_settings;
+        apply_override(
+            &mut verifier_config.assigned_tasks_cache_ttl,
+            verifier.assigned_tasks_cache_ttl,
+        );
+        apply_override(
+            &mut verifier_config.assigned_tasks_cache_records_cap,
+            verifier.assigned_tasks_cache_records_cap,
+        );
+
+        let reliability = &self.reliability_check;
+        let reliability_config = &mut toml_config.reliability_check;
+        apply_override(&mut reliability_config.is_enabled, reliability.is_enabled);
+        apply_override(
+            &mut reliability_config.accept_threshold,
+            reliability.accept_threshold,
+        );
+        apply_override(
+            &mut reliability_config.maximum_score,
+            reliability.maximum_score,
+        );
+        apply_override(
+            &mut reliability_config.estimation_interval,
+            reliability.estimation_interval,
+        );
+        apply_override(
+            &mut reliability_config.number_of_challenges,
+            reliability.number_of_challenges,
+        );
+        apply_override(
+            &mut reliability_config.challenge_size,
+            reliability.challenge_size,
+        );
+        apply_override(&mut reliability_config.total_size, reliability.total_size);
+        apply_override(&mut reliability_config.batch_size, reliability.batch_size);
+    }
+}
+
 impl From<&CoordinatorConfig> for CoordinatorTomlConfiguration {
     fn from(config: &CoordinatorConfig) -> Self {
         let replacement_contributors = config
@@ -136,8 +380,8 @@ impl From<&CoordinatorConfig> for CoordinatorTomlConfiguration {
             .map(|c| c.address.clone())
             .collect();
 
-        Self {
-            listen_address: SocketAddr::from_str("0.0.0.0:9000").unwrap(),
+        let mut toml_config = Self {
+            listen_address: SocketAddr::from(([0, 0, 0, 0], config.port)),
             sqlite_file: "setup.db3".into(),
             setup: config.environment,
             replacement_contributors,
@@ -145,14 +389,7 @@ impl From<&CoordinatorConfig> for CoordinatorTomlConfiguration {
                 operator_update_loop_delay: NonZeroU64::new(10_000).unwrap(),
                 rayon_global_pool_threads: NonZeroU16::new(30).unwrap(),
             },
-            environment_parameters: EnvironmentParameters {
-                minimum_contributors_per_round: NonZeroUsize::new(1).unwrap(),
-                maximum_contributors_per_round: NonZeroUsize::new(5).unwrap(),
-                contributor_seen_timeout: 3600,
-                participant_lock_timeout: 900,
-                queue_seen_timeout: 3600,
-                disable_reliability_zeroing: true,
-            },
+            environment_parameters: EnvironmentParameters::default_parameters(),
             verifier_settings: VerifierSettings {
                 assigned_tasks_cache_ttl: NonZeroU64::new(60).unwrap(),
                 assigned_tasks_cache_records_cap: NonZeroUsize::new(1000).unwrap(),
@@ -171,13 +408,16 @@ impl From<&CoordinatorConfig> for CoordinatorTomlConfiguration {
                 consumer_token: "some_token".to_string(),
                 consumer_secret: "some_secret".to_string(),
             },
-        }
+        };
+
+        config.overrides.apply(&mut toml_config);
+        toml_config
     }
 }
 
 /// Configuration for the [run_coordinator()] function to run
 /// `aleo-setup-coordinator` rocket server.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CoordinatorConfig {
     /// The location of the `aleo-setup-coordinator` repository.
     pub crate_dir: PathBuf,
@@ -190,8 +430,15 @@ pub struct CoordinatorConfig {
     /// the coordinator will be stored (and the current working
     /// directory for the process).
     pub out_dir: PathBuf,
+    /// The port that the coordinator will listen on.
+    pub port: u16,
     /// List of replacement contributors in use for the ceremony.
     pub replacement_contributors: Vec<ContributorRef>,
+    /// Overrides for the generated coordinator configuration.
+    pub overrides: CoordinatorConfigOverrides,
+    /// Extra environment variables, arguments and the log filter for
+    /// the coordinator process.
+    pub process: ProcessConfig,
 }
 
 impl CoordinatorConfig {
@@ -204,54 +451,226 @@ impl CoordinatorConfig {
             self.out_dir.join("transcript")
         }
     }
+
+    /// The `contributor_seen_timeout` and `participant_lock_timeout`
+    /// of the generated coordinator configuration (including
+    /// overrides).
+    pub fn participant_timeouts(&self) -> (Duration, Duration) {
+        self.overrides.participant_timeouts()
+    }
+}
+
+/// Message to the [run_coordinator()] thread which supervises the
+/// coordinator process.
+#[derive(Debug, Clone, Copy)]
+pub enum CoordinatorMessage {
+    /// Stop the running coordinator process with the specified
+    /// signal, and start it again with the same configuration and
+    /// `out_dir`, so that it recovers its state from storage and the
+    /// transcript.
+    Restart(CoordinatorSignal),
+}
+
+/// A join handle for the thread created in [run_coordinator()].
+#[must_use]
+pub struct CoordinatorJoin {
+    supervisor_join: JoinHandle<eyre::Result<()>>,
+}
+
+impl std::fmt::Debug for CoordinatorJoin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CoordinatorJoin")
+    }
+}
+
+impl MultiJoinable for CoordinatorJoin {
+    fn join(self: Box<Self>) -> std::thread::Result<()> {
+        match self.supervisor_join.join() {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(error)) => Err(Box::new(error)),
+            Err(panic) => Err(panic),
+        }
+    }
 }
 
-/// Run the `aleo-setup-coordinator` rocket server.
+/// Run the `aleo-setup-coordinator` rocket server. The process is
+/// supervised by a thread which restarts it when it receives a
+/// [CoordinatorMessage::Restart] via the returned [Sender].
 pub fn run_coordinator(
     config: &CoordinatorConfig,
     ceremony_tx: Sender<CeremonyMessage>,
     ceremony_rx: Receiver<CeremonyMessage>,
-) -> eyre::Result<MonitorProcessJoin> {
+) -> eyre::Result<(CoordinatorJoin, Sender<CoordinatorMessage>)> {
     let span = tracing::error_span!("coordinator");
     let _guard = span.enter();
 
     let toml_config = CoordinatorTomlConfiguration::from(config);
+    toml_config.validate()?;
     let toml_config_str = toml::to_string_pretty(&toml_config)
         .wrap_err("Error while serializing coordinator toml config")?;
     let toml_config_path = config.out_dir.join("config.toml");
     std::fs::write(&toml_config_path, &toml_config_str)
         .wrap_err("Error while writing corodinator config.toml file")?;
 
+    // The state of the coordinator when the previous process exited,
+    // used to resume monitoring a restarted process.
+    let last_state: Arc<Mutex<Option<CoordinatorState>>> = Arc::new(Mutex::new(None));
+
+    let (process_join, process_monitor_tx) = start_coordinator_process(
+        config,
+        None,
+        last_state.clone(),
+        ceremony_tx.clone(),
+        ceremony_rx.clone(),
+    )?;
+
+    let coordinator_bus = mpmc_bus::Bus::new(5);
+    let coordinator_tx = coordinator_bus.broadcaster();
+
+    let supervisor_config = config.clone();
+    let supervisor_span = tracing::error_span!("supervisor");
+    let supervisor_join = std::thread::spawn(move || {
+        let _guard = supervisor_span.enter();
+        let mut process = Some((process_join, process_monitor_tx));
+
+        let result = supervise_coordinator(
+            &supervisor_config,
+            &mut process,
+            &last_state,
+            coordinator_bus.subscribe(),
+            &ceremony_tx,
+            ceremony_rx,
+        );
+
+        if let Err(error) = &result {
+            tracing::error!("{:?}", error);
+            // Tell the other threads to shut down, which also
+            // terminates the coordinator process (if it is running).
+            let _ = ceremony_tx.broadcast(CeremonyMessage::Shutdown(ShutdownReason::Error));
+        }
+
+        if let Some((process_join, _)) = process {
+            process_join
+                .join()
+                .map_err(|_| eyre::eyre!("Coordinator process monitor thread panicked"))?;
+        }
+
+        tracing::debug!("Thread closing gracefully.");
+        result
+    });
+
+    Ok((CoordinatorJoin { supervisor_join }, coordinator_tx))
+}
+
+/// Supervise the coordinator `process`, restarting it when a
+/// [CoordinatorMessage::Restart] is received via `coordinator_rx`,
+/// until the ceremony is shut down. `process` is `None` if the
+/// coordinator process could not be restarted.
+fn supervise_coordinator(
+    config: &CoordinatorConfig,
+    process: &mut Option<(MonitorProcessJoin, Sender<MonitorProcessMessage>)>,
+    last_state: &Arc<Mutex<Option<CoordinatorState>>>,
+    mut coordinator_rx: Receiver<CoordinatorMessage>,
+    ceremony_tx: &Sender<CeremonyMessage>,
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+) -> eyre::Result<()> {
+    loop {
+        // Sleep occasionally because otherwise this loop will run too fast.
+        std::thread::sleep(Duration::from_millis(100));
+
+        match coordinator_rx.try_recv() {
+            Ok(CoordinatorMessage::Restart(signal)) => {
+                tracing::info!("Restarting the coordinator process ({:?}).", signal);
+
+                if let Some((process_join, process_monitor_tx)) = process.take() {
+                    let monitor_message = match signal {
+                        CoordinatorSignal::Kill => MonitorProcessMessage::Kill,
+                        CoordinatorSignal::Term => MonitorProcessMessage::Terminate,
+                    };
+                    process_monitor_tx
+                        .broadcast(monitor_message)
+                        .wrap_err("Error sending message to coordinator process monitor")?;
+                    process_join
+                        .join()
+                        .map_err(|_| eyre::eyre!("Coordinator process monitor thread panicked"))?;
+                }
+
+                let resume_state = last_state.lock().expect("error obtaining lock").take();
+                let restarted = start_coordinator_process(
+                    config,
+                    resume_state,
+                    last_state.clone(),
+                    ceremony_tx.clone(),
+                    ceremony_rx.clone(),
+                )
+                .wrap_err("Error while restarting coordinator")?;
+                *process = Some(restarted);
+            }
+            Err(TryRecvError::Disconnected) => {
+                return Err(eyre::eyre!("`coordinator_rx` disconnected"));
+            }
+            Err(TryRecvError::Empty) => {}
+        }
+
+        // Process all the messages received since the last check.
+        loop {
+            match ceremony_rx.try_recv() {
+                Ok(CeremonyMessage::Shutdown(_)) => return Ok(()),
+                Ok(_) => {}
+                Err(TryRecvError::Disconnected) => {
+                    return Err(eyre::eyre!("`ceremony_rx` disconnected"));
+                }
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+    }
+}
+
+/// Start the `aleo-setup-coordinator` process. Its output is
+/// monitored by a [CoordinatorStateReporter], which resumes from
+/// `resume_state` if the process is being restarted. The state of the
+/// reporter is stored in `last_state` once the process has exited.
+fn start_coordinator_process(
+    config: &CoordinatorConfig,
+    resume_state: Option<CoordinatorState>,
+    last_state: Arc<Mutex<Option<CoordinatorState>>>,
+    ceremony_tx: Sender<CeremonyMessage>,
+    ceremony_rx: Receiver<CeremonyMessage>,
+) -> eyre::Result<(MonitorProcessJoin, Sender<MonitorProcessMessage>)> {
     tracing::info!("Starting setup coordinator.");
 
+    let toml_config_path = config.out_dir.join("config.toml");
     let exec = Exec::cmd(config.setup_coordinator_bin.canonicalize()?)
         .cwd(&config.out_dir)
-        .env("RUST_BACKTRACE", "1")
-        .env("RUST_LOG", "debug")
         .arg("--config")
         .arg(
             toml_config_path
                 .canonicalize()
                 .wrap_err("cannot canonicalize toml config path")?,
         );
+    let exec = config.process.apply(exec, Some("debug"));
 
     let log_file_path = config.out_dir.join("coordinator.log");
 
-    let (join, _) = run_monitor_process(
+    run_monitor_process(
         "coordinator".to_string(),
         exec,
         default_parse_exit_status,
         ceremony_tx,
         ceremony_rx,
         fallible_monitor(move |stdout, ceremony_tx, _monitor_tx| {
-            monitor_coordinator(stdout, ceremony_tx, &log_file_path)
+            monitor_coordinator(
+                stdout,
+                ceremony_tx,
+                &log_file_path,
+                resume_state,
+                &last_state,
+            )
         }),
-    )?;
-
-    Ok(join)
+    )
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
 enum CoordinatorState {
     /// The process has just started.
@@ -276,6 +695,9 @@ enum CoordinatorState {
 struct CoordinatorStateReporter {
     ceremony_tx: Sender<CeremonyMessage>,
     current_state: CoordinatorState,
+    /// The state of the coordinator before the process was
+    /// restarted, which is resumed once the process has booted up.
+    resume_state: Option<CoordinatorState>,
 }
 
 lazy_static::lazy_static! {
@@ -286,7 +708,9 @@ lazy_static::lazy_static! {
     static ref ROUND_FINISHED_RE: Regex = Regex::new(".*Round (?P<round>[0-9]+) is finished.*").unwrap();
     static ref DROPPED_PARTICIPANT_RE: Regex = Regex::new(".*Dropping (?P<address>aleo[a-z0-9]+)[.](?P<participant_type>contributor|verifier) from the ceremony").unwrap();
     static ref SUCCESSFUL_CONTRIBUTION_RE: Regex = Regex::new(".*((?P<address>aleo[a-z0-9]+)[.]contributor) added a contribution to chunk (?P<chunk>[0-9]+)").unwrap();
-    static ref ROUND_RESTARTED_NO_CONTRIBUTORS_RE: Regex = Regex::new(".*No contributors remaining to reset and complete the current round. Rolling back to round 0 to wait and accept new participants.*").unwrap();
+    static ref SUCCESSFUL_VERIFICATION_RE: Regex = Regex::new(".*((?P<address>aleo[a-z0-9]+)[.]verifier) verified chunk (?P<chunk>[0-9]+)").unwrap();
+    static ref CONTRIBUTOR_REPLACED_RE: Regex = Regex::new(".*Assigning (?P<tasks>[0-9]+) tasks from dropped contributor (?P<dropped>aleo[a-z0-9]+)[.]contributor to replacement contributor (?P<replacement>aleo[a-z0-9]+)[.]contributor").unwrap();
+    static ref ROUND_RESTARTED_NO_CONTRIBUTORS_RE: Regex = Regex::new(".*No contributors remaining to reset and complete the current round. Rolling back to round [0-9]+ to wait and accept new participants.*").unwrap();
 }
 
 impl CoordinatorStateReporter {
@@ -296,9 +720,56 @@ impl CoordinatorStateReporter {
         Self {
             ceremony_tx,
             current_state: CoordinatorState::ProcessStarted,
+            resume_state: None,
+        }
+    }
+
+    /// Create a new [CoordinatorStateReporter] with the state that
+    /// the process has just been restarted, after the previous
+    /// process exited in the `previous_state`.
+    fn process_restarted(
+        ceremony_tx: Sender<CeremonyMessage>,
+        previous_state: CoordinatorState,
+    ) -> Self {
+        Self {
+            ceremony_tx,
+            current_state: CoordinatorState::ProcessStarted,
+            resume_state: Some(previous_state),
         }
     }
 
+    /// Resynchronise with the state of the coordinator once a
+    /// restarted process has booted up. The coordinator recovers the
+    /// round it was running from storage, so the `previous_state` is
+    /// resumed without broadcasting the messages for that state again.
+    fn resynchronise(&mut self, previous_state: CoordinatorState) -> eyre::Result<()> {
+        self.ceremony_tx
+            .broadcast(CeremonyMessage::CoordinatorRestarted)?;
+
+        self.current_state = match previous_state {
+            // The previous process exited before it had booted up.
+            CoordinatorState::ProcessStarted => {
+                self.ceremony_tx
+                    .broadcast(CeremonyMessage::RoundWaitingForParticipants(1))?;
+                CoordinatorState::RoundWaitingForParticipants(1)
+            }
+            // The previous process exited before the next round was
+            // awaiting participants.
+            CoordinatorState::RoundFinished(round) => {
+                self.ceremony_tx
+                    .broadcast(CeremonyMessage::RoundWaitingForParticipants(round + 1))?;
+                CoordinatorState::RoundWaitingForParticipants(round + 1)
+            }
+            state => state,
+        };
+
+        tracing::debug!(
+            "Coordinator process has restarted, resuming in state {:?}",
+            self.current_state
+        );
+        Ok(())
+    }
+
     /// Check whether a participant has been dropped from the round
     /// (and broadcast this fact with [CeremonyMessage::ParticipantDropped]).
     fn check_participant_dropped(&mut self, line: &str) -> eyre::Result<()> {
@@ -333,6 +804,46 @@ impl CoordinatorStateReporter {
         Ok(())
     }
 
+    /// Check whether the tasks of a dropped contributor have been
+    /// assigned to a replacement contributor (and broadcast this fact
+    /// with [CeremonyMessage::ContributorReplaced]). This matches a
+    /// line printed by the `stand-in-coordinator`, which is not
+    /// printed by the `aleo-setup-coordinator`.
+    fn check_contributor_replaced(&mut self, line: &str) -> eyre::Result<()> {
+        if let Some(captures) = CONTRIBUTOR_REPLACED_RE.captures(line) {
+            let capture = |name: &str| {
+                captures
+                    .name(name)
+                    .unwrap_or_else(|| panic!("expected {} group to be captured", name))
+                    .as_str()
+            };
+
+            let tasks = u64::from_str(capture("tasks"))?;
+            let dropped = ContributorRef {
+                address: AleoPublicKey::from_str(capture("dropped"))?,
+            };
+            let replacement = ContributorRef {
+                address: AleoPublicKey::from_str(capture("replacement"))?,
+            };
+
+            tracing::debug!(
+                "Contributor {} replaced by replacement contributor {} for {} tasks.",
+                &dropped,
+                &replacement,
+                tasks
+            );
+
+            self.ceremony_tx
+                .broadcast(CeremonyMessage::ContributorReplaced {
+                    dropped,
+                    replacement,
+                    tasks,
+                })?;
+        }
+
+        Ok(())
+    }
+
     /// Parse stdout line from the `coordinator` process, broadcast
     /// messages to the ceremony when the coordinator state changes.
     /// Keeps track of the current state of the ceremony.
@@ -343,10 +854,15 @@ impl CoordinatorStateReporter {
         match self.current_state {
             CoordinatorState::ProcessStarted => {
                 if BOOTED_RE.is_match(line) {
-                    tracing::debug!("Coordinator process has started");
-                    self.ceremony_tx
-                        .broadcast(CeremonyMessage::RoundWaitingForParticipants(1))?;
-                    self.current_state = CoordinatorState::RoundWaitingForParticipants(1);
+                    match self.resume_state.take() {
+                        Some(previous_state) => self.resynchronise(previous_state)?,
+                        None => {
+                            tracing::debug!("Coordinator process has started");
+                            self.ceremony_tx
+                                .broadcast(CeremonyMessage::RoundWaitingForParticipants(1))?;
+                            self.current_state = CoordinatorState::RoundWaitingForParticipants(1);
+                        }
+                    }
                 }
             }
             CoordinatorState::RoundWaitingForParticipants(round) => {
@@ -368,6 +884,7 @@ impl CoordinatorStateReporter {
                 // Check whether any participants have beend dropped
                 // while the round is running.
                 self.check_participant_dropped(line)?;
+                self.check_contributor_replaced(line)?;
                 if ROUND_STARTED_AGGREGATION_RE.is_match(line) {
                     tracing::debug!("Detected that round {} is has started running.", round);
                     self.ceremony_tx
@@ -381,8 +898,14 @@ impl CoordinatorStateReporter {
                         round
                     );
                     self.ceremony_tx
-                        .broadcast(CeremonyMessage::Shutdown(ShutdownReason::TestFinished))?;
-                    self.current_state = CoordinatorState::RoundFinished(round);
+                        .broadcast(CeremonyMessage::RoundRestarted(round))?;
+                    // The coordinator rolls back to the previous
+                    // round, and waits for participants to join this
+                    // round again.
+                    self.ceremony_tx
+                        .broadcast(CeremonyMessage::RoundWaitingForParticipants(round))?;
+                    self.current_state = CoordinatorState::RoundWaitingForParticipants(round);
+                    return Ok(());
                 }
 
                 if let Some(captures) = SUCCESSFUL_CONTRIBUTION_RE.captures(line) {
@@ -414,6 +937,33 @@ impl CoordinatorStateReporter {
                             chunk,
                         })?;
                 }
+
+                if let Some(captures) = SUCCESSFUL_VERIFICATION_RE.captures(line) {
+                    let address_str = captures
+                        .name("address")
+                        .expect("expected address group to be captured")
+                        .as_str()
+                        .to_string();
+
+                    let chunk = u64::from_str(
+                        captures
+                            .name("chunk")
+                            .expect("exprected chunk address to be captured")
+                            .as_str(),
+                    )?;
+
+                    let address = AleoPublicKey::from_str(&address_str)?;
+
+                    tracing::debug!(
+                        "Verifier {} made a successful verification of chunk {}.",
+                        &address,
+                        &chunk
+                    );
+
+                    let verifier = VerifierRef { address };
+                    self.ceremony_tx
+                        .broadcast(CeremonyMessage::SuccessfulVerification { verifier, chunk })?;
+                }
             }
             CoordinatorState::RoundAggregating(round) => {
                 if ROUND_AGGREGATED_RE.is_match(line) {
@@ -446,14 +996,23 @@ impl CoordinatorStateReporter {
 /// Monitor the setup coordinator. Parses the `stderr`/`stdout` and
 /// emits messages/alters state when certain events occur, and also
 /// pipes the output to the [tracing::debug!()], and
-/// `coordinator_log.txt` log file.
+/// `coordinator_log.txt` log file. If the process has been restarted
+/// then `resume_state` is the state of the previous process. The
+/// state when the process exits is stored in `last_state`.
 fn monitor_coordinator(
     stdout: File,
     ceremony_tx: Sender<CeremonyMessage>,
     log_file_path: impl AsRef<Path>,
+    resume_state: Option<CoordinatorState>,
+    last_state: &Mutex<Option<CoordinatorState>>,
 ) -> eyre::Result<()> {
     let buf_pipe = BufReader::new(stdout);
-    let mut state_reporter = CoordinatorStateReporter::process_started(ceremony_tx);
+    let mut state_reporter = match resume_state {
+        Some(previous_state) => {
+            CoordinatorStateReporter::process_restarted(ceremony_tx, previous_state)
+        }
+        None => CoordinatorStateReporter::process_started(ceremony_tx),
+    };
 
     let mut log_file = OpenOptions::new()
         .append(true)
@@ -481,6 +1040,8 @@ fn monitor_coordinator(
         }
     }
 
+    *last_state.lock().expect("error obtaining lock") = Some(state_reporter.current_state);
+
     Ok(())
 }
 
@@ -492,16 +1053,31 @@ struct RoundState {
     /// The ids of the verifiers in the round.
     #[serde(rename = "verifierIds")]
     verifier_ids: Vec<String>,
+    /// The state of each chunk in the round.
+    #[serde(default)]
+    chunks: Vec<RoundChunkState>,
 }
 
-/// Check that the specified participants are in the specified round
-/// transcript.
-pub fn check_participants_in_round(
-    config: &CoordinatorConfig,
-    round: u64,
-    contributors: &[Contributor],
-    verifiers: &[Verifier],
-) -> eyre::Result<()> {
+#[derive(Deserialize)]
+struct RoundChunkState {
+    /// The id of the chunk.
+    #[serde(rename = "chunkId")]
+    chunk_id: String,
+    /// The contributions made to the chunk, indexed by contribution
+    /// id.
+    #[serde(default)]
+    contributions: HashMap<String, RoundContributionState>,
+}
+
+#[derive(Deserialize)]
+struct RoundContributionState {
+    /// The id of the contributor who made this contribution.
+    #[serde(rename = "contributorId")]
+    contributor_id: Option<String>,
+}
+
+/// Read the `state.json` for the specified round from the transcript.
+fn read_round_state(config: &CoordinatorConfig, round: u64) -> eyre::Result<RoundState> {
     let state_file = config
         .transcript_dir()
         .join(format!("round_{}", round))
@@ -510,8 +1086,19 @@ pub fn check_participants_in_round(
     let state_file_str = std::fs::read_to_string(&state_file)
         .wrap_err_with(|| eyre::eyre!("Unable to read state file: {:?}", &state_file))?;
 
-    let state: RoundState = serde_json::from_str(&state_file_str)
-        .wrap_err_with(|| eyre::eyre!("Unable to deserialize state file: {:?}", state_file))?;
+    serde_json::from_str(&state_file_str)
+        .wrap_err_with(|| eyre::eyre!("Unable to deserialize state file: {:?}", state_file))
+}
+
+/// Check that the specified participants are in the specified round
+/// transcript. Verifiers whose address is unknown are not checked.
+pub fn check_participants_in_round(
+    config: &CoordinatorConfig,
+    round: u64,
+    contributors: &[Contributor],
+    verifiers: &[Verifier],
+) -> eyre::Result<()> {
+    let state = read_round_state(config, round)?;
 
     for contributor in contributors {
         state
@@ -526,5 +1113,454 @@ pub fn check_participants_in_round(
             })?;
     }
 
+    for verifier_id in verifiers.iter().filter_map(Verifier::id_on_coordinator) {
+        state
+            .verifier_ids
+            .iter()
+            .find(|round_verifier_id| round_verifier_id == &&verifier_id)
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "Unable to find verifier {} in round state file",
+                    verifier_id
+                )
+            })?;
+    }
+
     Ok(())
 }
+
+/// Read the ids of the contributors and verifiers in the specified
+/// round from the transcript.
+pub fn read_round_participant_ids(
+    config: &CoordinatorConfig,
+    round: u64,
+) -> eyre::Result<BTreeSet<String>> {
+    let state = read_round_state(config, round)?;
+    Ok(state
+        .contributor_ids
+        .into_iter()
+        .chain(state.verifier_ids.into_iter())
+        .collect())
+}
+
+/// Check that the ids of the contributors and verifiers in the
+/// specified round in the transcript are exactly the `expected` ids.
+pub fn check_round_participant_ids(
+    config: &CoordinatorConfig,
+    round: u64,
+    expected: &BTreeSet<String>,
+) -> eyre::Result<()> {
+    let participant_ids = read_round_participant_ids(config, round)?;
+
+    if &participant_ids != expected {
+        return Err(eyre::eyre!(
+            "The participants in round {} state file differ from those expected ({})",
+            round,
+            id_set_difference(&participant_ids, expected)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Expectations for the state of a round in the transcript
+/// (`round_N/state.json`), checked after the round has finished.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectRoundState {
+    /// (Optional) Check that `contributorIds` contains exactly the
+    /// contributors which started the round and were not dropped
+    /// (ignoring replacement contributors). Default: `true`.
+    #[serde(default = "default_true")]
+    pub contributors: bool,
+    /// (Optional) Check that `verifierIds` contains exactly the
+    /// verifiers running during the round which were not dropped.
+    /// This check is skipped (with a warning) if the address of any
+    /// of the verifiers is unknown. Default: `true`.
+    #[serde(default = "default_true")]
+    pub verifiers: bool,
+    /// (Optional) The expected number of chunks in the round. If
+    /// `None` then the number of chunks is not checked. Default:
+    /// `None`.
+    #[serde(default)]
+    pub chunks: Option<usize>,
+    /// (Optional) Check that every chunk has a contribution from each
+    /// of the remaining (non-replacement) contributors in the round.
+    /// Default: `true`.
+    #[serde(default = "default_true")]
+    pub contributions: bool,
+    /// (Optional) Check that replacement contributors are present in
+    /// `contributorIds` if (and only if) contributors were dropped
+    /// during the round, and replacement contributors are available.
+    /// Default: `true`.
+    #[serde(default = "default_true")]
+    pub replacements: bool,
+}
+
+impl Default for ExpectRoundState {
+    fn default() -> Self {
+        Self {
+            contributors: true,
+            verifiers: true,
+            chunks: None,
+            contributions: true,
+            replacements: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// The participants expected to be in the state of a round, see
+/// [check_round_state()].
+pub struct ExpectedRoundParticipants<'a> {
+    /// The contributors which started the round, and were not
+    /// dropped (excluding replacement contributors).
+    pub contributors: &'a [Contributor],
+    /// The verifiers which were running during the round, and were
+    /// not dropped.
+    pub verifiers: &'a [Verifier],
+    /// Whether any contributors were dropped during the round.
+    pub contributors_dropped: bool,
+}
+
+/// Format the difference between the `actual` and `expected` ids.
+fn id_set_difference(actual: &BTreeSet<String>, expected: &BTreeSet<String>) -> String {
+    format!(
+        "missing: {:?}, unexpected: {:?}",
+        expected.difference(actual).collect::<Vec<_>>(),
+        actual.difference(expected).collect::<Vec<_>>(),
+    )
+}
+
+/// Check the state of the specified round in the transcript against
+/// the `expect` configuration, after the round has finished. Returns
+/// an error naming each field of the state which differed from what
+/// was expected.
+pub fn check_round_state(
+    config: &CoordinatorConfig,
+    round: u64,
+    expect: &ExpectRoundState,
+    participants: &ExpectedRoundParticipants,
+) -> eyre::Result<()> {
+    let state = read_round_state(config, round)?;
+    let mut differences: Vec<String> = Vec::new();
+
+    let replacement_ids: BTreeSet<String> = config
+        .replacement_contributors
+        .iter()
+        .map(ContributorRef::id_on_coordinator)
+        .collect();
+    let contributor_ids: BTreeSet<String> = state
+        .contributor_ids
+        .iter()
+        .filter(|id| !replacement_ids.contains(*id))
+        .cloned()
+        .collect();
+    let expected_contributor_ids: BTreeSet<String> = participants
+        .contributors
+        .iter()
+        .map(Contributor::id_on_coordinator)
+        .collect();
+
+    if expect.contributors && contributor_ids != expected_contributor_ids {
+        differences.push(format!(
+            "`contributorIds` does not contain the expected contributors ({})",
+            id_set_difference(&contributor_ids, &expected_contributor_ids)
+        ));
+    }
+
+    if expect.verifiers {
+        let unknown_verifiers: Vec<&str> = participants
+            .verifiers
+            .iter()
+            .filter(|verifier| verifier.address.is_none())
+            .map(|verifier| verifier.id.as_str())
+            .collect();
+
+        if unknown_verifiers.is_empty() {
+            let expected_verifier_ids: BTreeSet<String> = participants
+                .verifiers
+                .iter()
+                .filter_map(Verifier::id_on_coordinator)
+                .collect();
+            let verifier_ids: BTreeSet<String> = state.verifier_ids.iter().cloned().collect();
+
+            if verifier_ids != expected_verifier_ids {
+                differences.push(format!(
+                    "`verifierIds` does not contain the expected verifiers ({})",
+                    id_set_difference(&verifier_ids, &expected_verifier_ids)
+                ));
+            }
+        } else {
+            tracing::warn!(
+                "Not checking `verifierIds` because the addresses of verifiers {} are unknown.",
+                unknown_verifiers.join(", ")
+            );
+        }
+    }
+
+    if let Some(chunks) = expect.chunks {
+        if state.chunks.len() != chunks {
+            differences.push(format!(
+                "`chunks` contains {} chunks, expected {}",
+                state.chunks.len(),
+                chunks
+            ));
+        }
+    }
+
+    if expect.contributions {
+        for chunk in &state.chunks {
+            let chunk_contributor_ids: BTreeSet<String> = chunk
+                .contributions
+                .values()
+                .filter_map(|contribution| contribution.contributor_id.clone())
+                .collect();
+            let missing: Vec<&String> = expected_contributor_ids
+                .difference(&chunk_contributor_ids)
+                .collect();
+
+            if !missing.is_empty() {
+                differences.push(format!(
+                    "`chunks[{}].contributions` is missing contributions from: {:?}",
+                    chunk.chunk_id, missing
+                ));
+            }
+        }
+    }
+
+    if expect.replacements && !replacement_ids.is_empty() {
+        let round_replacements: Vec<&String> = state
+            .contributor_ids
+            .iter()
+            .filter(|id| replacement_ids.contains(*id))
+            .collect();
+
+        if participants.contributors_dropped && round_replacements.is_empty() {
+            differences.push(
+                "`contributorIds` does not contain any replacement contributors, \
+                    expected at least one because contributors were dropped"
+                    .to_string(),
+            );
+        } else if !participants.contributors_dropped && !round_replacements.is_empty() {
+            differences.push(format!(
+                "`contributorIds` contains replacement contributors {:?}, \
+                    but no contributors were dropped",
+                round_replacements
+            ));
+        }
+    }
+
+    if differences.is_empty() {
+        Ok(())
+    } else {
+        Err(eyre::eyre!(
+            "The state of round {} differs from what was expected:\n{}",
+            round,
+            differences.join("\n")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroUsize;
+
+    use mpmc_bus::Bus;
+
+    use super::{
+        check_round_state, CoordinatorConfig, CoordinatorConfigOverrides, CoordinatorState,
+        CoordinatorStateReporter, CoordinatorTomlConfiguration, ExpectRoundState,
+        ExpectedRoundParticipants, ROUND_RESTARTED_NO_CONTRIBUTORS_RE,
+    };
+    use crate::{
+        contributor::Contributor, process::ProcessConfig, stand_in::address_from_seed,
+        verifier::Verifier, CeremonyMessage, Environment,
+    };
+
+    fn coordinator_config(overrides: CoordinatorConfigOverrides) -> CoordinatorConfig {
+        CoordinatorConfig {
+            crate_dir: "aleo-setup-coordinator".into(),
+            setup_coordinator_bin: "aleo-setup-coordinator".into(),
+            environment: Environment::Development,
+            out_dir: "out".into(),
+            port: 9000,
+            replacement_contributors: Vec::new(),
+            overrides,
+            process: ProcessConfig::default(),
+        }
+    }
+
+    /// Test that overrides specified in a specification are applied
+    /// to the generated configuration.
+    #[test]
+    fn test_apply_overrides() {
+        let overrides: CoordinatorConfigOverrides = ron::from_str(
+            "(
+                environment_parameters: (
+                    maximum_contributors_per_round: Some(2),
+                    participant_lock_timeout: Some(60),
+                ),
+                reliability_check: (is_enabled: Some(true)),
+            )",
+        )
+        .unwrap();
+
+        let toml_config = CoordinatorTomlConfiguration::from(&coordinator_config(overrides));
+        let parameters = &toml_config.environment_parameters;
+        assert_eq!(
+            NonZeroUsize::new(2).unwrap(),
+            parameters.maximum_contributors_per_round
+        );
+        assert_eq!(60, parameters.participant_lock_timeout);
+        assert_eq!(3600, parameters.contributor_seen_timeout);
+        assert!(toml_config.reliability_check.is_enabled);
+        assert!(toml_config.validate().is_ok());
+    }
+
+    /// Test that overrides which violate the configuration
+    /// constraints are rejected.
+    #[test]
+    fn test_invalid_overrides() {
+        assert!(ron::from_str::<CoordinatorConfigOverrides>(
+            "(runtime_parameters: (operator_update_loop_delay: Some(0)))"
+        )
+        .is_err());
+
+        let overrides: CoordinatorConfigOverrides =
+            ron::from_str("(environment_parameters: (minimum_contributors_per_round: Some(6)))")
+                .unwrap();
+        let toml_config = CoordinatorTomlConfiguration::from(&coordinator_config(overrides));
+        assert!(toml_config.validate().is_err());
+    }
+
+    /// Test that differences between the round state in the
+    /// transcript and the expected state are reported by field.
+    #[test]
+    fn test_check_round_state() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let mut config = coordinator_config(CoordinatorConfigOverrides::default());
+        config.out_dir = out_dir.path().to_owned();
+
+        let contributor = Contributor {
+            id: "contributor1".to_string(),
+            key_file: "contributor1.key".into(),
+            address: address_from_seed("contributor1"),
+        };
+        let verifier = Verifier {
+            id: "verifier1".to_string(),
+            view_key_path: "verifier1.key".into(),
+            address: Some(address_from_seed("verifier1")),
+        };
+
+        let round_dir = config.transcript_dir().join("round_1");
+        std::fs::create_dir_all(&round_dir).unwrap();
+        let state = serde_json::json!({
+            "contributorIds": [contributor.id_on_coordinator()],
+            "verifierIds": [verifier.id_on_coordinator().unwrap()],
+            "chunks": [
+                {
+                    "chunkId": "0",
+                    "contributions": {
+                        "1": { "contributorId": contributor.id_on_coordinator() },
+                    },
+                },
+                { "chunkId": "1", "contributions": {} },
+            ],
+        });
+        std::fs::write(round_dir.join("state.json"), state.to_string()).unwrap();
+
+        let participants = ExpectedRoundParticipants {
+            contributors: &[contributor.clone()],
+            verifiers: &[verifier],
+            contributors_dropped: false,
+        };
+        let expect = ExpectRoundState {
+            chunks: Some(2),
+            contributions: false,
+            ..ExpectRoundState::default()
+        };
+        assert!(check_round_state(&config, 1, &expect, &participants).is_ok());
+
+        let expect = ExpectRoundState {
+            chunks: Some(3),
+            ..ExpectRoundState::default()
+        };
+        let participants = ExpectedRoundParticipants {
+            verifiers: &[],
+            ..participants
+        };
+        let error = check_round_state(&config, 1, &expect, &participants)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("`verifierIds`"));
+        assert!(error.contains("`chunks`"));
+        assert!(error.contains("`chunks[1].contributions`"));
+        assert!(!error.contains("`contributorIds`"));
+
+        // `verifierIds` is not checked if a verifier's address is
+        // unknown.
+        let unknown_verifier = Verifier {
+            id: "verifier2".to_string(),
+            view_key_path: "verifier2.key".into(),
+            address: None,
+        };
+        let participants = ExpectedRoundParticipants {
+            verifiers: &[unknown_verifier],
+            ..participants
+        };
+        let error = check_round_state(&config, 1, &expect, &participants)
+            .unwrap_err()
+            .to_string();
+        assert!(!error.contains("`verifierIds`"));
+    }
+
+    /// Test that the state reporter resumes from the state of the
+    /// previous coordinator process once a restarted process has
+    /// booted up, without repeating messages for that state.
+    #[test]
+    fn test_resynchronise_after_restart() {
+        let bus: Bus<CeremonyMessage> = Bus::new(100);
+        let mut ceremony_rx = bus.subscribe();
+        let mut reporter = CoordinatorStateReporter::process_restarted(
+            bus.broadcaster(),
+            CoordinatorState::RoundAggregating(2),
+        );
+
+        let lines = [
+            "Coordinator has booted up",
+            "Starting aggregation on round 2",
+            "Round 2 is aggregated",
+            "Round 2 is finished",
+        ];
+        for line in &lines {
+            reporter.parse_output_line(line).unwrap();
+        }
+
+        let messages: Vec<CeremonyMessage> =
+            std::iter::from_fn(|| ceremony_rx.try_recv().ok()).collect();
+        assert_eq!(
+            vec![
+                CeremonyMessage::CoordinatorRestarted,
+                CeremonyMessage::RoundAggregated(2),
+                CeremonyMessage::RoundFinished(2),
+            ],
+            messages
+        );
+    }
+
+    #[test]
+    fn test_round_restarted_no_contributors_re() {
+        for round in &[0, 2] {
+            let line = format!(
+                "No contributors remaining to reset and complete the current round. \
+                Rolling back to round {} to wait and accept new participants.",
+                round
+            );
+            assert!(ROUND_RESTARTED_NO_CONTRIBUTORS_RE.is_match(&line));
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Restarting the coordinator during a round of the ceremony, in
+//! order to test that it is able to recover its state from storage
+//! and the transcript.
+
+use std::{
+    collections::BTreeSet,
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use eyre::Context;
+use humantime::format_duration;
+use mpmc_bus::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    coordinator::{
+        check_round_participant_ids, read_round_participant_ids, CoordinatorConfig,
+        CoordinatorMessage,
+    },
+    CeremonyMessage,
+};
+
+/// The signal used to stop the coordinator process before it is
+/// restarted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoordinatorSignal {
+    /// `SIGKILL`, the coordinator is stopped immediately (simulating
+    /// a crash).
+    #[default]
+    Kill,
+    /// `SIGTERM`, the coordinator is given the opportunity to shut
+    /// down gracefully.
+    Term,
+}
+
+/// When the coordinator is restarted during a round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CoordinatorFaultTrigger {
+    /// Restart the coordinator as soon as the round has started.
+    RoundStart,
+    /// Restart the coordinator after this number of contributions
+    /// have been made in the round.
+    AfterContributions(u64),
+    /// Restart the coordinator once it has started aggregating the
+    /// round.
+    Aggregation,
+}
+
+/// The configuration for restarting the coordinator during a round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CoordinatorFaultConfig {
+    /// When the coordinator is restarted.
+    pub trigger: CoordinatorFaultTrigger,
+    /// (Optional) The signal used to stop the coordinator process.
+    /// Default: [CoordinatorSignal::Kill].
+    #[serde(default)]
+    pub signal: CoordinatorSignal,
+}
+
+/// How long to wait for the state of the round to be written to the
+/// transcript once the fault has been triggered.
+const ROUND_STATE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The interval at which the transcript is checked for the state of
+/// the round while waiting for it to be written.
+const ROUND_STATE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Read the ids of the participants in the round state of the
+/// transcript (see [read_round_participant_ids()]). The coordinator
+/// may not have written the state of the round yet when it has just
+/// started, so this retries until it can be read, or until
+/// [ROUND_STATE_TIMEOUT] has elapsed.
+fn wait_for_round_participant_ids(
+    coordinator_config: &CoordinatorConfig,
+    round: u64,
+) -> eyre::Result<BTreeSet<String>> {
+    let start_time = Instant::now();
+    loop {
+        match read_round_participant_ids(coordinator_config, round) {
+            Ok(participant_ids) => return Ok(participant_ids),
+            Err(error) if start_time.elapsed() < ROUND_STATE_TIMEOUT => {
+                tracing::debug!("Waiting for the state of round {}: {}", round, error);
+                std::thread::sleep(ROUND_STATE_POLL_INTERVAL);
+            }
+            Err(error) => {
+                return Err(error).wrap_err_with(|| {
+                    eyre::eyre!(
+                        "The state of round {} could not be read within {}",
+                        round,
+                        format_duration(ROUND_STATE_TIMEOUT)
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Restart the coordinator (via the `coordinator_tx` returned by
+/// [crate::coordinator::run_coordinator()]) during the specified
+/// `round`, when the fault described in `config` is triggered. Once
+/// the round has finished, checks that the participants in the round
+/// state in the transcript are the same as those before the restart
+/// (taking into account participants which were dropped and replaced
+/// by the coordinator). Returns an error if the round ends before the
+/// coordinator is restarted.
+pub fn restart_coordinator(
+    round: u64,
+    config: CoordinatorFaultConfig,
+    coordinator_config: CoordinatorConfig,
+    coordinator_tx: Sender<CoordinatorMessage>,
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+) -> JoinHandle<eyre::Result<()>> {
+    let span = tracing::error_span!("coordinator_fault", round);
+    std::thread::spawn(move || {
+        let _guard = span.enter();
+        let mut n_round_contributions: u64 = 0;
+
+        // Wait for the fault to be triggered.
+        loop {
+            let triggered = match ceremony_rx.recv()? {
+                CeremonyMessage::RoundStarted(message_round) if message_round == round => {
+                    matches!(config.trigger, CoordinatorFaultTrigger::RoundStart)
+                }
+                CeremonyMessage::SuccessfulContribution { .. } => {
+                    n_round_contributions += 1;
+                    match config.trigger {
+                        CoordinatorFaultTrigger::AfterContributions(contributions) => {
+                            n_round_contributions >= contributions
+                        }
+                        _ => false,
+                    }
+                }
+                CeremonyMessage::RoundStartedAggregation(message_round)
+                    if message_round == round =>
+                {
+                    matches!(config.trigger, CoordinatorFaultTrigger::Aggregation)
+                }
+                CeremonyMessage::RoundFinished(message_round)
+                | CeremonyMessage::RoundRestarted(message_round)
+                    if message_round == round =>
+                {
+                    return Err(eyre::eyre!(
+                        "Round {} ended before the coordinator was restarted ({:?}).",
+                        round,
+                        config
+                    ));
+                }
+                CeremonyMessage::Shutdown(_) => return Ok(()),
+                _ => false,
+            };
+
+            if triggered {
+                break;
+            }
+        }
+
+        let mut expected_participant_ids: BTreeSet<String> =
+            wait_for_round_participant_ids(&coordinator_config, round)?;
+
+        tracing::info!("Restarting the coordinator ({:?}).", config);
+        coordinator_tx.broadcast(CoordinatorMessage::Restart(config.signal))?;
+
+        // Wait for the round to finish, keeping track of the changes
+        // to the participants made by the coordinator.
+        loop {
+            match ceremony_rx.recv()? {
+                CeremonyMessage::ParticipantDropped(participant) => {
+                    expected_participant_ids.remove(&participant.id_on_coordinator());
+                }
+                CeremonyMessage::ContributorReplaced { replacement, .. } => {
+                    expected_participant_ids.insert(replacement.id_on_coordinator());
+                }
+                CeremonyMessage::RoundFinished(message_round) if message_round == round => {
+                    check_round_participant_ids(
+                        &coordinator_config,
+                        round,
+                        &expected_participant_ids,
+                    )?;
+                    tracing::info!("Round finished with the same participants after the restart.");
+                    break;
+                }
+                CeremonyMessage::RoundRestarted(message_round) if message_round == round => break,
+                CeremonyMessage::Shutdown(_) => break,
+                _ => {}
+            }
+        }
+
+        tracing::debug!("Thread closing gracefully.");
+        Ok(())
+    })
+}
@@ -1,9 +1,16 @@
-use crate::{CeremonyMessage, ContributorRef, ParticipantRef, ShutdownReason};
+use crate::{
+    process::MonitorProcessMessage, CeremonyMessage, ContributorRef, ParticipantRef,
+    ShutdownReason, VerifierRef,
+};
 
-use mpmc_bus::{Receiver, Sender};
+use mpmc_bus::{Receiver, Sender, TryRecvError};
 use serde::{Deserialize, Serialize};
 
-use std::{collections::HashMap, thread::JoinHandle};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
 
 /// The configuration for dropping a contributor from the ceremony.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +20,18 @@ pub struct DropContributorConfig {
     pub after_contributions: u64,
 }
 
+/// The configuration for dropping a verifier from the ceremony.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DropVerifierConfig {
+    /// A verifier is dropped (process killed) after the coordinator
+    /// has received this number of verifications from it during the
+    /// round.
+    AfterVerifications(u64),
+    /// A verifier is dropped (process killed) after this amount of
+    /// time has elapsed since the start of the round.
+    AfterDelay(#[serde(with = "humantime_serde")] Duration),
+}
+
 /// Configuration for running [monitor_drops()].
 pub struct MonitorDropsConfig {
     /// Expected dropped contributors. If not all drops specified here
@@ -20,45 +39,196 @@ pub struct MonitorDropsConfig {
     /// down at the end of the test, then an error will be returned
     /// during join.
     pub contributor_drops: HashMap<ContributorRef, DropContributorConfig>,
+    /// Expected dropped verifiers. The same rules apply as for
+    /// [MonitorDropsConfig::contributor_drops].
+    pub verifier_drops: HashMap<VerifierRef, DropVerifierConfig>,
+    /// Whether to check that the tasks of each dropped contributor
+    /// (from [MonitorDropsConfig::contributor_drops]) were assigned
+    /// to a replacement contributor, which then contributed to the
+    /// chunks that were missing.
+    pub expect_replacements: bool,
+    /// Participants which are expected to be dropped by the
+    /// coordinator because their process was paused for longer than
+    /// the coordinator's timeouts, see [crate::pause::PauseConfig].
+    pub pause_drops: HashSet<ParticipantRef>,
+}
+
+/// The number of successful contributions made by each contributor
+/// during a round.
+pub type ContributionCounts = HashMap<ContributorRef, u64>;
+
+/// The results of [monitor_drops()] for a round.
+#[derive(Debug, Default)]
+pub struct DropResults {
+    /// The number of contributions made by each contributor during
+    /// the round.
+    pub contribution_counts: ContributionCounts,
+    /// The replacement contributor assigned the tasks of each dropped
+    /// contributor, indexed by the dropped contributor.
+    pub replacements: HashMap<ContributorRef, ContributorRef>,
+}
+
+/// The assignment of a dropped contributor's tasks to a replacement
+/// contributor, see [CeremonyMessage::ContributorReplaced].
+#[derive(Debug)]
+struct Replacement {
+    replacement: ContributorRef,
+    /// The number of tasks assigned to the replacement.
+    tasks: u64,
+    /// The chunks that the dropped contributor had contributed to
+    /// before it was replaced.
+    dropped_chunks: BTreeSet<u64>,
+    /// The chunks that the replacement has contributed to since it
+    /// was assigned the tasks.
+    replacement_chunks: BTreeSet<u64>,
 }
 
-/// Monitor the ceremony for dropped contributors. Returns an error if
-/// an unexpected drop occurs or if not all expected drops have
-/// occurred.
+/// Monitor the ceremony for dropped participants. Returns an error if
+/// an unexpected drop occurs, if not all expected drops have
+/// occurred, if a contributor is dropped before having made the
+/// number of contributions specified in its [DropContributorConfig],
+/// or if a contributor makes more contributions than that (i.e. after
+/// it was killed). If [MonitorDropsConfig::expect_replacements] is
+/// enabled, then an error is also returned if the tasks of a dropped
+/// contributor were not completed by a replacement contributor. Upon
+/// success the number of contributions made by each contributor, and
+/// the replacements made during the round are returned.
 pub fn monitor_drops(
     config: MonitorDropsConfig,
     mut ceremony_rx: Receiver<CeremonyMessage>,
     ceremony_tx: Sender<CeremonyMessage>,
-) -> JoinHandle<eyre::Result<()>> {
+) -> JoinHandle<eyre::Result<DropResults>> {
+    let contributor_drop_configs = config.contributor_drops.clone();
     let mut contributor_drops = config.contributor_drops;
+    let mut verifier_drops = config.verifier_drops;
+    let mut pause_drops = config.pause_drops;
+    let expect_replacements = config.expect_replacements;
     let span = tracing::error_span!("monitor_drops");
     std::thread::spawn(move || {
         let _guard = span.enter();
+        let mut contribution_counts = ContributionCounts::new();
+        let mut contributed_chunks: HashMap<ContributorRef, BTreeSet<u64>> = HashMap::new();
+        let mut replacements: HashMap<ContributorRef, Replacement> = HashMap::new();
 
         loop {
             match ceremony_rx.recv()? {
-                CeremonyMessage::RoundFinished(_) => {
-                    check_drops(&contributor_drops)?;
+                CeremonyMessage::RoundFinished(_) | CeremonyMessage::RoundRestarted(_) => {
+                    check_drops(&contributor_drops, &verifier_drops, &pause_drops)?;
+                    if expect_replacements {
+                        check_replacements(&contributor_drop_configs, &replacements)?;
+                    }
 
                     break;
                 }
                 CeremonyMessage::Shutdown(reason) => {
                     if let ShutdownReason::TestFinished = reason {
-                        check_drops(&contributor_drops)?;
+                        check_drops(&contributor_drops, &verifier_drops, &pause_drops)?;
+                        if expect_replacements {
+                            check_replacements(&contributor_drop_configs, &replacements)?;
+                        }
                     }
 
                     break;
                 }
+                CeremonyMessage::ContributorReplaced {
+                    dropped,
+                    replacement,
+                    tasks,
+                } => {
+                    tracing::info!(
+                        "{} tasks from dropped contributor {} were assigned to \
+                            replacement contributor {}.",
+                        tasks,
+                        dropped,
+                        replacement
+                    );
+                    let dropped_chunks = contributed_chunks
+                        .get(&dropped)
+                        .cloned()
+                        .unwrap_or_default();
+                    replacements.insert(
+                        dropped,
+                        Replacement {
+                            replacement,
+                            tasks,
+                            dropped_chunks,
+                            replacement_chunks: BTreeSet::new(),
+                        },
+                    );
+                }
+                CeremonyMessage::SuccessfulContribution { contributor, chunk } => {
+                    let n_contributions =
+                        contribution_counts.entry(contributor.clone()).or_insert(0);
+                    *n_contributions += 1;
+                    contributed_chunks
+                        .entry(contributor.clone())
+                        .or_default()
+                        .insert(chunk);
+
+                    for replacement in replacements.values_mut() {
+                        if replacement.replacement == contributor {
+                            replacement.replacement_chunks.insert(chunk);
+                        }
+                    }
+
+                    if let Some(drop_config) = contributor_drop_configs.get(&contributor) {
+                        if *n_contributions > drop_config.after_contributions {
+                            ceremony_tx
+                                .broadcast(CeremonyMessage::Shutdown(ShutdownReason::Error))?;
+                            return Err(eyre::eyre!(
+                                "Contributor {} made contribution {} after it was \
+                                    killed (expected to be dropped after {} contributions).",
+                                &contributor,
+                                n_contributions,
+                                drop_config.after_contributions
+                            ));
+                        }
+                    }
+                }
                 CeremonyMessage::ParticipantDropped(participant) => {
-                    if let ParticipantRef::Contributor(contributor) = &participant {
-                        if let Some(_drop_config) = contributor_drops.remove(contributor) {
-                            tracing::info!(
-                                "Participant {:?} dropped during the round (as expected).",
-                                &participant
-                            );
-                            continue;
-                            // TODO: check that participant was dropped after the correct number of contributions.
+                    if pause_drops.remove(&participant) {
+                        tracing::info!(
+                            "Participant {:?} dropped during the round after being paused \
+                                (as expected).",
+                            &participant
+                        );
+                        continue;
+                    }
+
+                    let expected = match &participant {
+                        ParticipantRef::Contributor(contributor) => {
+                            match contributor_drops.remove(contributor) {
+                                Some(drop_config) => {
+                                    let n_contributions =
+                                        contribution_counts.get(contributor).cloned().unwrap_or(0);
+                                    if n_contributions < drop_config.after_contributions {
+                                        ceremony_tx.broadcast(CeremonyMessage::Shutdown(
+                                            ShutdownReason::Error,
+                                        ))?;
+                                        return Err(eyre::eyre!(
+                                            "Contributor {} was dropped after {} contributions, \
+                                                before the expected {} contributions were made.",
+                                            contributor,
+                                            n_contributions,
+                                            drop_config.after_contributions
+                                        ));
+                                    }
+                                    true
+                                }
+                                None => false,
+                            }
+                        }
+                        ParticipantRef::Verifier(verifier) => {
+                            verifier_drops.remove(verifier).is_some()
                         }
+                    };
+
+                    if expected {
+                        tracing::info!(
+                            "Participant {:?} dropped during the round (as expected).",
+                            &participant
+                        );
+                        continue;
                     }
 
                     ceremony_tx.broadcast(CeremonyMessage::Shutdown(ShutdownReason::Error))?;
@@ -73,20 +243,300 @@ pub fn monitor_drops(
 
         tracing::info!("Thread terminated gracefully");
 
-        Ok(())
+        Ok(DropResults {
+            contribution_counts,
+            replacements: replacements
+                .into_iter()
+                .map(|(dropped, replacement)| (dropped, replacement.replacement))
+                .collect(),
+        })
     })
 }
 
 fn check_drops(
     contributor_drops: &HashMap<ContributorRef, DropContributorConfig>,
+    verifier_drops: &HashMap<VerifierRef, DropVerifierConfig>,
+    pause_drops: &HashSet<ParticipantRef>,
 ) -> eyre::Result<()> {
-    if !contributor_drops.is_empty() {
+    if !contributor_drops.is_empty() || !verifier_drops.is_empty() || !pause_drops.is_empty() {
         return Err(eyre::eyre!(
             "The specified drops did not occur as \
-                expected during the ceremony: {:?}",
-            contributor_drops
+                expected during the ceremony: {:?} {:?} {:?}",
+            contributor_drops,
+            verifier_drops,
+            pause_drops
         ));
     } else {
         Ok(())
     }
 }
+
+/// Check that the tasks of each of the dropped contributors were
+/// assigned to a replacement contributor, and that the replacement
+/// contributed to the chunks that the dropped contributor had not.
+fn check_replacements(
+    contributor_drops: &HashMap<ContributorRef, DropContributorConfig>,
+    replacements: &HashMap<ContributorRef, Replacement>,
+) -> eyre::Result<()> {
+    for dropped in contributor_drops.keys() {
+        let replacement = replacements.get(dropped).ok_or_else(|| {
+            eyre::eyre!(
+                "The tasks of dropped contributor {} were not assigned to a \
+                    replacement contributor.",
+                dropped
+            )
+        })?;
+
+        if (replacement.replacement_chunks.len() as u64) < replacement.tasks {
+            return Err(eyre::eyre!(
+                "Replacement contributor {} only contributed to {} of the {} chunks \
+                    assigned from dropped contributor {}.",
+                replacement.replacement,
+                replacement.replacement_chunks.len(),
+                replacement.tasks,
+                dropped
+            ));
+        }
+
+        let duplicate_chunks: Vec<&u64> = replacement
+            .replacement_chunks
+            .intersection(&replacement.dropped_chunks)
+            .collect();
+        if !duplicate_chunks.is_empty() {
+            return Err(eyre::eyre!(
+                "Replacement contributor {} contributed to chunks {:?} which dropped \
+                    contributor {} had already contributed to.",
+                replacement.replacement,
+                duplicate_chunks,
+                dropped
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Drop the verifier referred to by `verifier` during the specified
+/// `round` by terminating its process via `verifier_monitor_tx`,
+/// according to the specified `config`.
+pub fn drop_verifier(
+    round: u64,
+    verifier: VerifierRef,
+    config: DropVerifierConfig,
+    verifier_monitor_tx: Sender<MonitorProcessMessage>,
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+) -> JoinHandle<eyre::Result<()>> {
+    let span = tracing::error_span!("drop_verifier", verifier = %verifier);
+    std::thread::spawn(move || {
+        let _guard = span.enter();
+        let mut round_start_time: Option<Instant> = None;
+        let mut n_verifications: u64 = 0;
+
+        loop {
+            // Sleep occasionally because otherwise this loop will run too fast.
+            std::thread::sleep(Duration::from_millis(100));
+
+            // Process all the messages received since the last check.
+            let mut round_ended = false;
+            loop {
+                match ceremony_rx.try_recv() {
+                    Ok(CeremonyMessage::RoundStarted(started_round)) if started_round == round => {
+                        round_start_time = Some(Instant::now());
+                    }
+                    Ok(CeremonyMessage::SuccessfulVerification {
+                        verifier: message_verifier,
+                        chunk: _,
+                    }) if message_verifier == verifier => {
+                        n_verifications += 1;
+                        tracing::info!(
+                            "Verifier {} made {} verifications (before drop will occur)",
+                            verifier,
+                            n_verifications
+                        );
+                    }
+                    Ok(CeremonyMessage::RoundFinished(_))
+                    | Ok(CeremonyMessage::RoundRestarted(_))
+                    | Ok(CeremonyMessage::Shutdown(_)) => {
+                        round_ended = true;
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(TryRecvError::Disconnected) => {
+                        panic!("`ceremony_rx` disconnected");
+                    }
+                    Err(TryRecvError::Empty) => break,
+                }
+            }
+
+            if round_ended {
+                break;
+            }
+
+            let drop_now = match &config {
+                DropVerifierConfig::AfterVerifications(verifications) => {
+                    n_verifications >= *verifications
+                }
+                DropVerifierConfig::AfterDelay(delay) => round_start_time
+                    .map(|start_time| start_time.elapsed() >= *delay)
+                    .unwrap_or(false),
+            };
+
+            if drop_now {
+                tracing::info!(
+                    "Verifier {} terminating process now to perform drop ({:?}).",
+                    verifier,
+                    config
+                );
+                verifier_monitor_tx.broadcast(MonitorProcessMessage::Terminate)?;
+                break;
+            }
+        }
+
+        tracing::debug!("Thread closing gracefully.");
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{HashMap, HashSet};
+
+    use mpmc_bus::Bus;
+
+    use super::{monitor_drops, DropContributorConfig, DropResults, MonitorDropsConfig};
+    use crate::{
+        stand_in::address_from_seed, CeremonyMessage, ContributorRef, ParticipantRef,
+        ShutdownReason,
+    };
+
+    /// Run [monitor_drops()] with a single contributor expected to be
+    /// dropped after 2 contributions, feeding it the specified
+    /// `messages`.
+    fn run_monitor_drops(
+        contributor: &ContributorRef,
+        messages: Vec<CeremonyMessage>,
+        expect_replacements: bool,
+    ) -> eyre::Result<DropResults> {
+        let bus: Bus<CeremonyMessage> = Bus::new(100);
+        let ceremony_tx = bus.broadcaster();
+
+        let mut contributor_drops = HashMap::new();
+        contributor_drops.insert(
+            contributor.clone(),
+            DropContributorConfig {
+                after_contributions: 2,
+            },
+        );
+        let config = MonitorDropsConfig {
+            contributor_drops,
+            verifier_drops: HashMap::new(),
+            expect_replacements,
+            pause_drops: HashSet::new(),
+        };
+        let join = monitor_drops(config, bus.subscribe(), ceremony_tx.clone());
+
+        for message in messages {
+            ceremony_tx.broadcast(message)?;
+        }
+        ceremony_tx.broadcast(CeremonyMessage::Shutdown(ShutdownReason::TestFinished))?;
+
+        join.join().expect("Error joining monitor_drops thread")
+    }
+
+    #[test]
+    fn test_monitor_drops_contribution_counts() {
+        let contributor = ContributorRef {
+            address: address_from_seed("contributor"),
+        };
+        let contribution = |chunk| CeremonyMessage::SuccessfulContribution {
+            contributor: contributor.clone(),
+            chunk,
+        };
+        let dropped =
+            CeremonyMessage::ParticipantDropped(ParticipantRef::Contributor(contributor.clone()));
+
+        let results = run_monitor_drops(
+            &contributor,
+            vec![contribution(0), contribution(1), dropped.clone()],
+            false,
+        )
+        .unwrap();
+        assert_eq!(Some(&2), results.contribution_counts.get(&contributor));
+
+        // Dropped before the expected number of contributions.
+        assert!(
+            run_monitor_drops(&contributor, vec![contribution(0), dropped.clone()], false).is_err()
+        );
+
+        // Contribution received after the contributor was killed.
+        assert!(run_monitor_drops(
+            &contributor,
+            vec![contribution(0), contribution(1), contribution(2), dropped],
+            false,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_monitor_drops_replacements() {
+        let contributor = ContributorRef {
+            address: address_from_seed("contributor"),
+        };
+        let replacement = ContributorRef {
+            address: address_from_seed("replacement"),
+        };
+        let contribution =
+            |contributor: &ContributorRef, chunk| CeremonyMessage::SuccessfulContribution {
+                contributor: contributor.clone(),
+                chunk,
+            };
+        let dropped =
+            CeremonyMessage::ParticipantDropped(ParticipantRef::Contributor(contributor.clone()));
+        let replaced = CeremonyMessage::ContributorReplaced {
+            dropped: contributor.clone(),
+            replacement: replacement.clone(),
+            tasks: 2,
+        };
+
+        let results = run_monitor_drops(
+            &contributor,
+            vec![
+                contribution(&contributor, 0),
+                contribution(&contributor, 1),
+                dropped.clone(),
+                replaced.clone(),
+                contribution(&replacement, 2),
+                contribution(&replacement, 3),
+            ],
+            true,
+        )
+        .unwrap();
+        assert_eq!(Some(&replacement), results.replacements.get(&contributor));
+
+        // No replacement was assigned.
+        assert!(run_monitor_drops(
+            &contributor,
+            vec![
+                contribution(&contributor, 0),
+                contribution(&contributor, 1),
+                dropped.clone(),
+            ],
+            true,
+        )
+        .is_err());
+
+        // The replacement did not complete the assigned tasks.
+        assert!(run_monitor_drops(
+            &contributor,
+            vec![
+                contribution(&contributor, 0),
+                contribution(&contributor, 1),
+                dropped,
+                replaced,
+                contribution(&replacement, 2),
+            ],
+            true,
+        )
+        .is_err());
+    }
+}
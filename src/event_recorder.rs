@@ -0,0 +1,125 @@
+//! This module contains a recorder which persists the
+//! [CeremonyMessage]s sent during a test to a newline delimited JSON
+//! file, creating a machine-readable timeline of the ceremony.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    thread::JoinHandle,
+    time::Instant,
+};
+
+use eyre::Context;
+use mpmc_bus::Receiver;
+use serde::{Deserialize, Serialize};
+
+use crate::CeremonyMessage;
+
+/// Name of the file that the events are recorded to, in the test's
+/// out directory.
+pub const EVENTS_FILE_NAME: &str = "events.ndjson";
+
+/// A [CeremonyMessage] which has been recorded, along with the time
+/// that it was received.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CeremonyEvent {
+    /// Monotonic time (in seconds) since the recorder was started.
+    pub elapsed: f64,
+    /// Wall-clock time that the message was received.
+    pub time: chrono::DateTime<chrono::Utc>,
+    /// The round that the ceremony was in when the message was
+    /// received (if a round has been reported yet).
+    pub round: Option<u64>,
+    /// The message that was received.
+    pub message: CeremonyMessage,
+}
+
+/// Record all the [CeremonyMessage]s received on `ceremony_rx` to the
+/// `events_path` file (one [CeremonyEvent] per line) until a
+/// [CeremonyMessage::Shutdown] is received.
+pub fn record_events(
+    events_path: impl AsRef<Path>,
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+) -> eyre::Result<JoinHandle<eyre::Result<()>>> {
+    let events_path = events_path.as_ref().to_owned();
+    let events_file = File::create(&events_path)
+        .wrap_err_with(|| eyre::eyre!("Error creating events file {:?}", events_path))?;
+    let span = tracing::error_span!("event_recorder");
+
+    Ok(std::thread::spawn(move || {
+        let _guard = span.enter();
+        let mut writer = BufWriter::new(events_file);
+        let start_time = Instant::now();
+        let mut round: Option<u64> = None;
+
+        loop {
+            let message = ceremony_rx.recv()?;
+            let is_shutdown = matches!(message, CeremonyMessage::Shutdown(_));
+
+            if let Some(message_round) = message.round() {
+                round = Some(message_round);
+            }
+
+            let event = CeremonyEvent {
+                elapsed: start_time.elapsed().as_secs_f64(),
+                time: chrono::Utc::now(),
+                round,
+                message,
+            };
+
+            serde_json::to_writer(&mut writer, &event)?;
+            writer.write_all(b"\n")?;
+            // Flush after each event so that the timeline is
+            // available even if the test is aborted.
+            writer.flush()?;
+
+            if is_shutdown {
+                break;
+            }
+        }
+
+        tracing::debug!("Thread terminated gracefully");
+        Ok(())
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use mpmc_bus::Bus;
+
+    use super::{record_events, CeremonyEvent};
+    use crate::{CeremonyMessage, ShutdownReason};
+
+    #[test]
+    fn test_record_events() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let events_path = out_dir.path().join("events.ndjson");
+
+        let bus: Bus<CeremonyMessage> = Bus::new(100);
+        let ceremony_tx = bus.broadcaster();
+        let join = record_events(&events_path, bus.subscribe()).unwrap();
+
+        let messages = vec![
+            CeremonyMessage::RoundWaitingForParticipants(1),
+            CeremonyMessage::RoundStarted(1),
+            CeremonyMessage::Shutdown(ShutdownReason::TestFinished),
+        ];
+        for message in &messages {
+            ceremony_tx.broadcast(message.clone()).unwrap();
+        }
+        join.join().unwrap().unwrap();
+
+        let events: Vec<CeremonyEvent> = std::fs::read_to_string(&events_path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(messages.len(), events.len());
+        for (message, event) in messages.iter().zip(&events) {
+            assert_eq!(message, &event.message);
+            assert_eq!(Some(1), event.round);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Types shared by the faults which can be injected into the
+//! participants of a round (see [crate::network_proxy] and
+//! [crate::pause]), describing which participant the fault applies to,
+//! and when it is triggered.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CeremonyMessage, ParticipantRef};
+
+/// The participant that a fault applies to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FaultTarget {
+    /// The contributor in the round with this index (starting at 1).
+    Contributor(u8),
+    /// The replacement contributor with this index (starting at 1).
+    ReplacementContributor(u8),
+    /// The verifier with this index (starting at 1).
+    Verifier(u8),
+}
+
+/// When a fault is triggered during the round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FaultTrigger {
+    /// After this amount of time has elapsed since the start of the
+    /// round.
+    AfterDelay(#[serde(with = "humantime_serde")] Duration),
+    /// After the coordinator has received this number of
+    /// contributions (or verifications in the case of a verifier)
+    /// from the target participant during the round.
+    AfterContributions(u64),
+    /// After the coordinator has received this number of
+    /// contributions from all the contributors during the round.
+    AfterRoundContributions(u64),
+}
+
+/// Keeps track of the messages during a round which are required to
+/// determine whether a [FaultTrigger] has been triggered.
+#[derive(Debug)]
+pub struct FaultTriggerState {
+    round: u64,
+    /// The participant targeted by the fault (if its address is
+    /// known).
+    participant: Option<ParticipantRef>,
+    round_start_time: Option<Instant>,
+    n_participant_contributions: u64,
+    n_round_contributions: u64,
+}
+
+impl FaultTriggerState {
+    pub fn new(round: u64, participant: Option<ParticipantRef>) -> Self {
+        Self {
+            round,
+            participant,
+            round_start_time: None,
+            n_participant_contributions: 0,
+            n_round_contributions: 0,
+        }
+    }
+
+    /// Update the state with a message received during the round.
+    pub fn update(&mut self, message: &CeremonyMessage) {
+        match message {
+            CeremonyMessage::RoundStarted(started_round) if *started_round == self.round => {
+                self.round_start_time = Some(Instant::now());
+            }
+            CeremonyMessage::SuccessfulContribution {
+                contributor,
+                chunk: _,
+            } => {
+                self.n_round_contributions += 1;
+                if matches!(
+                    &self.participant,
+                    Some(ParticipantRef::Contributor(participant)) if participant == contributor
+                ) {
+                    self.n_participant_contributions += 1;
+                }
+            }
+            CeremonyMessage::SuccessfulVerification { verifier, chunk: _ } => {
+                if matches!(
+                    &self.participant,
+                    Some(ParticipantRef::Verifier(participant)) if participant == verifier
+                ) {
+                    self.n_participant_contributions += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the `trigger` has been triggered.
+    pub fn is_triggered(&self, trigger: &FaultTrigger) -> bool {
+        match trigger {
+            FaultTrigger::AfterDelay(delay) => self
+                .round_start_time
+                .map_or(false, |start_time| start_time.elapsed() >= *delay),
+            FaultTrigger::AfterContributions(contributions) => {
+                self.n_participant_contributions >= *contributions
+            }
+            FaultTrigger::AfterRoundContributions(contributions) => {
+                self.n_round_contributions >= *contributions
+            }
+        }
+    }
+}
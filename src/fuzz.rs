@@ -0,0 +1,411 @@
+//! Generation of random (but valid) test scenarios from a seed, to
+//! cover ceremony scenarios which nobody thought to write a
+//! specification for. The same seed always generates the same
+//! scenario, so a failing scenario can be replayed exactly, and its
+//! specification committed as a regression test.
+
+use serde::Deserialize;
+
+use crate::{
+    coordinator::CoordinatorConfigOverrides,
+    drop_participant::DropContributorConfig,
+    round_outcome::RoundOutcome,
+    specification::{SingleTestOptions, Specification},
+    stand_in::number_of_chunks,
+    test::{ContributorStartConfig, StartAfterRoundContributions, TestRound},
+    Environment,
+};
+
+/// A small deterministic pseudo-random number generator
+/// ([SplitMix64](https://prng.di.unimi.it/splitmix64.c)). Not
+/// suitable for anything other than generating test scenarios.
+#[derive(Debug, Clone)]
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in the range `low..=high`.
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        if high <= low {
+            return low;
+        }
+        low + self.next_u64() % (high - low + 1)
+    }
+
+    /// `true` with a probability of `1 / n`.
+    fn one_in(&mut self, n: u64) -> bool {
+        self.next_u64() % n == 0
+    }
+}
+
+/// Configuration of the ceremony that the tests generated with
+/// `--fuzz` are run with.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FuzzConfig {
+    /// (Optional) What environment to use for the generated tests.
+    /// Default: `Development`.
+    #[serde(default = "default_fuzz_environment")]
+    pub environment: Environment,
+    /// (Optional) Overrides for the configuration generated for the
+    /// `aleo-setup-coordinator` in the generated tests. The number of
+    /// contributors in each round is kept within the
+    /// `minimum_contributors_per_round` and
+    /// `maximum_contributors_per_round` of the coordinator. Default:
+    /// `None`.
+    #[serde(default)]
+    pub coordinator: Option<CoordinatorConfigOverrides>,
+    /// (Optional) The number of chunks in a round of the ceremony in
+    /// the `environment`. Required when not running with
+    /// [crate::config::Config::stand_ins], the number of chunks of
+    /// the stand-in coordinator is used otherwise. Default: `None`.
+    #[serde(default)]
+    pub chunks: Option<u64>,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            environment: default_fuzz_environment(),
+            coordinator: None,
+            chunks: None,
+        }
+    }
+}
+
+/// Default value for [FuzzConfig::environment].
+fn default_fuzz_environment() -> Environment {
+    Environment::Development
+}
+
+/// The limits for the scenarios generated by [generate_test()].
+#[derive(Debug, Clone)]
+pub struct FuzzLimits {
+    /// What environment to use for the generated tests.
+    pub environment: Environment,
+    /// Overrides for the configuration generated for the coordinator.
+    pub coordinator: Option<CoordinatorConfigOverrides>,
+    /// Whether the generated tests check that the dropped
+    /// contributors are replaced, which is only supported with the
+    /// stand-ins.
+    pub expect_replacements: bool,
+    /// The minimum number of contributors in a round.
+    pub min_contributors: u8,
+    /// The maximum number of contributors in a round.
+    pub max_contributors: u8,
+    /// The maximum number of verifiers.
+    pub max_verifiers: u8,
+    /// The maximum number of replacement contributors.
+    pub max_replacement_contributors: u8,
+    /// The maximum number of rounds which finish (not including
+    /// rounds which are restarted).
+    pub max_rounds: u8,
+    /// The number of chunks in the ceremony. Drops and late starts
+    /// are triggered before this number of contributions, so that
+    /// they occur before a contributor has finished.
+    pub chunks: u64,
+    /// The time limit for each test (in seconds).
+    pub time_limit: u64,
+}
+
+impl FuzzLimits {
+    /// The limits for tests run with the `fuzz` configuration
+    /// ([crate::config::Config::fuzz]), taking the number of
+    /// contributors per round from the coordinator overrides that the
+    /// tests are run with. `stand_ins` is whether the tests are run
+    /// with [crate::config::Config::stand_ins].
+    pub fn new(fuzz: &FuzzConfig, stand_ins: bool) -> eyre::Result<Self> {
+        let chunks = match (fuzz.chunks, stand_ins) {
+            (Some(chunks), _) => chunks,
+            (None, true) => number_of_chunks(fuzz.environment),
+            (None, false) => {
+                return Err(eyre::eyre!(
+                    "`fuzz.chunks` needs to be specified in the configuration \
+                    to run fuzz tests without the stand-ins"
+                ))
+            }
+        };
+        if chunks < 2 {
+            return Err(eyre::eyre!(
+                "`fuzz.chunks` is {}, expected at least 2",
+                chunks
+            ));
+        }
+
+        let (min_contributors, max_contributors) = fuzz
+            .coordinator
+            .clone()
+            .unwrap_or_default()
+            .contributors_per_round();
+        let to_u8 = |contributors: usize| contributors.min(u8::MAX as usize) as u8;
+
+        Ok(Self {
+            environment: fuzz.environment,
+            coordinator: fuzz.coordinator.clone(),
+            expect_replacements: stand_ins,
+            min_contributors: to_u8(min_contributors),
+            max_contributors: to_u8(max_contributors),
+            max_verifiers: 3,
+            max_replacement_contributors: 2,
+            max_rounds: 3,
+            chunks,
+            time_limit: 3600,
+        })
+    }
+}
+
+/// Generate a test from `seed`, with the id `fuzz-<seed>`.
+pub fn generate_test(seed: u64, limits: &FuzzLimits) -> SingleTestOptions {
+    let mut rng = Rng::new(seed);
+    let min_contributors = limits.min_contributors as u64;
+    let max_contributors = limits.max_contributors as u64;
+    let max_contributions = limits.chunks - 1;
+
+    let verifiers = rng.range(1, limits.max_verifiers as u64) as u8;
+    let replacement_contributors = if rng.one_in(3) {
+        rng.range(1, limits.max_replacement_contributors as u64) as u8
+    } else {
+        0
+    };
+    // Replacement contributors which have not yet replaced a dropped
+    // contributor.
+    let mut available_replacements = replacement_contributors as u64;
+
+    let n_rounds = rng.range(1, limits.max_rounds as u64);
+    let mut rounds = Vec::new();
+
+    for _ in 0..n_rounds {
+        // Occasionally drop all of the contributors in a round, so
+        // that it is restarted and run again. This only happens when
+        // there are no replacement contributors to take their place.
+        if replacement_contributors == 0 && rng.one_in(5) {
+            let contributors = rng.range(min_contributors, max_contributors);
+            rounds.push(TestRound {
+                contributors: contributors as u8,
+                contributor_drops: (0..contributors)
+                    .map(|_| DropContributorConfig {
+                        after_contributions: rng.range(1, max_contributions),
+                    })
+                    .collect(),
+                expect: RoundOutcome::Restart,
+                ..TestRound::default()
+            });
+        }
+
+        // The last contributor in the round always starts with the
+        // round and is never dropped, so that the round can finish,
+        // and so that the contributions which start the late
+        // contributors are made.
+        let contributors = rng.range(min_contributors.max(1), max_contributors);
+        let replace = available_replacements > 0;
+        let max_drops = if replace {
+            (contributors - 1).min(available_replacements)
+        } else {
+            contributors - 1
+        };
+        let n_drops = rng.range(0, max_drops);
+        let n_late_starts = rng.range(0, contributors - 1 - n_drops);
+        if replace {
+            available_replacements -= n_drops;
+        }
+
+        // Contributors are assigned to the drops and starts in order,
+        // so the dropped contributors start with the round, followed
+        // by the late contributors.
+        let contributor_drops = (0..n_drops)
+            .map(|_| DropContributorConfig {
+                after_contributions: rng.range(1, max_contributions),
+            })
+            .collect();
+        let contributor_starts = if n_late_starts > 0 {
+            std::iter::repeat(ContributorStartConfig::RoundStart)
+                .take(n_drops as usize)
+                .chain((0..n_late_starts).map(|_| {
+                    ContributorStartConfig::AfterRoundContributions(StartAfterRoundContributions {
+                        after_round_contributions: rng.range(1, max_contributions),
+                    })
+                }))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        rounds.push(TestRound {
+            contributors: contributors as u8,
+            contributor_drops,
+            expect_replacements: limits.expect_replacements && replace && n_drops > 0,
+            contributor_starts,
+            ..TestRound::default()
+        });
+    }
+
+    SingleTestOptions {
+        id: format!("fuzz-{}", seed),
+        verifiers,
+        replacement_contributors,
+        environment: limits.environment,
+        timout: Some(limits.time_limit),
+        skip: false,
+        tags: vec!["fuzz".to_string()],
+        coordinator: limits.coordinator.clone(),
+        verify_transcript: false,
+        network_proxy: false,
+        baseline_tolerance: Default::default(),
+        retries: None,
+        processes: Default::default(),
+        rounds,
+    }
+}
+
+/// Generate a [Specification] containing `n_tests` tests, generated
+/// with the seeds `seed`, `seed + 1`, etc. A single test can be
+/// replayed using its seed (which is included in its id).
+pub fn generate_specification(seed: u64, n_tests: u64, limits: &FuzzLimits) -> Specification {
+    Specification {
+        tests: (0..n_tests)
+            .map(|i| generate_test(seed.wrapping_add(i), limits))
+            .collect(),
+    }
+}
+
+/// A seed for [generate_specification()] derived from the current
+/// time, for when no seed is specified.
+pub fn random_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// The name of the file in the out directory that the specification
+/// generated with `seed` is saved to.
+pub fn fuzz_specification_file_name(seed: u64) -> String {
+    format!("fuzz-{}.ron", seed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate_specification, generate_test, FuzzConfig, FuzzLimits};
+    use crate::{
+        round_outcome::RoundOutcome, specification::Specification, stand_in::number_of_chunks,
+        Environment,
+    };
+
+    #[test]
+    fn test_generate_test() {
+        let limits = FuzzLimits::new(&FuzzConfig::default(), true).unwrap();
+
+        for seed in 0..200 {
+            let test = generate_test(seed, &limits);
+            assert_eq!(test.id, format!("fuzz-{}", seed));
+            assert!(test.verifiers >= 1 && test.verifiers <= limits.max_verifiers);
+
+            let finished_rounds = test
+                .rounds
+                .iter()
+                .filter(|round| round.expect == RoundOutcome::Finish)
+                .count();
+            assert!(finished_rounds >= 1 && finished_rounds <= limits.max_rounds as usize);
+
+            let mut drops_replaced = 0;
+            for round in &test.rounds {
+                assert!(round.contributors >= limits.min_contributors);
+                assert!(round.contributors <= limits.max_contributors);
+                assert!(round.contributor_starts.len() < round.contributors as usize);
+
+                match round.expect {
+                    RoundOutcome::Finish => {
+                        assert!(round.contributor_drops.len() < round.contributors as usize);
+                    }
+                    RoundOutcome::Restart => {
+                        assert_eq!(test.replacement_contributors, 0);
+                        assert_eq!(round.contributor_drops.len(), round.contributors as usize);
+                    }
+                }
+
+                for drop in &round.contributor_drops {
+                    assert!(drop.after_contributions >= 1);
+                    assert!(drop.after_contributions < limits.chunks);
+                }
+
+                if round.expect_replacements {
+                    drops_replaced += round.contributor_drops.len();
+                }
+            }
+            assert!(drops_replaced <= test.replacement_contributors as usize);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_limits() {
+        let fuzz: FuzzConfig = ron::from_str(
+            r#"(
+                environment: inner,
+                coordinator: Some((
+                    environment_parameters: (
+                        minimum_contributors_per_round: Some(2),
+                        maximum_contributors_per_round: Some(3),
+                    ),
+                )),
+            )"#,
+        )
+        .unwrap();
+
+        let limits = FuzzLimits::new(&fuzz, true).unwrap();
+        assert_eq!(limits.min_contributors, 2);
+        assert_eq!(limits.max_contributors, 3);
+        assert_eq!(limits.chunks, number_of_chunks(Environment::Inner));
+
+        let test = generate_test(1, &limits);
+        assert_eq!(test.environment, Environment::Inner);
+        assert!(test.coordinator.is_some());
+
+        // The number of chunks of the real coordinator is not known.
+        assert!(FuzzLimits::new(&fuzz, false).is_err());
+        let fuzz = FuzzConfig {
+            chunks: Some(64),
+            ..fuzz
+        };
+        let limits = FuzzLimits::new(&fuzz, false).unwrap();
+        assert_eq!(limits.chunks, 64);
+        assert!(!limits.expect_replacements);
+    }
+
+    #[test]
+    fn test_generate_specification_replay() {
+        let limits = FuzzLimits::new(&FuzzConfig::default(), true).unwrap();
+        let specification = generate_specification(42, 3, &limits);
+        let specification_str =
+            ron::ser::to_string_pretty(&specification, Default::default()).unwrap();
+
+        // The same seed generates the same specification.
+        let regenerated = generate_specification(42, 3, &limits);
+        assert_eq!(
+            specification_str,
+            ron::ser::to_string_pretty(&regenerated, Default::default()).unwrap()
+        );
+
+        // The generated specification can be loaded from the saved
+        // ron.
+        let replayed: Specification = ron::from_str(&specification_str).unwrap();
+        let ids: Vec<&str> = replayed.tests.iter().map(|test| test.id.as_str()).collect();
+        assert_eq!(ids, vec!["fuzz-42", "fuzz-43", "fuzz-44"]);
+        assert_eq!(
+            replayed.tests[1].rounds.len(),
+            generate_test(43, &limits).rounds.len()
+        );
+    }
+}
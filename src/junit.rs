@@ -0,0 +1,226 @@
+//! This module contains functions for writing the results of running
+//! the tests in a [crate::specification::Specification] as a JUnit
+//! XML report, which can be consumed by CI dashboards.
+
+use std::{io::Write, path::Path, time::Duration};
+
+use eyre::Context;
+
+use crate::{
+    reporting::strip_ansi_escapes,
+    specification::{TestOutcome, TestReport},
+};
+
+/// Name used for the test suite in the JUnit report.
+const TEST_SUITE_NAME: &str = "aleo-setup-integration-test";
+
+/// Escape `text` so that it can be used as XML text or as an XML
+/// attribute value. ANSI escape sequences and characters which are
+/// not valid in XML are removed.
+fn escape_xml(text: &str) -> String {
+    let text = strip_ansi_escapes(text);
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Format a duration in seconds, as expected by the JUnit `time`
+/// attribute.
+fn format_seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Write the JUnit XML report for the `reports` to `writer`.
+pub fn write_junit_report(mut writer: impl Write, reports: &[TestReport]) -> eyre::Result<()> {
+    let n_failures = reports
+        .iter()
+        .filter(|report| report.outcome.error().is_some())
+        .count();
+    let n_skipped = reports
+        .iter()
+        .filter(|report| matches!(report.outcome, TestOutcome::Skipped(_)))
+        .count();
+    let total_duration: Duration = reports.iter().map(|report| report.duration).sum();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="{name}" tests="{tests}" failures="{failures}" skipped="{skipped}" time="{time}">"#,
+        name = TEST_SUITE_NAME,
+        tests = reports.len(),
+        failures = n_failures,
+        skipped = n_skipped,
+        time = format_seconds(total_duration),
+    )?;
+    writeln!(
+        writer,
+        r#"  <testsuite name="{name}" tests="{tests}" failures="{failures}" errors="0" skipped="{skipped}" time="{time}">"#,
+        name = TEST_SUITE_NAME,
+        tests = reports.len(),
+        failures = n_failures,
+        skipped = n_skipped,
+        time = format_seconds(total_duration),
+    )?;
+
+    for report in reports {
+        let testcase = format!(
+            r#"    <testcase name="{name}" classname="{classname}" time="{time}""#,
+            name = escape_xml(&report.id),
+            classname = TEST_SUITE_NAME,
+            time = format_seconds(report.duration),
+        );
+
+        if matches!(report.outcome, TestOutcome::Passed) && report.failed_attempts.is_empty() {
+            writeln!(writer, "{}/>", testcase)?;
+            continue;
+        }
+
+        writeln!(writer, "{}>", testcase)?;
+
+        // Earlier attempts which failed are recorded using the
+        // elements used by the Maven Surefire plugin for reruns.
+        let failed_attempt_element = match &report.outcome {
+            TestOutcome::Passed => "flakyFailure",
+            TestOutcome::Skipped(reason) => {
+                writeln!(
+                    writer,
+                    r#"      <skipped message="{}"/>"#,
+                    escape_xml(reason)
+                )?;
+                "rerunFailure"
+            }
+            TestOutcome::Failed(error) | TestOutcome::TimedOut(error) => {
+                let failure_type = match &report.outcome {
+                    TestOutcome::TimedOut(_) => "timeout",
+                    _ => "error",
+                };
+                writeln!(
+                    writer,
+                    r#"      <failure message="{}" type="{}">{}</failure>"#,
+                    escape_xml(&format!("{:#}", error)),
+                    failure_type,
+                    escape_xml(&format!("{:?}", error)),
+                )?;
+                "rerunFailure"
+            }
+        };
+
+        for failed in &report.failed_attempts {
+            writeln!(
+                writer,
+                r#"      <{element} message="{message}" type="error" time="{time}">{trace}</{element}>"#,
+                element = failed_attempt_element,
+                message = escape_xml(&format!("attempt {}: {:#}", failed.attempt, failed.error)),
+                time = format_seconds(failed.duration),
+                trace = escape_xml(&format!("{:?}", failed.error)),
+            )?;
+        }
+
+        writeln!(writer, "    </testcase>")?;
+    }
+
+    writeln!(writer, "  </testsuite>")?;
+    writeln!(writer, "</testsuites>")?;
+
+    Ok(())
+}
+
+/// Write the JUnit XML report for the `reports` to the file at
+/// `path`.
+pub fn write_junit_report_file(path: impl AsRef<Path>, reports: &[TestReport]) -> eyre::Result<()> {
+    let path = path.as_ref();
+    tracing::info!("Writing JUnit report to {:?}", path);
+
+    let file = std::fs::File::create(path)
+        .wrap_err_with(|| eyre::eyre!("Error creating JUnit report file {:?}", path))?;
+    let mut writer = std::io::BufWriter::new(file);
+    write_junit_report(&mut writer, reports)
+        .and_then(|_| writer.flush().map_err(eyre::Error::from))
+        .wrap_err_with(|| eyre::eyre!("Error writing JUnit report file {:?}", path))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{escape_xml, write_junit_report};
+    use crate::specification::{FailedAttempt, TestOutcome, TestReport};
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            "a &lt;b&gt; &amp; &quot;c&quot;",
+            escape_xml("a <b> & \"c\"")
+        );
+        assert_eq!("red", escape_xml("\x1b[31mred\x1b[0m"));
+    }
+
+    #[test]
+    fn test_write_junit_report() {
+        let reports = vec![
+            TestReport {
+                id: "passed".to_string(),
+                duration: Duration::from_millis(1500),
+                outcome: TestOutcome::Passed,
+                results: None,
+                attempts: 1,
+                failed_attempts: Vec::new(),
+            },
+            TestReport {
+                id: "failed".to_string(),
+                duration: Duration::from_secs(2),
+                outcome: TestOutcome::Failed(eyre::eyre!("round <1> failed")),
+                results: None,
+                attempts: 1,
+                failed_attempts: Vec::new(),
+            },
+            TestReport {
+                id: "skipped".to_string(),
+                duration: Duration::from_secs(0),
+                outcome: TestOutcome::Skipped("Skipped in specification".to_string()),
+                results: None,
+                attempts: 0,
+                failed_attempts: Vec::new(),
+            },
+            TestReport {
+                id: "flaky".to_string(),
+                duration: Duration::from_secs(3),
+                outcome: TestOutcome::Passed,
+                results: None,
+                attempts: 2,
+                failed_attempts: vec![FailedAttempt {
+                    attempt: 1,
+                    duration: Duration::from_secs(1),
+                    error: eyre::eyre!("timed out"),
+                }],
+            },
+        ];
+
+        let mut report = Vec::new();
+        write_junit_report(&mut report, &reports).unwrap();
+        let report = String::from_utf8(report).unwrap();
+
+        assert!(report.contains(r#"tests="4" failures="1" skipped="1" time="6.500""#));
+        assert!(report.contains(
+            r#"<testcase name="passed" classname="aleo-setup-integration-test" time="1.500"/>"#
+        ));
+        assert!(report.contains(r#"<failure message="round &lt;1&gt; failed""#));
+        assert!(report.contains(r#"<skipped message="Skipped in specification"/>"#));
+        assert!(report.contains(
+            r#"<flakyFailure message="attempt 1: timed out" type="error" time="1.000">"#
+        ));
+    }
+}
@@ -3,33 +3,59 @@ use waiter::IsShutdownMessage;
 
 use std::{fmt::Display, str::FromStr};
 
+pub mod baseline;
 pub mod ceremony_waiter;
 pub mod config;
 pub mod contributor;
 pub mod coordinator;
+pub mod coordinator_fault;
 pub mod drop_participant;
+pub mod event_recorder;
+pub mod fault;
+pub mod fuzz;
 pub mod git;
 pub mod join;
+pub mod junit;
+pub mod matrix;
+pub mod network_proxy;
 pub mod npm;
 pub mod options;
+pub mod pause;
 pub mod process;
 pub mod reporting;
+pub mod resources;
+pub mod retention;
+pub mod round_outcome;
+pub mod round_timing;
 pub mod rust;
+pub mod selection;
 pub mod specification;
+pub mod stand_in;
 pub mod state_monitor;
+pub mod summary;
 pub mod test;
 pub mod time_limit;
+pub mod transcript;
 pub mod util;
+pub mod validate;
 pub mod verifier;
 pub mod waiter;
 
 /// A reference to a contributor in the ceremony.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ContributorRef {
     /// Public aleo address
     pub address: AleoPublicKey,
 }
 
+impl ContributorRef {
+    /// The id used to reference this contributor by the coordinator,
+    /// and within the ceremony transcript.
+    pub fn id_on_coordinator(&self) -> String {
+        format!("{}.contributor", self.address)
+    }
+}
+
 impl std::fmt::Display for ContributorRef {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.address.fmt(f)
@@ -37,24 +63,50 @@ impl std::fmt::Display for ContributorRef {
 }
 
 /// A reference to a verifier in the ceremony.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct VerifierRef {
     /// Public aleo address
     pub address: AleoPublicKey,
 }
 
+impl VerifierRef {
+    /// The id used to reference this verifier by the coordinator, and
+    /// within the ceremony transcript.
+    pub fn id_on_coordinator(&self) -> String {
+        format!("{}.verifier", self.address)
+    }
+}
+
+impl std::fmt::Display for VerifierRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.address.fmt(f)
+    }
+}
+
 /// A reference to a participant in the ceremony.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum ParticipantRef {
     Contributor(ContributorRef),
     Verifier(VerifierRef),
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+impl ParticipantRef {
+    /// The id used to reference this participant by the coordinator,
+    /// and within the ceremony transcript.
+    pub fn id_on_coordinator(&self) -> String {
+        match self {
+            Self::Contributor(contributor) => contributor.id_on_coordinator(),
+            Self::Verifier(verifier) => verifier.id_on_coordinator(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum ShutdownReason {
     Error,
     TestFinished,
+    TimeLimit,
 }
 
 impl std::fmt::Display for ShutdownReason {
@@ -62,6 +114,7 @@ impl std::fmt::Display for ShutdownReason {
         match self {
             ShutdownReason::Error => f.write_str("there was an error"),
             ShutdownReason::TestFinished => todo!("the test is finished"),
+            ShutdownReason::TimeLimit => f.write_str("the time limit was exceeded"),
         }
     }
 }
@@ -69,7 +122,7 @@ impl std::fmt::Display for ShutdownReason {
 /// Message sent between the various components running during the
 /// setup ceremony. Each component will have a process monitor running
 /// in its own thread which will listen to these messages.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum CeremonyMessage {
     /// Notify the receivers that the specified round has started.
@@ -88,10 +141,23 @@ pub enum CeremonyMessage {
     /// sucessfully.
     /// Data is the round number.
     RoundFinished(u64),
+    /// Notify the receivers that the specified round has been
+    /// restarted (rolled back) because no contributors remained to
+    /// complete it. The coordinator then waits for participants to
+    /// join the round again.
+    /// Data is the round number.
+    RoundRestarted(u64),
     /// Notify the receivers that the coordinator is ready and waiting
     /// for participants for the specified round before starting it.
     /// Data is the round number.
     RoundWaitingForParticipants(u64),
+    /// Notify the receivers that the coordinator process has been
+    /// restarted, and has booted up again.
+    CoordinatorRestarted,
+    /// Notify the receivers that a process has been started by
+    /// [process::run_monitor_process()], with the specified `id`, and
+    /// process id `pid`.
+    ProcessStarted { id: String, pid: u32 },
     /// Notify the receivers that the coordinator has just dropped a
     /// participant in the current round.
     ParticipantDropped(ParticipantRef),
@@ -101,10 +167,35 @@ pub enum CeremonyMessage {
         contributor: ContributorRef,
         chunk: u64,
     },
+    /// The coordinator has assigned the remaining tasks of a dropped
+    /// contributor to a replacement contributor.
+    ContributorReplaced {
+        dropped: ContributorRef,
+        replacement: ContributorRef,
+        tasks: u64,
+    },
+    /// The coordinator has successfully received a verification from
+    /// a verifier at a given chunk.
+    SuccessfulVerification { verifier: VerifierRef, chunk: u64 },
     /// Tell all the recievers to shut down.
     Shutdown(ShutdownReason),
 }
 
+impl CeremonyMessage {
+    /// The round number that this message refers to (if any).
+    pub fn round(&self) -> Option<u64> {
+        match self {
+            Self::RoundStarted(round)
+            | Self::RoundStartedAggregation(round)
+            | Self::RoundAggregated(round)
+            | Self::RoundFinished(round)
+            | Self::RoundRestarted(round)
+            | Self::RoundWaitingForParticipants(round) => Some(*round),
+            _ => None,
+        }
+    }
+}
+
 impl IsShutdownMessage for CeremonyMessage {
     fn is_shutdown_message(&self) -> bool {
         matches!(self, Self::Shutdown(_))
@@ -112,7 +203,7 @@ impl IsShutdownMessage for CeremonyMessage {
 }
 
 /// Which phase of the setup is to be run.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Environment {
     #[serde(rename = "development")]
     Development,
@@ -3,14 +3,21 @@
 
 use aleo_setup_integration_test::{
     config::Config,
+    fuzz::{fuzz_specification_file_name, generate_specification, random_seed, FuzzLimits},
     options::CmdOptions,
     reporting::{setup_reporting, LogFileWriter},
-    specification::{Specification, TestId},
+    selection::TestSelection,
+    specification::{clean_out_dir, Specification},
+    util::create_dir_if_not_exists,
+    validate::validation_result,
 };
 
+use color_eyre::Section;
 use eyre::Context;
 use structopt::StructOpt;
 
+use std::path::PathBuf;
+
 /// The main method of the test, which runs the test. In the future
 /// this may accept command line arguments to configure how the test
 /// is run.
@@ -20,11 +27,6 @@ fn main() -> eyre::Result<()> {
 
     let options: CmdOptions = CmdOptions::from_args();
 
-    let only_tests: Vec<TestId> = match &options.id {
-        Some(id) => vec![id.clone()],
-        None => Vec::new(),
-    };
-
     let config_string = match &options.config_file {
         Some(config_file) => {
             tracing::info!("Loading configuration from file: {:?}", &config_file);
@@ -37,38 +39,186 @@ fn main() -> eyre::Result<()> {
         }
     };
 
-    let config: Config = ron::from_str(&config_string)
+    let mut config: Config = ron::from_str(&config_string)
         .wrap_err_with(|| eyre::eyre!("Error while parsing configuration"))?;
 
+    if let Some(parallelism) = options.parallelism {
+        config.parallelism = parallelism;
+    }
+
+    if let Some(junit) = &options.junit {
+        config.junit = Some(junit.clone());
+    }
+
+    if let Some(save_baseline) = &options.save_baseline {
+        config.save_baseline = Some(save_baseline.clone());
+    }
+
+    if let Some(compare_baseline) = &options.compare_baseline {
+        config.compare_baseline = Some(compare_baseline.clone());
+    }
+
+    if options.fail_fast {
+        config.fail_fast = true;
+    }
+
+    let selection = TestSelection {
+        ids: options.ids.clone(),
+        tags: options.tags.clone(),
+        exclude_tags: options.exclude_tags.clone(),
+    };
+
+    let result = match options.fuzz {
+        Some(seed) => run_fuzz(
+            seed.unwrap_or_else(random_seed),
+            options.fuzz_tests,
+            config,
+            &selection,
+            &log_writer,
+        ),
+        None if options.list => list_tests(&options.specification_files, &selection),
+        None if options.validate => {
+            validate_specification_files(&options.specification_files, &config)
+        }
+        None => run_specification_files(
+            &options.specification_files,
+            &config,
+            &selection,
+            &log_writer,
+        ),
+    };
+
+    // report the error to tracing and log file
+    if let Err(error) = &result {
+        tracing::error!("{}", error);
+    }
+
+    result
+}
+
+/// Print the tests in the `specification_files` which are selected
+/// to run with the `selection`.
+fn list_tests(specification_files: &[PathBuf], selection: &TestSelection) -> eyre::Result<()> {
+    let specification = Specification::load(specification_files)?;
+    let (selected, _skipped) = specification.select(selection);
+
+    let id_width = selected
+        .iter()
+        .map(|options| options.id.len())
+        .max()
+        .unwrap_or(0);
+    for options in &selected {
+        println!(
+            "{:width$}  tags: {}",
+            options.id,
+            options.all_tags().join(", "),
+            width = id_width
+        );
+    }
+    println!(
+        "{} of {} tests selected.",
+        selected.len(),
+        specification.tests.len()
+    );
+
+    Ok(())
+}
+
+/// Check the `specification_files` and the `config` for problems,
+/// without running any tests.
+fn validate_specification_files(
+    specification_files: &[PathBuf],
+    config: &Config,
+) -> eyre::Result<()> {
+    tracing::info!("Validating specifications {:?}", specification_files);
+
+    let specification = Specification::load(specification_files)?;
+    validation_result(specification.validate(config))
+        .wrap_err_with(|| eyre::eyre!("Invalid specifications {:?}", specification_files))?;
+
     tracing::info!(
-        "Running integration test using specification {:?}",
-        &options.specification_file
+        "Specifications {:?} are valid ({} tests).",
+        specification_files,
+        specification.tests.len()
     );
+    Ok(())
+}
 
-    let specification_string = std::fs::read_to_string(&options.specification_file)
-        .wrap_err_with(|| eyre::eyre!("Error while reading specification ron file"))?;
+/// Run the tests in the `specification_files` which are selected to
+/// run with the `selection`.
+fn run_specification_files(
+    specification_files: &[PathBuf],
+    config: &Config,
+    selection: &TestSelection,
+    log_writer: &LogFileWriter,
+) -> eyre::Result<()> {
+    tracing::info!(
+        "Running integration test using specifications {:?}",
+        specification_files
+    );
 
-    let specification: Specification =
-        ron::from_str(&specification_string).wrap_err_with(|| {
-            eyre::eyre!(
-                "Error while parsing test specification {:?}",
-                &options.specification_file
-            )
-        })?;
+    let specification = Specification::load(specification_files)?;
 
-    let result = specification
-        .run(&config, &only_tests, &log_writer)
+    specification
+        .run(config, selection, log_writer)
         .wrap_err_with(|| {
             eyre::eyre!(
                 "Error while running tests specified in {:?}",
-                &options.specification_file
+                specification_files
             )
-        });
+        })
+}
 
-    // report the error to tracing and log file
-    if let Err(error) = &result {
-        tracing::error!("{}", error);
+/// Run `n_tests` randomly generated tests from `seed`, saving the
+/// generated specification to the out directory so that it can be
+/// replayed.
+fn run_fuzz(
+    seed: u64,
+    n_tests: u64,
+    mut config: Config,
+    selection: &TestSelection,
+    log_writer: &LogFileWriter,
+) -> eyre::Result<()> {
+    let limits = FuzzLimits::new(&config.fuzz, config.stand_ins.is_some())?;
+    let specification = generate_specification(seed, n_tests, &limits);
+    let specification_string = ron::ser::to_string_pretty(&specification, Default::default())?;
+
+    tracing::info!(
+        "Running {} fuzz test(s) generated with seed {}:\n{}",
+        n_tests,
+        seed,
+        specification_string
+    );
+
+    // Clean the out directory here rather than when the tests start,
+    // so that the specification is saved before the tests run (in
+    // case the integration test itself does not finish).
+    if config.clean {
+        clean_out_dir(&config.out_dir)?;
     }
+    create_dir_if_not_exists(&config.out_dir)?;
+    let specification_path = config.out_dir.join(fuzz_specification_file_name(seed));
+    std::fs::write(&specification_path, &specification_string).wrap_err_with(|| {
+        eyre::eyre!(
+            "Error while saving fuzz specification to {:?}",
+            specification_path
+        )
+    })?;
+    tracing::info!("Saved fuzz specification to {:?}", specification_path);
 
-    result
+    config.clean = false;
+    specification
+        .run(&config, selection, log_writer)
+        .wrap_err_with(|| {
+            eyre::eyre!(
+                "Error while running fuzz tests generated with seed {}",
+                seed
+            )
+        })
+        .with_note(|| {
+            format!(
+                "Replay with `--fuzz {}`, or run the saved specification {:?}.",
+                seed, specification_path
+            )
+        })
 }
@@ -0,0 +1,254 @@
+//! Parameter matrices in a [crate::specification::Specification],
+//! which expand a template test into an individual test for each
+//! combination of the values of the matrix's axes.
+
+use serde::Deserialize;
+
+use crate::{specification::SingleTestOptions, Environment};
+
+/// The values of an axis of a [TestMatrix]. Either a list of values
+/// (e.g. `[1, 2, 4]`), or a range using the same syntax as rust
+/// (e.g. `"1..5"` or `"1..=5"`).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum MatrixAxis {
+    Values(Vec<u8>),
+    Range(String),
+}
+
+impl Default for MatrixAxis {
+    fn default() -> Self {
+        Self::Values(Vec::new())
+    }
+}
+
+impl MatrixAxis {
+    /// The values of the axis, in order.
+    pub fn values(&self) -> eyre::Result<Vec<u8>> {
+        match self {
+            Self::Values(values) => Ok(values.clone()),
+            Self::Range(range) => {
+                let parse = |s: &str| {
+                    s.trim().parse::<u8>().map_err(|error| {
+                        eyre::eyre!("Unable to parse matrix range {:?}: {}", range, error)
+                    })
+                };
+
+                if let Some((start, end)) = range.split_once("..=") {
+                    Ok((parse(start)?..=parse(end)?).collect())
+                } else if let Some((start, end)) = range.split_once("..") {
+                    Ok((parse(start)?..parse(end)?).collect())
+                } else {
+                    Err(eyre::eyre!(
+                        "Expected matrix range {:?} to be in the format \"start..end\" \
+                        or \"start..=end\"",
+                        range
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// A combination of values which is excluded from the expansion of a
+/// [TestMatrix]. Matches any combination which has all of the values
+/// which are specified.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatrixExclusion {
+    #[serde(default)]
+    pub verifiers: Option<u8>,
+    #[serde(default)]
+    pub contributors: Option<u8>,
+    #[serde(default)]
+    pub environment: Option<Environment>,
+}
+
+impl MatrixExclusion {
+    fn matches(&self, combination: &Combination) -> bool {
+        self.verifiers
+            .map_or(true, |verifiers| verifiers == combination.verifiers)
+            && self.contributors.map_or(true, |contributors| {
+                Some(contributors) == combination.contributors
+            })
+            && self
+                .environment
+                .map_or(true, |environment| environment == combination.environment)
+    }
+}
+
+/// Specification for a matrix of tests, which expands into a test
+/// for each combination of the values of its axes. Axes which are
+/// empty use the value from the `template`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestMatrix {
+    /// The test which each combination is applied to. The `id` of the
+    /// template is used as the prefix for the ids of the generated
+    /// tests, for example `inner-v2-c3`.
+    pub template: SingleTestOptions,
+    /// (Optional) Values for [SingleTestOptions::verifiers].
+    /// Default: `[]`.
+    #[serde(default)]
+    pub verifiers: MatrixAxis,
+    /// (Optional) Values for the number of contributors in every
+    /// round of the test. Default: `[]`.
+    #[serde(default)]
+    pub contributors: MatrixAxis,
+    /// (Optional) Values for [SingleTestOptions::environment].
+    /// Default: `[]`.
+    #[serde(default)]
+    pub environment: Vec<Environment>,
+    /// (Optional) Combinations to exclude from the expansion.
+    /// Default: `[]`.
+    #[serde(default)]
+    pub exclude: Vec<MatrixExclusion>,
+}
+
+/// A combination of the values of the axes of a [TestMatrix].
+#[derive(Debug, Clone, Copy)]
+struct Combination {
+    verifiers: u8,
+    contributors: Option<u8>,
+    environment: Environment,
+}
+
+impl TestMatrix {
+    /// Expand the matrix into the individual tests, in the order of
+    /// the `environment`, `verifiers` and `contributors` axes.
+    pub fn expand(&self) -> eyre::Result<Vec<SingleTestOptions>> {
+        let verifiers = self.verifiers.values()?;
+        let contributors = self.contributors.values()?;
+
+        // Use the template's value for axes which are not specified,
+        // and don't include them in the generated id.
+        let environments: Vec<Option<Environment>> = if self.environment.is_empty() {
+            vec![None]
+        } else {
+            self.environment.iter().copied().map(Some).collect()
+        };
+        let verifiers: Vec<Option<u8>> = if verifiers.is_empty() {
+            vec![None]
+        } else {
+            verifiers.into_iter().map(Some).collect()
+        };
+        let contributors: Vec<Option<u8>> = if contributors.is_empty() {
+            vec![None]
+        } else {
+            contributors.into_iter().map(Some).collect()
+        };
+
+        let mut tests = Vec::new();
+        for environment in &environments {
+            for n_verifiers in &verifiers {
+                for n_contributors in &contributors {
+                    let combination = Combination {
+                        verifiers: n_verifiers.unwrap_or(self.template.verifiers),
+                        contributors: *n_contributors,
+                        environment: environment.unwrap_or(self.template.environment),
+                    };
+
+                    if self
+                        .exclude
+                        .iter()
+                        .any(|exclusion| exclusion.matches(&combination))
+                    {
+                        continue;
+                    }
+
+                    let mut id = self.template.id.clone();
+                    if let Some(environment) = environment {
+                        id.push_str(&format!("-{}", environment));
+                    }
+                    if let Some(n_verifiers) = n_verifiers {
+                        id.push_str(&format!("-v{}", n_verifiers));
+                    }
+                    if let Some(n_contributors) = n_contributors {
+                        id.push_str(&format!("-c{}", n_contributors));
+                    }
+
+                    let mut test = self.template.clone();
+                    test.id = id;
+                    test.verifiers = combination.verifiers;
+                    test.environment = combination.environment;
+                    if let Some(n_contributors) = combination.contributors {
+                        for round in &mut test.rounds {
+                            round.contributors = n_contributors;
+                        }
+                    }
+
+                    tests.push(test);
+                }
+            }
+        }
+
+        if tests.is_empty() {
+            return Err(eyre::eyre!(
+                "Matrix {:?} does not expand into any tests",
+                self.template.id
+            ));
+        }
+
+        Ok(tests)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MatrixAxis, TestMatrix};
+    use crate::Environment;
+
+    #[test]
+    fn test_matrix_axis_range() {
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            MatrixAxis::Range("1..5".to_string()).values().unwrap()
+        );
+        assert_eq!(
+            vec![1, 2, 3, 4, 5],
+            MatrixAxis::Range("1..=5".to_string()).values().unwrap()
+        );
+        assert!(MatrixAxis::Range("1-5".to_string()).values().is_err());
+    }
+
+    #[test]
+    fn test_expand_matrix() {
+        let matrix: TestMatrix = ron::from_str(
+            r#"(
+                template: (
+                    id: "matrix",
+                    verifiers: 1,
+                    environment: development,
+                    rounds: [(contributors: 1), (contributors: 1)],
+                ),
+                verifiers: [1, 2],
+                contributors: "1..=3",
+                environment: [development, inner],
+                exclude: [
+                    (environment: Some(inner), verifiers: Some(2)),
+                    (contributors: Some(3)),
+                ],
+            )"#,
+        )
+        .unwrap();
+
+        let tests = matrix.expand().unwrap();
+        let ids: Vec<&str> = tests.iter().map(|test| test.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "matrix-development-v1-c1",
+                "matrix-development-v1-c2",
+                "matrix-development-v2-c1",
+                "matrix-development-v2-c2",
+                "matrix-inner-v1-c1",
+                "matrix-inner-v1-c2",
+            ]
+        );
+
+        let test = &tests[3];
+        assert_eq!(test.verifiers, 2);
+        assert_eq!(test.environment, Environment::Development);
+        assert!(test.rounds.iter().all(|round| round.contributors == 2));
+    }
+}
@@ -0,0 +1,505 @@
+//! This module contains a fault-injecting TCP proxy, which the
+//! integration test runs between each participant and the
+//! coordinator. Faults (latency, bandwidth caps, connection resets,
+//! stalls and partitions) can be injected into the connection of an
+//! individual participant during a round, see [NetworkFaultConfig].
+
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    num::NonZeroU64,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use eyre::Context;
+use mpmc_bus::{Receiver, TryRecvError};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fault::{FaultTarget, FaultTrigger, FaultTriggerState},
+    join::MultiJoinable,
+    CeremonyMessage, ParticipantRef,
+};
+
+/// How often the proxy threads check for shutdown, and for changes to
+/// the injected faults.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Size of the buffer used to forward data between the participant
+/// and the coordinator.
+const BUFFER_SIZE: usize = 8192;
+
+/// Timeout for the proxy to connect to the coordinator.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A fault which can be injected into the connection between a
+/// participant and the coordinator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NetworkFault {
+    /// Delay the data sent in each direction by this amount of time.
+    Latency(#[serde(with = "humantime_serde")] Duration),
+    /// Limit the data sent in each direction to this number of bytes
+    /// per second.
+    Bandwidth(NonZeroU64),
+    /// Reset all the open connections. New connections are accepted
+    /// as normal.
+    Reset,
+    /// Stop forwarding data in either direction, while keeping the
+    /// connections open.
+    Stall,
+    /// Close all the open connections, and close any new connections
+    /// as soon as they are accepted.
+    Partition,
+}
+
+/// The configuration for injecting a fault into the connection
+/// between a participant and the coordinator during a round. e.g.
+/// `(target: Contributor(2), trigger: AfterContributions(5), fault:
+/// Partition, duration: Some("60s"))`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkFaultConfig {
+    /// The participant whose connection the fault is injected into.
+    pub target: FaultTarget,
+    /// When the fault is injected.
+    pub trigger: FaultTrigger,
+    /// The fault to inject.
+    pub fault: NetworkFault,
+    /// (Optional) How long the fault lasts for after it has been
+    /// injected (not applicable to [NetworkFault::Reset]). If `None`
+    /// then the fault lasts until the end of the round. Default:
+    /// `None`.
+    #[serde(default, with = "humantime_serde")]
+    pub duration: Option<Duration>,
+}
+
+/// The faults currently injected into a [NetworkProxy].
+#[derive(Debug, Clone, Default)]
+struct ProxyFaults {
+    latency: Option<Duration>,
+    bandwidth: Option<NonZeroU64>,
+    stalled: bool,
+    partitioned: bool,
+    /// Incremented to close all connections opened before the
+    /// increment.
+    generation: u64,
+}
+
+/// A handle used to inject faults into a [NetworkProxy].
+#[derive(Debug, Clone)]
+pub struct NetworkProxyHandle {
+    id: String,
+    faults: Arc<Mutex<ProxyFaults>>,
+}
+
+impl NetworkProxyHandle {
+    /// The id of the participant that the proxy is for.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Inject the `fault` into the connections of this proxy.
+    pub fn inject(&self, fault: &NetworkFault) {
+        let mut faults = self.lock();
+        match fault {
+            NetworkFault::Latency(latency) => faults.latency = Some(*latency),
+            NetworkFault::Bandwidth(bandwidth) => faults.bandwidth = Some(*bandwidth),
+            NetworkFault::Reset => faults.generation += 1,
+            NetworkFault::Stall => faults.stalled = true,
+            NetworkFault::Partition => {
+                faults.partitioned = true;
+                faults.generation += 1;
+            }
+        }
+    }
+
+    /// Clear a `fault` that was previously injected with
+    /// [NetworkProxyHandle::inject()].
+    pub fn clear(&self, fault: &NetworkFault) {
+        let mut faults = self.lock();
+        match fault {
+            NetworkFault::Latency(_) => faults.latency = None,
+            NetworkFault::Bandwidth(_) => faults.bandwidth = None,
+            NetworkFault::Reset => {}
+            NetworkFault::Stall => faults.stalled = false,
+            NetworkFault::Partition => faults.partitioned = false,
+        }
+    }
+
+    /// A snapshot of the faults currently injected.
+    fn faults(&self) -> ProxyFaults {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ProxyFaults> {
+        // The faults are always left in a valid state, so it is safe
+        // to ignore poisoning.
+        self.faults
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A TCP proxy between a participant and the coordinator, listening
+/// on an ephemeral localhost port.
+pub struct NetworkProxy {
+    listener: TcpListener,
+    upstream: SocketAddr,
+    handle: NetworkProxyHandle,
+}
+
+impl NetworkProxy {
+    /// Bind a new proxy for the participant with the specified `id`,
+    /// which will forward connections to the coordinator listening
+    /// on `upstream`. Connections are not accepted until
+    /// [NetworkProxy::run()] is called.
+    pub fn bind(id: impl Into<String>, upstream: SocketAddr) -> eyre::Result<Self> {
+        let id = id.into();
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .wrap_err_with(|| eyre::eyre!("Error binding network proxy for {}", id))?;
+        listener.set_nonblocking(true)?;
+
+        Ok(Self {
+            listener,
+            upstream,
+            handle: NetworkProxyHandle {
+                id,
+                faults: Arc::new(Mutex::new(ProxyFaults::default())),
+            },
+        })
+    }
+
+    /// URL used by the participant to connect to the coordinator via
+    /// this proxy.
+    pub fn api_url(&self) -> eyre::Result<String> {
+        Ok(format!("http://{}", self.listener.local_addr()?))
+    }
+
+    /// A handle used to inject faults into this proxy.
+    pub fn handle(&self) -> NetworkProxyHandle {
+        self.handle.clone()
+    }
+
+    /// Start accepting and forwarding connections in a new thread,
+    /// until a [CeremonyMessage::Shutdown] is received.
+    pub fn run(self, mut ceremony_rx: Receiver<CeremonyMessage>) -> NetworkProxyJoin {
+        let id = self.handle.id.clone();
+        let span = tracing::error_span!("network_proxy", participant = %id);
+
+        let join = std::thread::spawn(move || {
+            let _guard = span.enter();
+            let shutdown = Arc::new(AtomicBool::new(false));
+
+            'accept: loop {
+                loop {
+                    match ceremony_rx.try_recv() {
+                        Ok(CeremonyMessage::Shutdown(_)) | Err(TryRecvError::Disconnected) => {
+                            break 'accept;
+                        }
+                        Ok(_) => {}
+                        Err(TryRecvError::Empty) => break,
+                    }
+                }
+
+                match self.listener.accept() {
+                    Ok((client, _)) => {
+                        if let Err(error) = self.forward(client, &shutdown) {
+                            tracing::warn!("Unable to forward connection: {:?}", error);
+                        }
+                    }
+                    Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                        std::thread::sleep(POLL_INTERVAL);
+                    }
+                    Err(error) => {
+                        tracing::error!("Error accepting connection: {}", error);
+                        std::thread::sleep(POLL_INTERVAL);
+                    }
+                }
+            }
+
+            // Tell the threads forwarding connections to close.
+            shutdown.store(true, Ordering::Relaxed);
+            tracing::debug!("Thread terminated gracefully");
+        });
+
+        NetworkProxyJoin { id, join }
+    }
+
+    /// Forward the `client` connection to the coordinator, spawning a
+    /// thread to forward the data in each direction.
+    fn forward(&self, client: TcpStream, shutdown: &Arc<AtomicBool>) -> eyre::Result<()> {
+        client.set_nonblocking(false)?;
+
+        let faults = self.handle.faults();
+        if faults.partitioned {
+            tracing::debug!("Closing new connection due to partition.");
+            return Ok(());
+        }
+
+        let server = TcpStream::connect_timeout(&self.upstream, CONNECT_TIMEOUT)
+            .wrap_err("Error connecting to coordinator")?;
+
+        for (from, to) in [(client.try_clone()?, server.try_clone()?), (server, client)] {
+            let handle = self.handle.clone();
+            let shutdown = shutdown.clone();
+            let generation = faults.generation;
+            std::thread::spawn(move || pump(from, to, generation, handle, shutdown));
+        }
+
+        Ok(())
+    }
+}
+
+/// Forward data from `from` to `to`, applying the faults currently
+/// injected via `handle`, until either connection is closed, the
+/// connection is reset (the faults `generation` changes), or the
+/// proxy is shut down.
+fn pump(
+    mut from: TcpStream,
+    mut to: TcpStream,
+    generation: u64,
+    handle: NetworkProxyHandle,
+    shutdown: Arc<AtomicBool>,
+) {
+    if let Err(error) = from.set_read_timeout(Some(POLL_INTERVAL)) {
+        tracing::error!("Error setting read timeout: {}", error);
+        return;
+    }
+
+    let mut buffer = [0u8; BUFFER_SIZE];
+
+    while !shutdown.load(Ordering::Relaxed) {
+        let faults = handle.faults();
+        if faults.generation != generation {
+            tracing::debug!("Resetting connection.");
+            break;
+        }
+
+        if faults.stalled {
+            std::thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+
+        // Read in smaller pieces when the bandwidth is capped so that
+        // the data is sent at a steady rate.
+        let max_read = faults.bandwidth.map_or(BUFFER_SIZE, |bandwidth| {
+            ((bandwidth.get() / 10) as usize).clamp(1, BUFFER_SIZE)
+        });
+
+        let n_read = match from.read(&mut buffer[..max_read]) {
+            Ok(0) => {
+                // Pass on the end of the stream, the other direction
+                // may still be in use.
+                let _ = to.shutdown(Shutdown::Write);
+                return;
+            }
+            Ok(n_read) => n_read,
+            Err(error)
+                if error.kind() == ErrorKind::WouldBlock || error.kind() == ErrorKind::TimedOut =>
+            {
+                continue
+            }
+            Err(_) => break,
+        };
+
+        if let Some(latency) = faults.latency {
+            std::thread::sleep(latency);
+        }
+
+        if let Some(bandwidth) = faults.bandwidth {
+            std::thread::sleep(Duration::from_secs_f64(
+                n_read as f64 / bandwidth.get() as f64,
+            ));
+        }
+
+        if to.write_all(&buffer[..n_read]).is_err() {
+            break;
+        }
+    }
+
+    let _ = from.shutdown(Shutdown::Both);
+    let _ = to.shutdown(Shutdown::Both);
+}
+
+/// A join handle for the thread created in [NetworkProxy::run()].
+#[must_use]
+pub struct NetworkProxyJoin {
+    id: String,
+    join: JoinHandle<()>,
+}
+
+impl std::fmt::Debug for NetworkProxyJoin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NetworkProxyJoin({})", self.id)
+    }
+}
+
+impl MultiJoinable for NetworkProxyJoin {
+    fn join(self: Box<Self>) -> std::thread::Result<()> {
+        self.join.join()
+    }
+}
+
+/// Inject the fault specified in `config` into the connection of a
+/// participant via its `proxy` during the specified `round`. The
+/// `participant` is required for [FaultTrigger::AfterContributions].
+/// Returns an error if the round finished (or restarted) before the
+/// fault was injected.
+pub fn inject_network_fault(
+    round: u64,
+    config: NetworkFaultConfig,
+    participant: Option<ParticipantRef>,
+    proxy: NetworkProxyHandle,
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+) -> JoinHandle<eyre::Result<()>> {
+    let span = tracing::error_span!("network_fault", participant = %proxy.id());
+    std::thread::spawn(move || {
+        let _guard = span.enter();
+        let mut trigger_state = FaultTriggerState::new(round, participant);
+        let mut injected_time: Option<Instant> = None;
+
+        loop {
+            // Sleep occasionally because otherwise this loop will run too fast.
+            std::thread::sleep(Duration::from_millis(100));
+
+            // Process all the messages received since the last check.
+            let mut round_finished = false;
+            let mut shutdown = false;
+            loop {
+                match ceremony_rx.try_recv() {
+                    Ok(CeremonyMessage::RoundFinished(_))
+                    | Ok(CeremonyMessage::RoundRestarted(_)) => {
+                        round_finished = true;
+                        break;
+                    }
+                    Ok(CeremonyMessage::Shutdown(_)) => {
+                        shutdown = true;
+                        break;
+                    }
+                    Ok(message) => trigger_state.update(&message),
+                    Err(TryRecvError::Disconnected) => {
+                        panic!("`ceremony_rx` disconnected");
+                    }
+                    Err(TryRecvError::Empty) => break,
+                }
+            }
+
+            if round_finished || shutdown {
+                if injected_time.is_some() {
+                    proxy.clear(&config.fault);
+                } else if round_finished {
+                    return Err(eyre::eyre!(
+                        "Round {} ended before the network fault {:?} was injected.",
+                        round,
+                        config
+                    ));
+                }
+                break;
+            }
+
+            match injected_time {
+                None => {
+                    if trigger_state.is_triggered(&config.trigger) {
+                        tracing::info!("Injecting network fault ({:?}).", config);
+                        proxy.inject(&config.fault);
+
+                        if let NetworkFault::Reset = config.fault {
+                            break;
+                        }
+                        injected_time = Some(Instant::now());
+                    }
+                }
+                Some(injected_time) => {
+                    if let Some(duration) = config.duration {
+                        if injected_time.elapsed() >= duration {
+                            tracing::info!("Clearing network fault ({:?}).", config.fault);
+                            proxy.clear(&config.fault);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        tracing::debug!("Thread closing gracefully.");
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::{SocketAddr, TcpListener, TcpStream},
+        time::{Duration, Instant},
+    };
+
+    use mpmc_bus::Bus;
+
+    use super::{NetworkFault, NetworkProxy};
+    use crate::{join::MultiJoinable, CeremonyMessage, ShutdownReason};
+
+    /// Run an echo server, returning its address.
+    fn echo_server() -> SocketAddr {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                std::thread::spawn(move || {
+                    let mut buffer = [0u8; 1024];
+                    while let Ok(n_read) = stream.read(&mut buffer) {
+                        if n_read == 0 || stream.write_all(&buffer[..n_read]).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        address
+    }
+
+    /// Send `message` via the proxy at `api_url` and read the echo.
+    fn echo(api_url: &str, message: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut stream = TcpStream::connect(api_url.trim_start_matches("http://"))?;
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        stream.write_all(message)?;
+        let mut response = vec![0u8; message.len()];
+        stream.read_exact(&mut response)?;
+        Ok(response)
+    }
+
+    #[test]
+    fn test_network_proxy_faults() {
+        let bus: Bus<CeremonyMessage> = Bus::new(100);
+        let ceremony_tx = bus.broadcaster();
+
+        let proxy = NetworkProxy::bind("contributor", echo_server()).unwrap();
+        let api_url = proxy.api_url().unwrap();
+        let handle = proxy.handle();
+        let join = proxy.run(bus.subscribe());
+
+        assert_eq!(b"hello".to_vec(), echo(&api_url, b"hello").unwrap());
+
+        let latency = NetworkFault::Latency(Duration::from_millis(200));
+        handle.inject(&latency);
+        let start_time = Instant::now();
+        assert_eq!(b"hello".to_vec(), echo(&api_url, b"hello").unwrap());
+        assert!(start_time.elapsed() >= Duration::from_millis(400));
+        handle.clear(&latency);
+
+        handle.inject(&NetworkFault::Partition);
+        assert!(echo(&api_url, b"hello").is_err());
+        handle.clear(&NetworkFault::Partition);
+        assert_eq!(b"hello".to_vec(), echo(&api_url, b"hello").unwrap());
+
+        ceremony_tx
+            .broadcast(CeremonyMessage::Shutdown(ShutdownReason::TestFinished))
+            .unwrap();
+        Box::new(join).join().unwrap();
+    }
+}
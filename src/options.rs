@@ -5,8 +5,6 @@ use std::path::PathBuf;
 use serde::Serialize;
 use structopt::StructOpt;
 
-use crate::specification::TestId;
-
 /// Command line options for running the Aleo Setup integration test.
 /// More complex options (such as drops) are available via the `multi`
 /// command interface by specifying the test in `ron` format.
@@ -16,13 +14,71 @@ use crate::specification::TestId;
     about = "An integration test for the aleo-setup and aleo-setup-coordinator repositories."
 )]
 pub struct CmdOptions {
-    /// ron file specifying the tests.
-    pub specification_file: PathBuf,
+    /// ron files specifying the tests, or directories containing
+    /// them. The tests from all of the files are combined. Not
+    /// required when running with `--fuzz`.
+    #[structopt(required_unless = "fuzz")]
+    pub specification_files: Vec<PathBuf>,
     /// ron file specifying the configuration for running the tests.
     #[structopt(long = "config")]
     pub config_file: Option<PathBuf>,
-    /// Test with only a specific test id contained within the
-    /// specification file.
+    /// Run only the tests with this id, can be a glob pattern (e.g.
+    /// `"drop-*"`) and can be specified multiple times. Overrides
+    /// the test's `skip` value.
     #[structopt(long = "id")]
-    pub id: Option<TestId>,
+    pub ids: Vec<String>,
+    /// Run only the tests with this tag (or environment), can be
+    /// specified multiple times to select the tests with all of the
+    /// tags. Overrides the test's `skip` value.
+    #[structopt(long = "tag")]
+    pub tags: Vec<String>,
+    /// Don't run the tests with this tag (or environment), can be
+    /// specified multiple times.
+    #[structopt(long = "exclude-tag")]
+    pub exclude_tags: Vec<String>,
+    /// Print the tests which are selected to run, without running
+    /// them.
+    #[structopt(long = "list", conflicts_with = "fuzz")]
+    pub list: bool,
+    /// The maximum number of tests to run at the same time,
+    /// overrides the `parallelism` value in the configuration file.
+    #[structopt(long = "parallelism")]
+    pub parallelism: Option<usize>,
+    /// Write a JUnit XML report of the test results to this path,
+    /// overrides the `junit` value in the configuration file.
+    #[structopt(long = "junit")]
+    pub junit: Option<PathBuf>,
+    /// Save the results of each test which passes as the baseline
+    /// with this name, overrides the `save_baseline` value in the
+    /// configuration file.
+    #[structopt(long = "save-baseline")]
+    pub save_baseline: Option<String>,
+    /// Compare the results of each test which passes against the
+    /// baseline with this name, overrides the `compare_baseline`
+    /// value in the configuration file.
+    #[structopt(long = "compare-baseline")]
+    pub compare_baseline: Option<String>,
+    /// Stop running tests after the first test fails, enables the
+    /// `fail_fast` option in the configuration file.
+    #[structopt(long = "fail-fast")]
+    pub fail_fast: bool,
+    /// Run randomly generated tests instead of a specification file.
+    /// The tests are generated from the specified seed, or from a
+    /// random seed if none is specified. The seed and the generated
+    /// specification are logged, and the specification is saved to
+    /// `fuzz-<seed>.ron` in the out directory so that it can be
+    /// replayed.
+    #[structopt(long = "fuzz")]
+    pub fuzz: Option<Option<u64>>,
+    /// The number of tests to generate with `--fuzz`. Each test is
+    /// generated from its own seed (included in its id), so it can
+    /// be replayed on its own with `--fuzz <seed>`.
+    #[structopt(long = "fuzz-tests", default_value = "1")]
+    pub fuzz_tests: u64,
+    /// Check the specification and configuration files for problems
+    /// without running any tests. All of the problems which are
+    /// found are reported, and the exit status is non-zero if there
+    /// are any.
+    #[structopt(long = "validate", conflicts_with = "fuzz")]
+    pub validate: bool,
 }
@@ -0,0 +1,198 @@
+//! Suspending (`SIGSTOP`) and resuming (`SIGCONT`) the processes of
+//! participants during a round, to simulate participants whose
+//! computers go to sleep (e.g. closing a laptop).
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use mpmc_bus::{Receiver, TryRecvError};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fault::{FaultTarget, FaultTrigger, FaultTriggerState},
+    process::signal_process,
+    CeremonyMessage, ParticipantRef,
+};
+
+/// The configuration for pausing the process of a participant during
+/// a round. e.g. `(target: Contributor(1), trigger:
+/// AfterContributions(2), duration: "30s")`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PauseConfig {
+    /// The participant whose process is paused.
+    pub target: FaultTarget,
+    /// When the process is paused.
+    pub trigger: FaultTrigger,
+    /// How long the process is paused for before it is resumed. If
+    /// this exceeds the coordinator's timeouts then the participant
+    /// is expected to be dropped, see [PauseConfig::expect_drop()].
+    #[serde(with = "humantime_serde")]
+    pub duration: Duration,
+}
+
+impl PauseConfig {
+    /// Whether the participant is expected to be dropped by the
+    /// coordinator because of this pause, given the coordinator's
+    /// `contributor_seen_timeout` and `participant_lock_timeout`.
+    /// Returns an error if the duration lies between the two
+    /// timeouts, because then whether the participant is dropped
+    /// depends on whether it held a lock on a chunk when it was
+    /// paused.
+    pub fn expect_drop(
+        &self,
+        contributor_seen_timeout: Duration,
+        participant_lock_timeout: Duration,
+    ) -> eyre::Result<bool> {
+        let shorter_timeout = contributor_seen_timeout.min(participant_lock_timeout);
+        let longer_timeout = contributor_seen_timeout.max(participant_lock_timeout);
+
+        if self.duration <= shorter_timeout {
+            Ok(false)
+        } else if self.duration > longer_timeout {
+            Ok(true)
+        } else {
+            Err(eyre::eyre!(
+                "The pause duration ({}) is between the coordinator's \
+                    `contributor_seen_timeout` ({}) and `participant_lock_timeout` ({}), \
+                    so it is unknown whether the participant will be dropped.",
+                humantime::format_duration(self.duration),
+                humantime::format_duration(contributor_seen_timeout),
+                humantime::format_duration(participant_lock_timeout),
+            ))
+        }
+    }
+}
+
+/// The ids of the processes of the participants which have been
+/// started, indexed by participant id.
+pub type ProcessIds = Arc<Mutex<HashMap<String, u32>>>;
+
+/// Pause the process of the participant with the specified `id`
+/// during the specified `round` (with `SIGSTOP`) according to the
+/// `config`, and resume it (with `SIGCONT`) once the pause duration
+/// has elapsed, or the round has ended. The process id is obtained
+/// from `process_ids` when the pause is triggered. The `participant`
+/// is required for [FaultTrigger::AfterContributions]. Returns an
+/// error if the round finished (or restarted) before the process was
+/// paused.
+pub fn pause_participant(
+    round: u64,
+    config: PauseConfig,
+    id: String,
+    participant: Option<ParticipantRef>,
+    process_ids: ProcessIds,
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+) -> JoinHandle<eyre::Result<()>> {
+    let span = tracing::error_span!("pause", participant = %id);
+    std::thread::spawn(move || {
+        let _guard = span.enter();
+        let mut trigger_state = FaultTriggerState::new(round, participant);
+        // The paused process, and the time that it was paused.
+        let mut paused: Option<(u32, Instant)> = None;
+
+        loop {
+            // Sleep occasionally because otherwise this loop will run too fast.
+            std::thread::sleep(Duration::from_millis(100));
+
+            // Process all the messages received since the last check.
+            let mut round_finished = false;
+            let mut shutdown = false;
+            loop {
+                match ceremony_rx.try_recv() {
+                    Ok(CeremonyMessage::RoundFinished(_))
+                    | Ok(CeremonyMessage::RoundRestarted(_)) => {
+                        round_finished = true;
+                        break;
+                    }
+                    Ok(CeremonyMessage::Shutdown(_)) => {
+                        shutdown = true;
+                        break;
+                    }
+                    Ok(message) => trigger_state.update(&message),
+                    Err(TryRecvError::Disconnected) => {
+                        panic!("`ceremony_rx` disconnected");
+                    }
+                    Err(TryRecvError::Empty) => break,
+                }
+            }
+
+            if round_finished || shutdown {
+                match paused {
+                    // Resume the process so that it can be shut down.
+                    Some((pid, _)) => signal_process(pid, "CONT")?,
+                    None if round_finished => {
+                        return Err(eyre::eyre!(
+                            "Round {} ended before the process of {} was paused ({:?}).",
+                            round,
+                            id,
+                            config
+                        ));
+                    }
+                    None => {}
+                }
+                break;
+            }
+
+            match paused {
+                None => {
+                    if trigger_state.is_triggered(&config.trigger) {
+                        let pid = process_ids
+                            .lock()
+                            .expect("error obtaining lock")
+                            .get(&id)
+                            .cloned()
+                            .ok_or_else(|| eyre::eyre!("The process of {} is not running", id))?;
+
+                        tracing::info!("Pausing process {} ({:?}).", pid, config);
+                        signal_process(pid, "STOP")?;
+                        paused = Some((pid, Instant::now()));
+                    }
+                }
+                Some((pid, paused_time)) => {
+                    if paused_time.elapsed() >= config.duration {
+                        tracing::info!("Resuming process {}.", pid);
+                        signal_process(pid, "CONT")?;
+                        break;
+                    }
+                }
+            }
+        }
+
+        tracing::debug!("Thread closing gracefully.");
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::PauseConfig;
+    use crate::fault::{FaultTarget, FaultTrigger};
+
+    #[test]
+    fn test_pause_expect_drop() {
+        let pause = |duration: Duration| PauseConfig {
+            target: FaultTarget::Contributor(1),
+            trigger: FaultTrigger::AfterContributions(1),
+            duration,
+        };
+        let seen_timeout = Duration::from_secs(10);
+        let lock_timeout = Duration::from_secs(5);
+
+        assert!(!pause(Duration::from_secs(5))
+            .expect_drop(seen_timeout, lock_timeout)
+            .unwrap());
+        assert!(pause(Duration::from_secs(11))
+            .expect_drop(seen_timeout, lock_timeout)
+            .unwrap());
+        assert!(pause(Duration::from_secs(7))
+            .expect_drop(seen_timeout, lock_timeout)
+            .is_err());
+    }
+}
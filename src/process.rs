@@ -1,12 +1,149 @@
 //! Functions for starting/managing/interacting with external processes.
 
-use std::{fs::File, thread::JoinHandle, time::Duration};
+use std::{collections::BTreeMap, fs::File, thread::JoinHandle, time::Duration};
 
 use eyre::Context;
 use mpmc_bus::{Receiver, Sender, TryRecvError};
+use serde::{Deserialize, Serialize};
 use subprocess::{Exec, Redirection};
 
-use crate::{join::MultiJoinable, CeremonyMessage, ShutdownReason};
+use crate::{join::MultiJoinable, selection::glob_matches, CeremonyMessage, ShutdownReason};
+
+/// The default value for [ProcessesConfig::contributor_passphrase].
+pub const DEFAULT_CONTRIBUTOR_PASSPHRASE: &str = "test";
+
+/// Options for launching the process of a component (such as the
+/// coordinator, or a contributor).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessConfig {
+    /// Extra environment variables to set for the process. These
+    /// override the default `RUST_BACKTRACE=1`.
+    pub env: BTreeMap<String, String>,
+    /// Extra command line arguments, appended to the arguments that
+    /// the process is started with.
+    pub args: Vec<String>,
+    /// The log filter (`RUST_LOG`) for the process, replacing the
+    /// component's default (e.g. `"debug,hyper=warn"`).
+    pub log_filter: Option<String>,
+}
+
+impl ProcessConfig {
+    /// Combine this config with `other`, which takes precedence. The
+    /// environment variables are merged, and the arguments of `other`
+    /// are appended to the arguments of this config.
+    pub fn merge(&self, other: &ProcessConfig) -> ProcessConfig {
+        let mut env = self.env.clone();
+        env.extend(other.env.clone());
+
+        ProcessConfig {
+            env,
+            args: self.args.iter().chain(&other.args).cloned().collect(),
+            log_filter: other.log_filter.clone().or_else(|| self.log_filter.clone()),
+        }
+    }
+
+    /// Apply the config to `exec`. `RUST_LOG` is set to the
+    /// [ProcessConfig::log_filter], or to `default_log_filter` if
+    /// there is no filter configured.
+    pub fn apply(&self, exec: Exec, default_log_filter: Option<&str>) -> Exec {
+        let mut exec = exec.env("RUST_BACKTRACE", "1");
+
+        if let Some(log_filter) = self.log_filter.as_deref().or(default_log_filter) {
+            exec = exec.env("RUST_LOG", log_filter);
+        }
+
+        for (key, value) in &self.env {
+            exec = exec.env(key, value);
+        }
+
+        exec.args(&self.args)
+    }
+}
+
+/// Options for launching the processes of each of the components of
+/// the ceremony.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessesConfig {
+    /// Options for the `aleo-setup-coordinator`.
+    pub coordinator: ProcessConfig,
+    /// Options for every contributor (including the replacement
+    /// contributors).
+    pub contributors: ProcessConfig,
+    /// Options for every verifier.
+    pub verifiers: ProcessConfig,
+    /// Options for the `aleo-setup-state-monitor`.
+    pub state_monitor: ProcessConfig,
+    /// Options for individual participants, as a list of
+    /// `(pattern, options)`, where the pattern is an id or a glob
+    /// pattern matching the ids (e.g. `"contributor1-*"` for the
+    /// contributors in the first configured round,
+    /// `"replacement_contributor1"` or `"verifier2"`). These are
+    /// applied after the options for the `contributors` or
+    /// `verifiers`, in the order that they are listed, so a pattern
+    /// for a single participant can be listed after a broader one to
+    /// take precedence.
+    pub participants: Vec<(String, ProcessConfig)>,
+    /// (Optional) The passphrase that the contributors' keys are
+    /// encrypted with, passed to `setup1-contributor` with
+    /// `--passphrase` when generating the keys and when contributing.
+    /// Default: `"test"`.
+    pub contributor_passphrase: Option<String>,
+}
+
+impl ProcessesConfig {
+    /// Combine this config with `other`, which takes precedence, see
+    /// [ProcessConfig::merge()]. The `participants` of `other` are
+    /// applied after those of this config.
+    pub fn merge(&self, other: &ProcessesConfig) -> ProcessesConfig {
+        ProcessesConfig {
+            coordinator: self.coordinator.merge(&other.coordinator),
+            contributors: self.contributors.merge(&other.contributors),
+            verifiers: self.verifiers.merge(&other.verifiers),
+            state_monitor: self.state_monitor.merge(&other.state_monitor),
+            participants: self
+                .participants
+                .iter()
+                .chain(&other.participants)
+                .cloned()
+                .collect(),
+            contributor_passphrase: other
+                .contributor_passphrase
+                .clone()
+                .or_else(|| self.contributor_passphrase.clone()),
+        }
+    }
+
+    /// The passphrase that the contributors' keys are encrypted with,
+    /// see [ProcessesConfig::contributor_passphrase].
+    pub fn contributor_passphrase(&self) -> &str {
+        self.contributor_passphrase
+            .as_deref()
+            .unwrap_or(DEFAULT_CONTRIBUTOR_PASSPHRASE)
+    }
+
+    /// The options for the participant with the specified `id`, on top
+    /// of the options for its type of participant (`base`).
+    fn participant(&self, base: &ProcessConfig, id: &str) -> ProcessConfig {
+        self.participants
+            .iter()
+            .filter(|(pattern, _)| glob_matches(pattern, id))
+            .fold(base.clone(), |config, (_, participant)| {
+                config.merge(participant)
+            })
+    }
+
+    /// The options for the contributor with the specified `id`.
+    pub fn contributor(&self, id: &str) -> ProcessConfig {
+        self.participant(&self.contributors, id)
+    }
+
+    /// The options for the verifier with the specified `id`.
+    pub fn verifier(&self, id: &str) -> ProcessConfig {
+        self.participant(&self.verifiers, id)
+    }
+}
 
 /// Returns `Ok` if the `exit_status` is `Exited(0)` or `Signaled(15)`
 /// (terminated by the host?), otherwise returns an `Err`.
@@ -26,6 +163,7 @@ pub fn default_parse_exit_status(exit_status: subprocess::ExitStatus) -> eyre::R
 #[must_use]
 pub struct MonitorProcessJoin {
     id: String,
+    pid: Option<u32>,
     monitor_join: JoinHandle<()>,
     messages_join: JoinHandle<()>,
 }
@@ -37,6 +175,12 @@ impl std::fmt::Debug for MonitorProcessJoin {
 }
 
 impl MonitorProcessJoin {
+    /// The id of the process (if it was available when the process
+    /// was started).
+    pub fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
     /// Join the threads
     pub fn join(self) -> std::thread::Result<()> {
         let span = tracing::error_span!("join", id = %self.id);
@@ -62,8 +206,10 @@ impl MultiJoinable for MonitorProcessJoin {
 /// monitor thread.
 #[derive(Clone)]
 pub enum MonitorProcessMessage {
-    /// Terminate the running process.
+    /// Terminate the running process (with `SIGTERM`).
     Terminate,
+    /// Kill the running process (with `SIGKILL`).
+    Kill,
 }
 
 /// Starts the process specified in `exec`, with `stdout` set to
@@ -99,6 +245,16 @@ where
     let mut stdout: Option<File> = None;
     std::mem::swap(&mut process.stdout, &mut stdout);
     let stdout = stdout.ok_or_else(|| eyre::eyre!("Unable to obtain process `stdout`."))?;
+    let pid = process.pid();
+
+    // Announce the process so that its resource usage can be
+    // monitored (see [crate::resources]).
+    if let Some(pid) = pid {
+        ceremony_tx.broadcast(CeremonyMessage::ProcessStarted {
+            id: id.clone(),
+            pid,
+        })?;
+    }
 
     let monitor_bus = mpmc_bus::Bus::new(5);
     let return_monitor_tx = monitor_bus.broadcaster();
@@ -126,6 +282,8 @@ where
 
         // Terminate the process at the end of the loop, and break.
         let mut terminate_process = false;
+        // Kill the process at the end of the loop, and break.
+        let mut kill_process = false;
 
         loop {
             // Sleep occasionally because otherwise this loop will run too fast.
@@ -134,6 +292,7 @@ where
             match monitor_rx.try_recv() {
                 Ok(message) => match message {
                     MonitorProcessMessage::Terminate => terminate_process = true,
+                    MonitorProcessMessage::Kill => kill_process = true,
                 },
                 Err(TryRecvError::Disconnected) => {
                     panic!("`monitor_rx` disconnected");
@@ -176,6 +335,14 @@ where
                     tracing::error!("Error while terminating process: {}. Thread closing.", err);
                 }
 
+                break;
+            } else if kill_process {
+                tracing::info!("Killing the process.");
+
+                if let Err(err) = process.kill() {
+                    tracing::error!("Error while killing process: {}. Thread closing.", err);
+                }
+
                 break;
             }
         }
@@ -186,6 +353,7 @@ where
     Ok((
         MonitorProcessJoin {
             id,
+            pid,
             monitor_join,
             messages_join,
         },
@@ -193,6 +361,18 @@ where
     ))
 }
 
+/// Send the `signal` (e.g. `STOP` or `CONT`) to the process with the
+/// specified `pid`, using the `kill` command.
+pub fn signal_process(pid: u32, signal: &str) -> eyre::Result<()> {
+    Exec::cmd("kill")
+        .arg(format!("-{}", signal))
+        .arg(pid.to_string())
+        .join()
+        .map_err(eyre::Error::from)
+        .and_then(default_parse_exit_status)
+        .wrap_err_with(|| eyre::eyre!("Error sending signal {} to process {}", signal, pid))
+}
+
 /// Create a monitor function to be used with [run_monitor_process()] that
 /// may return an [eyre::Result], if the result is an `Err` then a
 /// panic will occur and the ceremony will shut down with a
@@ -218,3 +398,61 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ProcessConfig, ProcessesConfig};
+
+    #[test]
+    fn test_merge_processes_config() {
+        let config: ProcessesConfig = ron::from_str(
+            r#"(
+                contributors: (
+                    env: {"RUST_BACKTRACE": "full", "A": "1"},
+                    args: ["--a"],
+                    log_filter: Some("info"),
+                ),
+                participants: [
+                    ("contributor1-*", (args: ["--first-round"], log_filter: Some("debug"))),
+                    ("contributor*", (log_filter: Some("info"))),
+                ],
+            )"#,
+        )
+        .unwrap();
+        let test_config: ProcessesConfig = ron::from_str(
+            r#"(
+                contributors: (env: {"A": "2"}, args: ["--b"]),
+                participants: [
+                    ("contributor1-2", (log_filter: Some("trace"))),
+                ],
+                contributor_passphrase: Some("secret"),
+            )"#,
+        )
+        .unwrap();
+
+        let merged = config.merge(&test_config);
+        assert_eq!(merged.contributors.env["A"], "2");
+        assert_eq!(merged.contributors.env["RUST_BACKTRACE"], "full");
+        assert_eq!(merged.contributors.args, vec!["--a", "--b"]);
+        assert_eq!(merged.contributors.log_filter.as_deref(), Some("info"));
+
+        let contributor = merged.contributor("contributor1-2");
+        assert_eq!(contributor.args, vec!["--a", "--b", "--first-round"]);
+        assert_eq!(contributor.log_filter.as_deref(), Some("trace"));
+
+        // The patterns are applied in the order that they are listed,
+        // not in the (ASCII) order of the patterns.
+        let contributor = merged.contributor("contributor1-1");
+        assert_eq!(contributor.args, vec!["--a", "--b", "--first-round"]);
+        assert_eq!(contributor.log_filter.as_deref(), Some("info"));
+
+        assert_eq!(
+            merged.contributor("contributor2-1").args,
+            vec!["--a", "--b"]
+        );
+        assert_eq!(merged.verifier("verifier1"), ProcessConfig::default());
+
+        assert_eq!(config.contributor_passphrase(), "test");
+        assert_eq!(merged.contributor_passphrase(), "secret");
+    }
+}
@@ -1,17 +1,72 @@
 //! Utilities for better error reporting and tracing/logging.
 
 use std::{
+    borrow::Cow,
+    fmt::Write as _,
     fs::{File, OpenOptions},
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
+use regex::Regex;
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Subscriber,
+};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{
-    prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter,
+    layer::Context, prelude::__tracing_subscriber_SubscriberExt, registry::LookupSpan,
+    util::SubscriberInitExt, EnvFilter, Layer,
 };
 
+/// The name of the file that the log output of the integration test
+/// is written to.
+pub const LOG_FILE_NAME: &str = "integration-test.log";
+
+lazy_static::lazy_static! {
+    /// Matches ANSI terminal escape sequences, such as the colors
+    /// used by [color_eyre] when formatting errors.
+    static ref ANSI_ESCAPE_RE: Regex = Regex::new("\x1b\\[[0-9;]*[A-Za-z]").unwrap();
+}
+
+/// Remove ANSI terminal escape sequences (such as the colors used by
+/// [color_eyre] when formatting errors) from `text`, for writing it
+/// to a file.
+pub fn strip_ansi_escapes(text: &str) -> Cow<'_, str> {
+    ANSI_ESCAPE_RE.replace_all(text, "")
+}
+
+/// Format the `rows` (the first of which is usually a header) as a
+/// table, with each column padded to the width of its widest cell.
+/// The columns are separated by two spaces, and trailing whitespace
+/// is removed from each line.
+pub fn format_table(rows: &[Vec<String>]) -> String {
+    let n_columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..n_columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.len())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 #[derive(Default, Debug)]
 struct LogFileWriterInternal {
     buffer: Vec<u8>,
@@ -56,9 +111,14 @@ impl Write for LogFileWriterInternal {
 /// This struct manages the writing to `integration-test.log` in the
 /// current out directory. If there is no out directory, then output
 /// is buffered in memory until there is one.
+///
+/// While tests are run in parallel, the output of each test is also
+/// written to `integration-test.log` in the test's own out directory,
+/// see [Self::set_tests_out_dir()].
 #[derive(Clone, Debug)]
 pub struct LogFileWriter {
     internal: Arc<Mutex<LogFileWriterInternal>>,
+    tests_out_dir: Arc<Mutex<Option<PathBuf>>>,
 }
 
 impl Default for LogFileWriter {
@@ -71,9 +131,20 @@ impl LogFileWriter {
     pub fn new() -> Self {
         LogFileWriter {
             internal: Default::default(),
+            tests_out_dir: Default::default(),
         }
     }
 
+    /// Set the out directory containing the out directories of the
+    /// tests. While this is `Some`, the events which occur within the
+    /// span of a test (the `test` span, with the test's `id` field)
+    /// are also written to [LOG_FILE_NAME] in the test's out
+    /// directory (`<tests_out_dir>/<test id>`), once it exists.
+    pub fn set_tests_out_dir(&self, tests_out_dir: Option<&Path>) {
+        *self.tests_out_dir.lock().expect("error obtaining lock") =
+            tests_out_dir.map(Path::to_path_buf);
+    }
+
     /// Stop logging to file, buffer log output in memory until
     /// [Self::set_out_file()] is called again, where the output will
     /// then be unbuffered to.
@@ -117,6 +188,113 @@ impl Write for LogFileWriter {
     }
 }
 
+/// The id of a test, recorded from the `id` field of the `test` span
+/// that the test runs in.
+struct TestSpanId(String);
+
+/// Records the `id` field of a span.
+#[derive(Default)]
+struct IdVisitor(Option<String>);
+
+impl Visit for IdVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "id" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "id" {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+/// Formats the fields of an event as `<message> <field>=<value> ...`.
+#[derive(Default)]
+struct EventVisitor(String);
+
+impl Visit for EventVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        let _ = if field.name() == "message" {
+            write!(self.0, "{:?}", value)
+        } else {
+            write!(self.0, "{}={:?}", field.name(), value)
+        };
+    }
+}
+
+/// A [Layer] which writes the events which occur within the span of
+/// a test to the test's own log file, see
+/// [LogFileWriter::set_tests_out_dir()]. Events in threads which do
+/// not enter a span created within the test's span are only written
+/// to the main log.
+struct TestLogLayer {
+    tests_out_dir: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl<S> Layer<S> for TestLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != "test" {
+            return;
+        }
+
+        let mut visitor = IdVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(test_id), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(TestSpanId(test_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let tests_out_dir = match &*self.tests_out_dir.lock().expect("error obtaining lock") {
+            Some(tests_out_dir) => tests_out_dir.clone(),
+            None => return,
+        };
+
+        let test_id =
+            std::iter::successors(ctx.lookup_current(), |span| span.parent()).find_map(|span| {
+                span.extensions()
+                    .get::<TestSpanId>()
+                    .map(|test_id| test_id.0.clone())
+            });
+        let test_dir = match test_id {
+            Some(test_id) => tests_out_dir.join(test_id),
+            None => return,
+        };
+
+        // The test's out directory is removed when the test is
+        // cleaned, events are written once it has been created again.
+        if !test_dir.is_dir() {
+            return;
+        }
+
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let line = format!(
+            "{} {:>5} {}: {}\n",
+            chrono::Utc::now().to_rfc3339(),
+            metadata.level(),
+            metadata.target(),
+            visitor.0
+        );
+
+        // Errors writing to the log file cannot be logged.
+        let _ = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(test_dir.join(LOG_FILE_NAME))
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+    }
+}
+
 #[must_use]
 pub struct ReportGuard {
     _guard: WorkerGuard,
@@ -128,6 +306,10 @@ pub fn setup_reporting(log_writer: LogFileWriter) -> eyre::Result<ReportGuard> {
 
     let filter_layer = EnvFilter::try_from_default_env().or_else(|_| EnvFilter::try_new("info"))?;
 
+    let test_log_layer = TestLogLayer {
+        tests_out_dir: log_writer.tests_out_dir.clone(),
+    };
+
     let (tracing_log_writer, guard) = tracing_appender::non_blocking(log_writer);
     let fmt_layer = tracing_subscriber::fmt::layer().with_writer(tracing_log_writer);
 
@@ -136,6 +318,7 @@ pub fn setup_reporting(log_writer: LogFileWriter) -> eyre::Result<ReportGuard> {
     tracing_subscriber::registry()
         .with(filter_layer)
         .with(fmt_layer)
+        .with(test_log_layer)
         .with(error_layer)
         .init();
 
@@ -144,9 +327,30 @@ pub fn setup_reporting(log_writer: LogFileWriter) -> eyre::Result<ReportGuard> {
 
 #[cfg(test)]
 mod test {
-    use std::io::Write;
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use tracing_subscriber::prelude::__tracing_subscriber_SubscriberExt;
+
+    use super::{format_table, LogFileWriter, TestLogLayer, LOG_FILE_NAME};
 
-    use super::LogFileWriter;
+    #[test]
+    fn test_format_table() {
+        let rows: Vec<Vec<String>> = vec![
+            vec!["test".into(), "status".into(), "error".into()],
+            vec!["long-test-id".into(), "failed".into(), "timed out".into()],
+            vec!["1".into(), "passed".into(), "".into()],
+        ];
+        assert_eq!(
+            format_table(&rows),
+            "test          status  error\n\
+            long-test-id  failed  timed out\n\
+            1             passed"
+        );
+        assert_eq!(format_table(&[]), "");
+    }
 
     #[test]
     fn test_log_file_writer() {
@@ -180,4 +384,34 @@ mod test {
 
         println!();
     }
+
+    /// Test that events within the span of a test are written to the
+    /// log file in the test's out directory.
+    #[test]
+    fn test_test_log_layer() {
+        let out_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(out_dir.path().join("a")).unwrap();
+
+        let layer = TestLogLayer {
+            tests_out_dir: Arc::new(Mutex::new(Some(out_dir.path().to_owned()))),
+        };
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("outside of a test");
+            for id in &["a", "b"] {
+                let span = tracing::error_span!("test", id = %id);
+                let _guard = span.enter();
+                let round_span = tracing::error_span!("round", round = 1);
+                let _round_guard = round_span.enter();
+                tracing::info!(chunk = 2, "inside test {}", id);
+            }
+        });
+
+        let log_string =
+            std::fs::read_to_string(out_dir.path().join("a").join(LOG_FILE_NAME)).unwrap();
+        assert_eq!(log_string.lines().count(), 1);
+        assert!(log_string.contains("inside test a chunk=2"));
+        // The out directory of test "b" did not exist.
+        assert!(!out_dir.path().join("b").exists());
+    }
 }
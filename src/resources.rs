@@ -0,0 +1,418 @@
+//! Sampling the CPU, memory and disk usage of the processes started
+//! during a test (see [crate::process::run_monitor_process()]) from
+//! `/proc/<pid>`, recording a time series to a CSV file, and a
+//! summary of the usage of each component during each round. Only
+//! supported on Linux.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use eyre::Context;
+use mpmc_bus::{Receiver, TryRecvError};
+use serde::{Deserialize, Serialize};
+
+use crate::CeremonyMessage;
+
+/// Name of the file that the resource usage samples are recorded to,
+/// in the test's out directory.
+pub const RESOURCES_FILE_NAME: &str = "resources.csv";
+
+/// Clock ticks per second used by `/proc/<pid>/stat` if it cannot be
+/// obtained with `getconf CLK_TCK`.
+const DEFAULT_CLOCK_TICKS: u64 = 100;
+
+/// The resource usage of a component (the process with a given id)
+/// during a round.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// The number of samples taken during the round.
+    pub samples: u64,
+    /// The peak resident set size of the process.
+    pub peak_rss_bytes: u64,
+    /// The mean resident set size of the process over the samples.
+    pub mean_rss_bytes: u64,
+    /// The CPU time (user and system) used by the process.
+    #[serde(with = "humantime_serde")]
+    pub cpu_time: Duration,
+    /// The number of bytes read from storage by the process.
+    pub read_bytes: u64,
+    /// The number of bytes written to storage by the process.
+    pub write_bytes: u64,
+}
+
+/// A single sample of the cumulative resource usage of a process,
+/// read from `/proc/<pid>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ProcessSample {
+    rss_bytes: u64,
+    cpu_time: Duration,
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+/// Parse the CPU time (`utime` + `stime`) from the contents of
+/// `/proc/<pid>/stat`. Returns `None` if the process is a zombie.
+fn parse_stat(stat: &str, clock_ticks: u64) -> eyre::Result<Option<Duration>> {
+    // The command name (second field) may contain spaces and
+    // parentheses, so the fields are counted from the last `)`.
+    let fields: Vec<&str> = stat
+        .rsplit_once(')')
+        .map(|(_, fields)| fields.split_whitespace().collect())
+        .ok_or_else(|| eyre::eyre!("Unexpected format for stat: {:?}", stat))?;
+
+    if fields.first() == Some(&"Z") {
+        return Ok(None);
+    }
+
+    // `utime` and `stime` are fields 14 and 15, and the fields start
+    // with the state (field 3).
+    let ticks = |index: usize| -> eyre::Result<u64> {
+        fields
+            .get(index)
+            .ok_or_else(|| eyre::eyre!("Missing field {} in stat: {:?}", index + 3, stat))?
+            .parse::<u64>()
+            .wrap_err_with(|| eyre::eyre!("Error parsing field {} in stat", index + 3))
+    };
+    let ticks = ticks(11)? + ticks(12)?;
+
+    Ok(Some(Duration::from_nanos(
+        ticks * 1_000_000_000 / clock_ticks.max(1),
+    )))
+}
+
+/// Parse the resident set size (in bytes) from the contents of
+/// `/proc/<pid>/status`.
+fn parse_status_rss(status: &str) -> eyre::Result<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .ok_or_else(|| eyre::eyre!("Unable to find `VmRSS` in status"))?
+        .trim()
+        .parse::<u64>()
+        .map(|kilobytes| kilobytes * 1024)
+        .wrap_err("Error parsing `VmRSS` in status")
+}
+
+/// Parse the `read_bytes` and `write_bytes` from the contents of
+/// `/proc/<pid>/io`.
+fn parse_io(io: &str) -> eyre::Result<(u64, u64)> {
+    let field = |name: &str| -> eyre::Result<u64> {
+        io.lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|value| value.strip_prefix(':'))
+            .ok_or_else(|| eyre::eyre!("Unable to find `{}` in io", name))?
+            .trim()
+            .parse::<u64>()
+            .wrap_err_with(|| eyre::eyre!("Error parsing `{}` in io", name))
+    };
+    Ok((field("read_bytes")?, field("write_bytes")?))
+}
+
+/// Sample the resource usage of the process with the specified
+/// `pid`. Returns `None` if the process is no longer running.
+fn sample_process(pid: u32, clock_ticks: u64) -> eyre::Result<Option<ProcessSample>> {
+    let proc_dir = Path::new("/proc").join(pid.to_string());
+
+    let stat = match std::fs::read_to_string(proc_dir.join("stat")) {
+        Ok(stat) => stat,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    let cpu_time = match parse_stat(&stat, clock_ticks)? {
+        Some(cpu_time) => cpu_time,
+        None => return Ok(None),
+    };
+
+    let rss_bytes = match std::fs::read_to_string(proc_dir.join("status")) {
+        Ok(status) => parse_status_rss(&status)?,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+
+    // `/proc/<pid>/io` is not available on all systems.
+    let (read_bytes, write_bytes) = std::fs::read_to_string(proc_dir.join("io"))
+        .map_err(eyre::Error::from)
+        .and_then(|io| parse_io(&io))
+        .unwrap_or((0, 0));
+
+    Ok(Some(ProcessSample {
+        rss_bytes,
+        cpu_time,
+        read_bytes,
+        write_bytes,
+    }))
+}
+
+/// Obtain the number of clock ticks per second used by
+/// `/proc/<pid>/stat`.
+fn clock_ticks() -> u64 {
+    subprocess::Exec::cmd("getconf")
+        .arg("CLK_TCK")
+        .stdout(subprocess::Redirection::Pipe)
+        .stderr(subprocess::Redirection::Merge)
+        .capture()
+        .ok()
+        .filter(|capture| capture.success())
+        .and_then(|capture| capture.stdout_str().trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CLOCK_TICKS)
+}
+
+/// Accumulates the samples of a component during a round.
+#[derive(Debug, Default)]
+struct UsageAccumulator {
+    samples: u64,
+    rss_sum: u64,
+    peak_rss_bytes: u64,
+    cpu_time: Duration,
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+impl UsageAccumulator {
+    /// Add a `sample`, where `previous` is the previous sample of the
+    /// same process (used to calculate the usage since then).
+    fn add(&mut self, sample: &ProcessSample, previous: &ProcessSample) {
+        self.samples += 1;
+        self.rss_sum += sample.rss_bytes;
+        self.peak_rss_bytes = self.peak_rss_bytes.max(sample.rss_bytes);
+        self.cpu_time += sample
+            .cpu_time
+            .checked_sub(previous.cpu_time)
+            .unwrap_or_default();
+        self.read_bytes += sample.read_bytes.saturating_sub(previous.read_bytes);
+        self.write_bytes += sample.write_bytes.saturating_sub(previous.write_bytes);
+    }
+
+    fn usage(&self) -> ResourceUsage {
+        ResourceUsage {
+            samples: self.samples,
+            peak_rss_bytes: self.peak_rss_bytes,
+            mean_rss_bytes: self.rss_sum.checked_div(self.samples).unwrap_or(0),
+            cpu_time: self.cpu_time,
+            read_bytes: self.read_bytes,
+            write_bytes: self.write_bytes,
+        }
+    }
+}
+
+/// The round currently being recorded by the resource monitor.
+#[derive(Debug, Default)]
+struct RoundUsage {
+    round: Option<u64>,
+    components: BTreeMap<String, UsageAccumulator>,
+}
+
+/// A handle to the thread created by [monitor_resources()], used to
+/// obtain the resource usage of each round.
+#[derive(Debug, Clone)]
+pub struct ResourceMonitorHandle {
+    round_usage: Arc<Mutex<RoundUsage>>,
+}
+
+impl ResourceMonitorHandle {
+    /// Start recording the resource usage for the specified `round`,
+    /// discarding anything recorded previously.
+    pub fn start_round(&self, round: u64) {
+        let mut round_usage = self.round_usage.lock().expect("error obtaining lock");
+        round_usage.round = Some(round);
+        round_usage.components.clear();
+    }
+
+    /// Stop recording the resource usage for the current round, and
+    /// return the usage of each component (indexed by process id)
+    /// since [ResourceMonitorHandle::start_round()] was called.
+    pub fn finish_round(&self) -> BTreeMap<String, ResourceUsage> {
+        let mut round_usage = self.round_usage.lock().expect("error obtaining lock");
+        round_usage.round = None;
+        std::mem::take(&mut round_usage.components)
+            .into_iter()
+            .map(|(id, accumulator)| (id, accumulator.usage()))
+            .collect()
+    }
+}
+
+/// Sample the resource usage of the processes announced with
+/// [CeremonyMessage::ProcessStarted] every `interval`, writing the
+/// samples (the cumulative usage of each process) to the
+/// `resources_path` CSV file until a [CeremonyMessage::Shutdown] is
+/// received. Only the processes themselves are sampled, not their
+/// children.
+pub fn monitor_resources(
+    interval: Duration,
+    resources_path: impl AsRef<Path>,
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+) -> eyre::Result<(ResourceMonitorHandle, JoinHandle<eyre::Result<()>>)> {
+    let resources_path = resources_path.as_ref().to_owned();
+    let resources_file = File::create(&resources_path)
+        .wrap_err_with(|| eyre::eyre!("Error creating resources file {:?}", resources_path))?;
+    let handle = ResourceMonitorHandle {
+        round_usage: Arc::new(Mutex::new(RoundUsage::default())),
+    };
+    let round_usage = handle.round_usage.clone();
+    let span = tracing::error_span!("resource_monitor");
+
+    let join = std::thread::spawn(move || {
+        let _guard = span.enter();
+        let mut writer = BufWriter::new(resources_file);
+        writeln!(
+            writer,
+            "elapsed,time,round,id,pid,rss_bytes,cpu_seconds,read_bytes,write_bytes"
+        )?;
+        writer.flush()?;
+
+        let enabled = Path::new("/proc").exists();
+        if !enabled {
+            tracing::warn!("`/proc` is not available, resource usage will not be recorded.");
+        }
+
+        let clock_ticks = clock_ticks();
+        let start_time = Instant::now();
+        let mut last_sample_time: Option<Instant> = None;
+        // The running processes, indexed by pid, along with their
+        // id and the previous sample.
+        let mut processes: HashMap<u32, (String, ProcessSample)> = HashMap::new();
+
+        loop {
+            // Sleep occasionally because otherwise this loop will run too fast.
+            std::thread::sleep(Duration::from_millis(100));
+
+            // Process all the messages received since the last check.
+            let mut shutdown = false;
+            loop {
+                match ceremony_rx.try_recv() {
+                    Ok(CeremonyMessage::ProcessStarted { id, pid }) => {
+                        processes.insert(pid, (id, ProcessSample::default()));
+                    }
+                    Ok(CeremonyMessage::Shutdown(_)) => {
+                        shutdown = true;
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(TryRecvError::Disconnected) => {
+                        panic!("`ceremony_rx` disconnected");
+                    }
+                    Err(TryRecvError::Empty) => break,
+                }
+            }
+
+            if shutdown {
+                break;
+            }
+
+            if !enabled || last_sample_time.map_or(false, |time| time.elapsed() < interval) {
+                continue;
+            }
+            last_sample_time = Some(Instant::now());
+
+            let elapsed = start_time.elapsed().as_secs_f64();
+            let time = chrono::Utc::now();
+            let mut round_usage = round_usage.lock().expect("error obtaining lock");
+            let round = round_usage.round;
+
+            let mut exited: Vec<u32> = Vec::new();
+            for (pid, (id, previous)) in processes.iter_mut() {
+                let sample = match sample_process(*pid, clock_ticks) {
+                    Ok(Some(sample)) => sample,
+                    Ok(None) => {
+                        exited.push(*pid);
+                        continue;
+                    }
+                    Err(error) => {
+                        tracing::warn!("Error sampling process {} ({}): {:?}", pid, id, error);
+                        continue;
+                    }
+                };
+
+                writeln!(
+                    writer,
+                    "{},{},{},{},{},{},{},{},{}",
+                    elapsed,
+                    time.to_rfc3339(),
+                    round.map(|round| round.to_string()).unwrap_or_default(),
+                    id,
+                    pid,
+                    sample.rss_bytes,
+                    sample.cpu_time.as_secs_f64(),
+                    sample.read_bytes,
+                    sample.write_bytes,
+                )?;
+
+                if round.is_some() {
+                    round_usage
+                        .components
+                        .entry(id.clone())
+                        .or_default()
+                        .add(&sample, previous);
+                }
+                *previous = sample;
+            }
+            drop(round_usage);
+
+            for pid in exited {
+                processes.remove(&pid);
+            }
+
+            // Flush after each set of samples so that the time series
+            // is available even if the test is aborted.
+            writer.flush()?;
+        }
+
+        tracing::debug!("Thread closing gracefully.");
+        Ok(())
+    });
+
+    Ok((handle, join))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{parse_io, parse_stat, parse_status_rss, ProcessSample, UsageAccumulator};
+
+    #[test]
+    fn test_parse_proc() {
+        let stat = "1234 (some (process)) S 1 1234 1234 0 -1 4194560 1000 0 0 0 \
+            250 50 0 0 20 0 4 0 100 1000000 500 18446744073709551615";
+        assert_eq!(parse_stat(stat, 100).unwrap(), Some(Duration::from_secs(3)));
+        let zombie = "1234 (process) Z 1 1234 1234 0 -1 4194560 1000 0 0 0 250 50";
+        assert_eq!(parse_stat(zombie, 100).unwrap(), None);
+
+        let status = "Name:\tprocess\nVmPeak:\t  20000 kB\nVmRSS:\t    2048 kB\nThreads:\t4\n";
+        assert_eq!(parse_status_rss(status).unwrap(), 2048 * 1024);
+
+        let io = "rchar: 100\nwchar: 200\nsyscr: 1\nsyscw: 2\nread_bytes: 4096\n\
+            write_bytes: 8192\ncancelled_write_bytes: 0\n";
+        assert_eq!(parse_io(io).unwrap(), (4096, 8192));
+    }
+
+    #[test]
+    fn test_usage_accumulator() {
+        let sample = |rss_bytes: u64, cpu_seconds: u64, io_bytes: u64| ProcessSample {
+            rss_bytes,
+            cpu_time: Duration::from_secs(cpu_seconds),
+            read_bytes: io_bytes,
+            write_bytes: io_bytes * 2,
+        };
+
+        let mut accumulator = UsageAccumulator::default();
+        accumulator.add(&sample(100, 2, 10), &sample(50, 1, 5));
+        accumulator.add(&sample(300, 5, 20), &sample(100, 2, 10));
+        let usage = accumulator.usage();
+
+        assert_eq!(usage.samples, 2);
+        assert_eq!(usage.peak_rss_bytes, 300);
+        assert_eq!(usage.mean_rss_bytes, 200);
+        assert_eq!(usage.cpu_time, Duration::from_secs(4));
+        assert_eq!(usage.read_bytes, 15);
+        assert_eq!(usage.write_bytes, 30);
+    }
+}
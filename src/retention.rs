@@ -0,0 +1,255 @@
+//! Retention of the artifacts (transcripts, keys and logs) left in the
+//! out directory by each test in a
+//! [crate::specification::Specification], and the creation of
+//! compressed bundles of a test's logs which can be attached to bug
+//! reports.
+
+use std::path::{Path, PathBuf};
+
+use eyre::Context;
+use serde::Deserialize;
+use subprocess::Exec;
+
+use crate::{
+    process::default_parse_exit_status,
+    specification::{TestOutcome, TestReport},
+};
+
+/// The extension of the bundle files created by [create_bundle()].
+pub const BUNDLE_FILE_EXTENSION: &str = "tar.zst";
+
+/// Extensions of the files in a test's out directory which are
+/// included in its bundle.
+const BUNDLE_EXTENSIONS: &[&str] = &["log", "ron", "ndjson", "csv", "txt"];
+
+/// Names of the files in a test's out directory which are included in
+/// its bundle, in addition to those with [BUNDLE_EXTENSIONS]. The
+/// other `.json` files in the out directory include the participants'
+/// keys.
+const BUNDLE_FILE_NAMES: &[&str] = &["state.json"];
+
+/// What to keep of the out directories of the tests after they have
+/// run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum RetentionPolicy {
+    /// Keep the out directory of every test.
+    #[default]
+    KeepAll,
+    /// Only keep the out directories of the tests which failed (or
+    /// passed on a retry), the out directories of the tests which
+    /// passed are removed.
+    KeepFailures,
+    /// Keep the out directories of the tests which failed (or passed
+    /// on a retry), and replace the out directories of the tests which
+    /// passed with their bundle.
+    CompressPassing,
+}
+
+/// Configuration for the retention of the artifacts of each test.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetentionConfig {
+    /// (Optional) What to keep of the out directories of the tests
+    /// after they have run. Default: `KeepAll`.
+    #[serde(default)]
+    pub policy: RetentionPolicy,
+    /// (Optional) Whether to create a bundle for every test which
+    /// ran. Bundles are always created for the tests which passed
+    /// with the [RetentionPolicy::CompressPassing] policy. Default:
+    /// `false`.
+    #[serde(default)]
+    pub bundles: bool,
+    /// (Optional) Directory where the bundles are written, as
+    /// `<test id>.tar.zst`. This is outside of the out directory by
+    /// default so that the bundles are not removed by the `clean`
+    /// option. Default: `"bundles"`.
+    #[serde(default = "default_bundles_dir")]
+    pub bundles_dir: PathBuf,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            policy: RetentionPolicy::default(),
+            bundles: false,
+            bundles_dir: default_bundles_dir(),
+        }
+    }
+}
+
+/// Default value for [RetentionConfig::bundles_dir].
+fn default_bundles_dir() -> PathBuf {
+    PathBuf::from("bundles")
+}
+
+/// Whether the file at `path` is included in a test's bundle.
+fn is_bundle_file(path: &Path) -> bool {
+    let file_name = path.file_name().and_then(|name| name.to_str());
+    let extension = path.extension().and_then(|extension| extension.to_str());
+
+    file_name.map_or(false, |name| BUNDLE_FILE_NAMES.contains(&name))
+        || extension.map_or(false, |extension| BUNDLE_EXTENSIONS.contains(&extension))
+}
+
+/// The files in the test out directory `test_dir` (and its
+/// subdirectories) which are included in its bundle: logs,
+/// `test_config.ron`, `results.ron`, events, resource usage, errors
+/// and each round's `state.json`. Transcripts, keys and other large
+/// parameter files are excluded. The paths are relative to
+/// `test_dir`, and sorted.
+pub fn bundle_files(test_dir: &Path) -> eyre::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![PathBuf::new()];
+
+    while let Some(dir) = dirs.pop() {
+        let dir_path = test_dir.join(&dir);
+        let entries = std::fs::read_dir(&dir_path)
+            .wrap_err_with(|| eyre::eyre!("Error while reading directory {:?}", dir_path))?;
+        for entry in entries {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = dir.join(entry.file_name());
+
+            if file_type.is_dir() {
+                dirs.push(path);
+            } else if file_type.is_file() && is_bundle_file(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Create a zstd compressed tar archive at `bundle_path` containing
+/// the [bundle_files()] of `test_dir`. Requires a version of `tar`
+/// which supports the `--zstd` option, and `zstd` to be installed.
+pub fn create_bundle(test_dir: &Path, bundle_path: &Path) -> eyre::Result<()> {
+    let files = bundle_files(test_dir)?;
+    if files.is_empty() {
+        tracing::warn!("No files to bundle in {:?}, skipping.", test_dir);
+        return Ok(());
+    }
+
+    tracing::info!(
+        "Creating bundle {:?} containing {} files from {:?}.",
+        bundle_path,
+        files.len(),
+        test_dir
+    );
+
+    Exec::cmd("tar")
+        .arg("--zstd")
+        .arg("--create")
+        .arg("--file")
+        .arg(bundle_path)
+        .arg("--directory")
+        .arg(test_dir)
+        .args(&files)
+        .join()
+        .map_err(eyre::Error::from)
+        .and_then(default_parse_exit_status)
+        .wrap_err_with(|| eyre::eyre!("Error while creating bundle {:?}", bundle_path))
+}
+
+/// Apply the retention `config` to the out directories (in `out_dir`)
+/// of the tests which ran, after all of the tests have finished.
+pub fn apply_retention(
+    config: &RetentionConfig,
+    out_dir: &Path,
+    reports: &[TestReport],
+) -> eyre::Result<()> {
+    for report in reports {
+        let test_dir = out_dir.join(&report.id);
+        if report.attempts == 0 || !test_dir.is_dir() {
+            continue;
+        }
+
+        let passed = matches!(report.outcome, TestOutcome::Passed) && !report.is_flaky();
+        let compress = passed && config.policy == RetentionPolicy::CompressPassing;
+
+        if config.bundles || compress {
+            std::fs::create_dir_all(&config.bundles_dir).wrap_err_with(|| {
+                eyre::eyre!("Error while creating path {:?}", config.bundles_dir)
+            })?;
+            let bundle_path = config
+                .bundles_dir
+                .join(format!("{}.{}", report.id, BUNDLE_FILE_EXTENSION));
+            create_bundle(&test_dir, &bundle_path)?;
+        }
+
+        if passed && config.policy != RetentionPolicy::KeepAll {
+            tracing::info!("Removing out dir of passing test: {:?}", test_dir);
+            std::fs::remove_dir_all(&test_dir)
+                .wrap_err_with(|| eyre::eyre!("Error while removing {:?}", test_dir))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{apply_retention, bundle_files, RetentionConfig, RetentionPolicy};
+    use crate::specification::{TestOutcome, TestReport};
+
+    #[test]
+    fn test_bundle_files() {
+        let test_dir = tempfile::tempdir().unwrap();
+        let round_dir = test_dir.path().join("coordinator/transcript/round_1");
+        std::fs::create_dir_all(&round_dir).unwrap();
+        std::fs::create_dir_all(test_dir.path().join("keys")).unwrap();
+
+        for file in &[
+            "test_config.ron",
+            "events.ndjson",
+            "coordinator/coordinator.log",
+            "coordinator/transcript/round_1/state.json",
+            "coordinator/transcript/round_1/chunk_0.unverified",
+            "keys/contributor-1.json",
+        ] {
+            std::fs::write(test_dir.path().join(file), "").unwrap();
+        }
+
+        let files = bundle_files(test_dir.path()).unwrap();
+        let expected: Vec<PathBuf> = vec![
+            "coordinator/coordinator.log".into(),
+            "coordinator/transcript/round_1/state.json".into(),
+            "events.ndjson".into(),
+            "test_config.ron".into(),
+        ];
+        assert_eq!(files, expected);
+    }
+
+    #[test]
+    fn test_apply_retention_keep_failures() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let report = |id: &str, outcome: TestOutcome| {
+            std::fs::create_dir_all(out_dir.path().join(id)).unwrap();
+            TestReport {
+                id: id.to_string(),
+                duration: Duration::from_secs(1),
+                outcome,
+                results: None,
+                attempts: 1,
+                failed_attempts: Vec::new(),
+            }
+        };
+        let reports = vec![
+            report("passed", TestOutcome::Passed),
+            report("failed", TestOutcome::Failed(eyre::eyre!("failed"))),
+        ];
+
+        let config = RetentionConfig {
+            policy: RetentionPolicy::KeepFailures,
+            ..RetentionConfig::default()
+        };
+        apply_retention(&config, out_dir.path(), &reports).unwrap();
+
+        assert!(!out_dir.path().join("passed").exists());
+        assert!(out_dir.path().join("failed").is_dir());
+    }
+}
@@ -0,0 +1,67 @@
+//! The expected outcome of each round of the ceremony (finishing or
+//! being restarted by the coordinator), and the monitoring of the
+//! ceremony to check that each round has its expected outcome.
+
+use std::thread::JoinHandle;
+
+use mpmc_bus::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+
+use crate::{CeremonyMessage, ShutdownReason};
+
+/// The expected outcome of a round of the ceremony.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundOutcome {
+    /// The round is aggregated and finishes successfully.
+    #[default]
+    Finish,
+    /// The round is restarted (rolled back) by the coordinator
+    /// because no contributors remain to complete it, see
+    /// [CeremonyMessage::RoundRestarted].
+    Restart,
+}
+
+/// Monitor the ceremony for the outcome of the specified `round`. If
+/// the round finishes or restarts when the `expected` outcome is
+/// different, then this will send a shutdown message and return an
+/// error.
+pub fn monitor_round_outcome(
+    round: u64,
+    expected: RoundOutcome,
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+    ceremony_tx: Sender<CeremonyMessage>,
+) -> JoinHandle<eyre::Result<()>> {
+    let span = tracing::error_span!("round_outcome", round);
+    std::thread::spawn(move || {
+        let _guard = span.enter();
+
+        loop {
+            let outcome = match ceremony_rx.recv()? {
+                CeremonyMessage::RoundFinished(message_round) if message_round == round => {
+                    RoundOutcome::Finish
+                }
+                CeremonyMessage::RoundRestarted(message_round) if message_round == round => {
+                    RoundOutcome::Restart
+                }
+                CeremonyMessage::Shutdown(_) => break,
+                _ => continue,
+            };
+
+            if outcome != expected {
+                ceremony_tx.broadcast(CeremonyMessage::Shutdown(ShutdownReason::Error))?;
+                return Err(eyre::eyre!(
+                    "Round {} had the outcome {:?}, expected {:?}.",
+                    round,
+                    outcome,
+                    expected
+                ));
+            }
+
+            tracing::info!("Round had the expected outcome ({:?}).", outcome);
+            break;
+        }
+
+        tracing::debug!("Thread terminated gracefully");
+        Ok(())
+    })
+}
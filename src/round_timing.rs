@@ -0,0 +1,293 @@
+//! Timing of the events during each round of the ceremony, to
+//! determine where the time of a round is spent (waiting for
+//! participants, waiting for the first contribution, and the time
+//! taken by each contributor to contribute to each chunk).
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Condvar, Mutex},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use mpmc_bus::Receiver;
+use serde::{Deserialize, Serialize};
+
+use crate::{CeremonyMessage, ContributorRef};
+
+/// The timing of the contributions made by a contributor during a
+/// round.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContributorTiming {
+    /// The number of chunks that the contributor contributed to.
+    pub chunks: u64,
+    /// The mean time taken to contribute to a chunk.
+    #[serde(with = "humantime_serde")]
+    pub mean_chunk_duration: Duration,
+}
+
+/// The time taken to contribute to a chunk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkTiming {
+    pub chunk: u64,
+    /// The address of the contributor.
+    pub contributor: String,
+    #[serde(with = "humantime_serde")]
+    pub duration: Duration,
+}
+
+/// A breakdown of the time taken by a round. The time taken to
+/// contribute to a chunk is measured from the contributor's previous
+/// contribution, or from when the contributor was assigned its tasks
+/// (the start of the round, or when it replaced a dropped
+/// contributor).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundTiming {
+    /// The time between the coordinator waiting for participants to
+    /// join the round, and the start of the round.
+    #[serde(with = "humantime_serde")]
+    pub waiting_for_participants: Option<Duration>,
+    /// The time between the start of the round, and the first
+    /// contribution.
+    #[serde(with = "humantime_serde")]
+    pub time_to_first_contribution: Option<Duration>,
+    /// The timing of each contributor, indexed by the contributor's
+    /// address.
+    pub contributors: BTreeMap<String, ContributorTiming>,
+    /// The chunk contribution which took the longest time.
+    pub slowest_chunk: Option<ChunkTiming>,
+}
+
+/// Accumulates the timing of a round as the messages are received.
+#[derive(Debug, Default)]
+struct RoundTimer {
+    /// The round which has started (if any).
+    round: Option<u64>,
+    waiting_time: Option<Instant>,
+    start_time: Option<Instant>,
+    timing: RoundTiming,
+    /// The time that each contributor started working on its next
+    /// chunk.
+    chunk_start_times: HashMap<ContributorRef, Instant>,
+    /// The total time spent contributing by each contributor.
+    contributor_durations: HashMap<ContributorRef, Duration>,
+}
+
+impl RoundTimer {
+    /// Update the timing with a `message` received at `time`.
+    fn update(&mut self, message: &CeremonyMessage, time: Instant) {
+        match message {
+            CeremonyMessage::RoundWaitingForParticipants(_) => {
+                self.waiting_time.get_or_insert(time);
+            }
+            CeremonyMessage::RoundStarted(round) => {
+                self.round = Some(*round);
+                self.start_time = Some(time);
+                self.timing.waiting_for_participants = self
+                    .waiting_time
+                    .map(|waiting_time| time.saturating_duration_since(waiting_time));
+            }
+            CeremonyMessage::ContributorReplaced { replacement, .. } => {
+                self.chunk_start_times.insert(replacement.clone(), time);
+            }
+            CeremonyMessage::SuccessfulContribution { contributor, chunk } => {
+                let start_time = match self.start_time {
+                    Some(start_time) => start_time,
+                    None => return,
+                };
+                if self.timing.time_to_first_contribution.is_none() {
+                    self.timing.time_to_first_contribution =
+                        Some(time.saturating_duration_since(start_time));
+                }
+
+                let chunk_start_time = self
+                    .chunk_start_times
+                    .insert(contributor.clone(), time)
+                    .unwrap_or(start_time);
+                let duration = time.saturating_duration_since(chunk_start_time);
+                *self
+                    .contributor_durations
+                    .entry(contributor.clone())
+                    .or_default() += duration;
+
+                let contributor_timing = self
+                    .timing
+                    .contributors
+                    .entry(contributor.to_string())
+                    .or_insert(ContributorTiming {
+                        chunks: 0,
+                        mean_chunk_duration: Duration::from_secs(0),
+                    });
+                contributor_timing.chunks += 1;
+                contributor_timing.mean_chunk_duration =
+                    self.contributor_durations[contributor] / contributor_timing.chunks as u32;
+
+                let slowest = self
+                    .timing
+                    .slowest_chunk
+                    .as_ref()
+                    .map_or(true, |slowest| duration > slowest.duration);
+                if slowest {
+                    self.timing.slowest_chunk = Some(ChunkTiming {
+                        chunk: *chunk,
+                        contributor: contributor.to_string(),
+                        duration,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The timing of the rounds which have ended, shared between the
+/// thread created in [monitor_round_timing()] and the
+/// [RoundTimingHandle].
+#[derive(Debug, Default)]
+struct EndedRounds {
+    rounds: Vec<(u64, RoundTiming)>,
+    stopped: bool,
+}
+
+/// A handle to the thread created by [monitor_round_timing()], used
+/// to obtain the timing of each round once it has ended.
+#[derive(Debug, Clone)]
+pub struct RoundTimingHandle {
+    ended_rounds: Arc<(Mutex<EndedRounds>, Condvar)>,
+}
+
+impl RoundTimingHandle {
+    /// Wait for the specified `round` to end (finish or restart), and
+    /// return its timing. If the monitor stops (e.g. because of a
+    /// [CeremonyMessage::Shutdown]) before the round has ended then
+    /// the timing recorded so far is returned (or the default if the
+    /// round did not start).
+    pub fn round_timing(&self, round: u64) -> RoundTiming {
+        let (ended_rounds, condvar) = &*self.ended_rounds;
+        let mut ended_rounds = ended_rounds.lock().expect("error obtaining lock");
+        loop {
+            if let Some(position) = ended_rounds
+                .rounds
+                .iter()
+                .position(|(ended_round, _)| *ended_round == round)
+            {
+                return ended_rounds.rounds.remove(position).1;
+            }
+            if ended_rounds.stopped {
+                return RoundTiming::default();
+            }
+            ended_rounds = condvar.wait(ended_rounds).expect("error obtaining lock");
+        }
+    }
+}
+
+/// Timestamp the messages received on `ceremony_rx` to record the
+/// [RoundTiming] of each round, until a [CeremonyMessage::Shutdown]
+/// is received. This needs to be started before the coordinator, so
+/// that it receives the [CeremonyMessage::RoundWaitingForParticipants]
+/// for the first round.
+pub fn monitor_round_timing(
+    mut ceremony_rx: Receiver<CeremonyMessage>,
+) -> (RoundTimingHandle, JoinHandle<eyre::Result<()>>) {
+    let handle = RoundTimingHandle {
+        ended_rounds: Arc::new((Mutex::new(EndedRounds::default()), Condvar::new())),
+    };
+    let ended_rounds = handle.ended_rounds.clone();
+    let span = tracing::error_span!("round_timing");
+
+    let join = std::thread::spawn(move || {
+        let _guard = span.enter();
+        let (ended_rounds, condvar) = &*ended_rounds;
+        let mut timer = RoundTimer::default();
+
+        let result = loop {
+            let message = match ceremony_rx.recv() {
+                Ok(message) => message,
+                Err(error) => break Err(error.into()),
+            };
+            let time = Instant::now();
+
+            match message {
+                CeremonyMessage::RoundFinished(round) | CeremonyMessage::RoundRestarted(round) => {
+                    let timer = std::mem::take(&mut timer);
+                    let mut ended_rounds = ended_rounds.lock().expect("error obtaining lock");
+                    ended_rounds.rounds.push((round, timer.timing));
+                    condvar.notify_all();
+                }
+                CeremonyMessage::Shutdown(_) => break Ok(()),
+                message => timer.update(&message, time),
+            }
+        };
+
+        // Release any threads waiting for the timing of a round
+        // which will not end, providing the timing of the round in
+        // progress.
+        let mut ended_rounds_guard = ended_rounds.lock().expect("error obtaining lock");
+        if let Some(round) = timer.round {
+            ended_rounds_guard.rounds.push((round, timer.timing));
+        }
+        ended_rounds_guard.stopped = true;
+        drop(ended_rounds_guard);
+        condvar.notify_all();
+
+        tracing::debug!("Thread closing gracefully.");
+        result
+    });
+
+    (handle, join)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::RoundTimer;
+    use crate::{stand_in::address_from_seed, CeremonyMessage, ContributorRef};
+
+    #[test]
+    fn test_round_timer() {
+        let contributor1 = ContributorRef {
+            address: address_from_seed("contributor1"),
+        };
+        let contributor2 = ContributorRef {
+            address: address_from_seed("contributor2"),
+        };
+        let start = Instant::now();
+        let at = |seconds: u64| start + Duration::from_secs(seconds);
+
+        let mut timer = RoundTimer::default();
+        timer.update(&CeremonyMessage::RoundWaitingForParticipants(1), at(0));
+        timer.update(&CeremonyMessage::RoundStarted(1), at(5));
+        let contribution =
+            |contributor: &ContributorRef, chunk: u64| CeremonyMessage::SuccessfulContribution {
+                contributor: contributor.clone(),
+                chunk,
+            };
+        timer.update(&contribution(&contributor1, 0), at(7));
+        timer.update(&contribution(&contributor1, 1), at(11));
+        timer.update(&contribution(&contributor2, 0), at(8));
+        let timing = timer.timing;
+
+        assert_eq!(
+            timing.waiting_for_participants,
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            timing.time_to_first_contribution,
+            Some(Duration::from_secs(2))
+        );
+
+        let contributor1_timing = &timing.contributors[&contributor1.to_string()];
+        assert_eq!(contributor1_timing.chunks, 2);
+        assert_eq!(
+            contributor1_timing.mean_chunk_duration,
+            Duration::from_secs(3)
+        );
+        assert_eq!(timing.contributors[&contributor2.to_string()].chunks, 1);
+
+        let slowest_chunk = timing.slowest_chunk.unwrap();
+        assert_eq!(slowest_chunk.chunk, 1);
+        assert_eq!(slowest_chunk.contributor, contributor1.to_string());
+        assert_eq!(slowest_chunk.duration, Duration::from_secs(4));
+    }
+}
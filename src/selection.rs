@@ -0,0 +1,167 @@
+//! Selection of the tests in a [crate::specification::Specification]
+//! to run, by id (or glob pattern) and by tag.
+
+use regex::Regex;
+
+use crate::specification::SingleTestOptions;
+
+/// Convert a glob `pattern` (where `*` matches any sequence of
+/// characters, and `?` matches any single character) into a regular
+/// expression which matches the whole string.
+fn glob_regex(pattern: &str) -> Regex {
+    let mut regex = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).expect("escaped glob pattern should be a valid regex")
+}
+
+/// Whether `text` matches the glob `pattern`, see [glob_regex()].
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    glob_regex(pattern).is_match(text)
+}
+
+/// Which tests to run. If [TestSelection::ids] or
+/// [TestSelection::tags] are specified, only the tests which match
+/// them are run (regardless of their `skip` value), otherwise the
+/// tests which are not marked `skip` are run. Tests with any of the
+/// [TestSelection::exclude_tags] are never run.
+#[derive(Debug, Clone, Default)]
+pub struct TestSelection {
+    /// Test ids, or glob patterns matching test ids. A test is
+    /// selected if it matches any of these.
+    pub ids: Vec<String>,
+    /// A test is selected if it has all of these tags.
+    pub tags: Vec<String>,
+    /// A test is not selected if it has any of these tags.
+    pub exclude_tags: Vec<String>,
+}
+
+impl TestSelection {
+    /// Select only the tests with the specified `ids`.
+    pub fn ids(ids: &[&str]) -> Self {
+        Self {
+            ids: ids.iter().map(|id| id.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Whether the tests are explicitly selected by id or tag, rather
+    /// than by their `skip` value.
+    pub fn is_explicit(&self) -> bool {
+        !self.ids.is_empty() || !self.tags.is_empty()
+    }
+
+    /// The reason that the test with the specified `options` is not
+    /// selected to run, or `None` if it is selected.
+    pub fn skip_reason(&self, options: &SingleTestOptions) -> Option<&'static str> {
+        let tags = options.all_tags();
+        let has_tag = |tag: &String| tags.iter().any(|test_tag| test_tag == tag);
+
+        if self.exclude_tags.iter().any(has_tag) {
+            return Some("Test has a tag which was excluded");
+        }
+
+        if self.is_explicit() {
+            let id_selected = self.ids.is_empty()
+                || self
+                    .ids
+                    .iter()
+                    .any(|pattern| glob_matches(pattern, &options.id));
+            let tags_selected = self.tags.iter().all(has_tag);
+
+            if id_selected && tags_selected {
+                None
+            } else {
+                Some("Test was not selected to run")
+            }
+        } else if options.skip {
+            Some("Test is marked as skipped in the specification")
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{glob_matches, TestSelection};
+    use crate::specification::SingleTestOptions;
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("drop", "drop"));
+        assert!(!glob_matches("drop", "drop-2"));
+        assert!(glob_matches("drop*", "drop-2"));
+        assert!(glob_matches("*-v?-c1", "inner-inner-v2-c1"));
+        assert!(!glob_matches("*-v?-c1", "inner-inner-v10-c1"));
+        assert!(glob_matches("a.b", "a.b"));
+        assert!(!glob_matches("a.b", "axb"));
+    }
+
+    #[test]
+    fn test_selection() {
+        let tests: Vec<SingleTestOptions> = ron::from_str(
+            r#"[
+                (
+                    id: "drop-dev",
+                    verifiers: 1,
+                    environment: development,
+                    tags: ["drop"],
+                    rounds: [(contributors: 1)],
+                ),
+                (
+                    id: "drop-inner",
+                    verifiers: 1,
+                    environment: inner,
+                    tags: ["drop", "slow"],
+                    rounds: [(contributors: 1)],
+                ),
+                (
+                    id: "skipped",
+                    verifiers: 1,
+                    environment: inner,
+                    skip: true,
+                    rounds: [(contributors: 1)],
+                ),
+            ]"#,
+        )
+        .unwrap();
+
+        let selected = |selection: &TestSelection| -> Vec<&str> {
+            tests
+                .iter()
+                .filter(|options| selection.skip_reason(options).is_none())
+                .map(|options| options.id.as_str())
+                .collect()
+        };
+
+        assert_eq!(
+            selected(&TestSelection::default()),
+            vec!["drop-dev", "drop-inner"]
+        );
+        assert_eq!(
+            selected(&TestSelection::ids(&["skipped", "drop-d*"])),
+            vec!["drop-dev", "skipped"]
+        );
+        assert_eq!(
+            selected(&TestSelection {
+                tags: vec!["drop".to_string(), "inner".to_string()],
+                ..TestSelection::default()
+            }),
+            vec!["drop-inner"]
+        );
+        assert_eq!(
+            selected(&TestSelection {
+                exclude_tags: vec!["slow".to_string()],
+                ..TestSelection::default()
+            }),
+            vec!["drop-dev"]
+        );
+    }
+}
@@ -1,34 +1,179 @@
 //! This module contains functions for running multiple integration
 //! tests.
 
-use std::time::Duration;
+use std::{
+    collections::{HashSet, VecDeque},
+    convert::TryFrom,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
 
 use color_eyre::Section;
 use eyre::Context;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    baseline::{
+        compare_results, format_comparison_table, load_baseline, save_baseline, BaselineTolerance,
+        Comparison, ComparisonStatus, BASELINE_COMPARISON_FILE_NAME,
+    },
     config::Config,
-    reporting::LogFileWriter,
-    test::{integration_test, TestOptions, TestRound},
-    util::create_dir_if_not_exists,
+    coordinator::CoordinatorConfigOverrides,
+    junit::write_junit_report_file,
+    matrix::TestMatrix,
+    process::ProcessesConfig,
+    reporting::{LogFileWriter, LOG_FILE_NAME},
+    retention::apply_retention,
+    selection::TestSelection,
+    summary::{format_summary_table, write_error_files, ERROR_FILE_NAME, SUMMARY_FILE_NAME},
+    test::{
+        integration_test, prepare_components, StateMonitorOptions, TestOptions, TestResults,
+        TestRound, DEFAULT_COORDINATOR_PORT,
+    },
+    time_limit::TimeLimitExceeded,
+    util::{create_dir_if_not_exists, free_ports},
+    validate::{
+        validate_config, validate_stand_in_only, validate_test, validation_result, ValidationError,
+    },
     Environment,
 };
 
 /// Specification for multiple tests to be performed. Will be
-/// deserialized from a ron file.
-#[derive(Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+/// deserialized from a ron file (see [SpecificationFile]), with any
+/// [TestMatrix] expanded into the individual tests.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(try_from = "SpecificationFile")]
 pub struct Specification {
     /// Specifications for the individual tests.
     pub tests: Vec<SingleTestOptions>,
 }
 
+/// The format of the specification ron file.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SpecificationFile {
+    /// (Optional) Specifications for the individual tests. Default:
+    /// `[]`.
+    #[serde(default)]
+    pub tests: Vec<SingleTestOptions>,
+    /// (Optional) Matrices of tests, which are expanded into
+    /// individual tests after the `tests`. Default: `[]`.
+    #[serde(default)]
+    pub matrices: Vec<TestMatrix>,
+}
+
+impl TryFrom<SpecificationFile> for Specification {
+    type Error = eyre::Error;
+
+    fn try_from(file: SpecificationFile) -> Result<Self, Self::Error> {
+        let mut tests = file.tests;
+        for matrix in &file.matrices {
+            tests.extend(matrix.expand()?);
+        }
+
+        let mut ids: HashSet<&str> = HashSet::new();
+        for test in &tests {
+            if !ids.insert(&test.id) {
+                return Err(eyre::eyre!(
+                    "More than one test in the specification has the id {:?}",
+                    test.id
+                ));
+            }
+        }
+
+        Ok(Self { tests })
+    }
+}
+
 pub type TestId = String;
 
+/// The reason given for skipping the tests which were not started
+/// because an earlier test failed with [Config::fail_fast] enabled.
+const FAIL_FAST_SKIP_REASON: &str = "Not run because an earlier test failed (fail fast)";
+
+/// The outcome of an individual test.
+#[derive(Debug)]
+pub enum TestOutcome {
+    /// The test ran successfully.
+    Passed,
+    /// The test failed with the specified error.
+    Failed(eyre::Error),
+    /// The test failed after exceeding its time limit, with the
+    /// specified error.
+    TimedOut(eyre::Error),
+    /// The test was not run, for the specified reason.
+    Skipped(String),
+}
+
+impl TestOutcome {
+    /// The error that the test failed with, if it failed.
+    pub fn error(&self) -> Option<&eyre::Error> {
+        match self {
+            Self::Failed(error) | Self::TimedOut(error) => Some(error),
+            Self::Passed | Self::Skipped(_) => None,
+        }
+    }
+}
+
+/// Report for an individual test in the [Specification].
+#[derive(Debug)]
+pub struct TestReport {
+    /// Id of the test.
+    pub id: TestId,
+    /// The time taken to run the test.
+    pub duration: Duration,
+    /// The outcome of the test.
+    pub outcome: TestOutcome,
+    /// The results of the test, if it ran successfully.
+    pub results: Option<TestResults>,
+    /// The number of attempts made to run the test (`0` if it was
+    /// skipped).
+    pub attempts: u32,
+    /// The earlier attempts which failed before the final attempt
+    /// (whose outcome is [TestReport::outcome]).
+    pub failed_attempts: Vec<FailedAttempt>,
+}
+
+impl TestReport {
+    /// A report for the test with the specified `id` which was not
+    /// run, for the specified `reason`.
+    pub fn skipped(id: TestId, reason: impl Into<String>) -> Self {
+        Self {
+            id,
+            duration: Duration::from_secs(0),
+            outcome: TestOutcome::Skipped(reason.into()),
+            results: None,
+            attempts: 0,
+            failed_attempts: Vec::new(),
+        }
+    }
+
+    /// Whether the test passed, but only after it was retried.
+    pub fn is_flaky(&self) -> bool {
+        matches!(self.outcome, TestOutcome::Passed) && !self.failed_attempts.is_empty()
+    }
+}
+
+/// An attempt at running a test which failed, and was followed by a
+/// retry.
+#[derive(Debug)]
+pub struct FailedAttempt {
+    /// The number of the attempt, starting at `1`.
+    pub attempt: u32,
+    /// The time taken by the attempt.
+    pub duration: Duration,
+    /// Why the attempt failed.
+    pub error: eyre::Error,
+}
+
 /// Options for each individual test in the [Specification]'s `tests`
 /// field.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct SingleTestOptions {
     /// Id for the individual test.
@@ -56,10 +201,81 @@ pub struct SingleTestOptions {
     #[serde(default = "skip_default")]
     pub skip: bool,
 
+    /// (Optional) Tags used to select this test to run with the
+    /// `--tag` and `--exclude-tag` command line options. The test is
+    /// also implicitly tagged with its `environment`. Default: `[]`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// (Optional) Overrides for the configuration generated for the
+    /// `aleo-setup-coordinator`. Any values not specified use the
+    /// integration test's defaults. Default: `None`
+    #[serde(default)]
+    pub coordinator: Option<CoordinatorConfigOverrides>,
+
+    /// (Optional) Whether to verify the transcript with the
+    /// `verify-transcript` binary after each round has finished. This
+    /// is only supported with the stand-ins, see
+    /// [crate::transcript]. Default: `false`.
+    #[serde(default)]
+    pub verify_transcript: bool,
+
+    /// (Optional) Whether to connect the contributors and verifiers
+    /// to the coordinator via a fault-injecting network proxy (see
+    /// [crate::network_proxy]). This is always enabled if any round
+    /// configures [TestRound::network_faults]. Default: `false`.
+    #[serde(default)]
+    pub network_proxy: bool,
+
+    /// (Optional) The tolerance for regressions in the round and
+    /// aggregation durations when comparing against a baseline with
+    /// [Config::compare_baseline]. Default: `(warn: 0.1, fail:
+    /// 0.25)`.
+    #[serde(default)]
+    pub baseline_tolerance: BaselineTolerance,
+
+    /// (Optional) The number of times to retry this test if it fails,
+    /// overriding [Config::retries]. Each attempt is run in its own
+    /// `attempt-<n>` directory in the test's out directory. A test
+    /// which only passes on a retry is reported as flaky. Default:
+    /// `None`.
+    #[serde(default)]
+    pub retries: Option<u32>,
+
+    /// (Optional) Extra environment variables, arguments and log
+    /// filters for the processes of the components of the ceremony,
+    /// applied on top of [Config::processes]. Default: none.
+    #[serde(default)]
+    pub processes: ProcessesConfig,
+
     /// Configure the tests performed for each round of the ceremony.
     pub rounds: Vec<TestRound>,
 }
 
+impl SingleTestOptions {
+    /// The [SingleTestOptions::tags] of the test, including the
+    /// implicit tag for its environment.
+    pub fn all_tags(&self) -> Vec<String> {
+        std::iter::once(self.environment.to_string())
+            .chain(self.tags.iter().cloned())
+            .collect()
+    }
+}
+
+/// Remove the `out_dir` containing the artifacts created during a
+/// previous integration test run, for [Config::clean].
+pub fn clean_out_dir(out_dir: &Path) -> eyre::Result<()> {
+    tracing::info!("Cleaning integration test.");
+
+    if out_dir.exists() {
+        tracing::info!("Removing out dir: {:?}", out_dir);
+        std::fs::remove_dir_all(out_dir)
+            .wrap_err_with(|| eyre::eyre!("Error while removing {:?}", out_dir))?;
+    }
+
+    Ok(())
+}
+
 /// Default value for [TestOptions::replacement_contributors].
 fn default_replacement_contributors() -> u8 {
     0
@@ -70,20 +286,126 @@ fn skip_default() -> bool {
 }
 
 impl Specification {
-    /// Run multiple tests specified in the ron specification file.
+    /// Check the tests in the specification and the `config` for
+    /// problems which can be found without running the tests.
+    /// Returns all of the problems which were found.
+    pub fn validate(&self, config: &Config) -> Vec<ValidationError> {
+        let mut errors = validate_config(config);
+
+        if self.tests.is_empty() {
+            errors.push(ValidationError {
+                test: None,
+                round: None,
+                message: "Expected at least one test to be defined in the specification file."
+                    .to_string(),
+            });
+        }
+
+        for options in &self.tests {
+            errors.extend(validate_test(
+                Some(&options.id),
+                options.verifiers,
+                options.replacement_contributors,
+                &options.coordinator.clone().unwrap_or_default(),
+                &config.processes.merge(&options.processes),
+                &options.rounds,
+            ));
+            errors.extend(validate_stand_in_only(
+                Some(&options.id),
+                config.stand_ins.is_some(),
+                options.verify_transcript,
+                &options.rounds,
+            ));
+        }
+
+        errors
+    }
+
+    /// Load the specification files at `paths`, and combine their
+    /// tests into a single specification. A path which is a
+    /// directory is replaced by the `.ron` files that it contains
+    /// (in order of their names).
+    pub fn load(paths: &[PathBuf]) -> eyre::Result<Self> {
+        let mut files: Vec<PathBuf> = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                let mut dir_files = std::fs::read_dir(path)
+                    .wrap_err_with(|| {
+                        eyre::eyre!("Error while reading specification directory {:?}", path)
+                    })?
+                    .map(|entry| Ok(entry?.path()))
+                    .collect::<std::io::Result<Vec<PathBuf>>>()?;
+                dir_files.retain(|file| file.extension().map_or(false, |ext| ext == "ron"));
+                dir_files.sort();
+                files.extend(dir_files);
+            } else {
+                files.push(path.clone());
+            }
+        }
+
+        let mut tests: Vec<SingleTestOptions> = Vec::new();
+        for file in &files {
+            let specification_string = std::fs::read_to_string(file).wrap_err_with(|| {
+                eyre::eyre!("Error while reading specification ron file {:?}", file)
+            })?;
+            let specification: Specification =
+                ron::from_str(&specification_string).wrap_err_with(|| {
+                    eyre::eyre!("Error while parsing test specification {:?}", file)
+                })?;
+
+            for options in specification.tests {
+                if tests.iter().any(|test| test.id == options.id) {
+                    return Err(eyre::eyre!(
+                        "The test id {:?} in {:?} is used by another specification file",
+                        options.id,
+                        file
+                    ));
+                }
+                tests.push(options);
+            }
+        }
+
+        Ok(Self { tests })
+    }
+
+    /// The tests which are selected to run with the `selection`, and
+    /// the reasons that the other tests are skipped.
+    pub fn select<'a>(
+        &'a self,
+        selection: &TestSelection,
+    ) -> (
+        Vec<&'a SingleTestOptions>,
+        Vec<(&'a SingleTestOptions, &'static str)>,
+    ) {
+        let mut selected = Vec::new();
+        let mut skipped = Vec::new();
+        for options in &self.tests {
+            match selection.skip_reason(options) {
+                Some(reason) => skipped.push((options, reason)),
+                None => selected.push(options),
+            }
+        }
+        (selected, skipped)
+    }
+
+    /// Run the tests in the specification which are selected by the
+    /// `selection`.
     ///
-    /// If `only_tests` contains some values, only the test id's contained
-    /// within this vector will be run. This will override the test's skip
-    /// value.
+    /// Up to [Config::parallelism] tests will be run at the same
+    /// time.
     pub fn run(
         &self,
         config: &Config,
-        only_tests: &[TestId],
+        selection: &TestSelection,
         log_writer: &LogFileWriter,
     ) -> eyre::Result<()> {
-        if self.tests.is_empty() {
+        validation_result(self.validate(config))?;
+
+        let (selected_tests, skipped_tests) = self.select(selection);
+        if selected_tests.is_empty() && selection.is_explicit() {
             return Err(eyre::eyre!(
-                "Expected at least one test to be defined in the specification file."
+                "None of the tests in the specification match the selection {:?}",
+                selection
             ));
         }
 
@@ -91,36 +413,52 @@ impl Specification {
 
         // Perfom the clean action if required.
         if config.clean {
-            tracing::info!("Cleaning integration test.");
-
-            if out_dir.exists() {
-                tracing::info!("Removing out dir: {:?}", out_dir);
-                std::fs::remove_dir_all(&out_dir)?;
-            }
+            clean_out_dir(&out_dir)?;
         }
 
         create_dir_if_not_exists(&out_dir)?;
 
-        let mut errors: Vec<eyre::Error> = self
-            .tests
-            .iter()
-            .filter(|options| {
-                if !only_tests.is_empty() {
-                    only_tests.contains(&options.id)
-                } else if options.skip {
-                    tracing::info!("Skipping test {}", options.id);
-                    false
-                } else {
-                    true
-                }
+        let skipped_reports: Vec<TestReport> = skipped_tests
+            .into_iter()
+            .map(|(options, reason)| {
+                tracing::info!("Skipping test {}: {}", options.id, reason);
+                TestReport::skipped(options.id.clone(), reason)
             })
+            .collect();
+
+        let parallel = config.parallelism > 1 && selected_tests.len() > 1;
+
+        // Each test running in parallel requires its own ports for
+        // the coordinator and state monitor.
+        let mut ports = if parallel {
+            free_ports(selected_tests.len() * 2)?
+        } else {
+            Vec::new()
+        }
+        .into_iter();
+
+        let tests: Vec<(TestId, TestOptions, u32)> = selected_tests
+            .into_iter()
             .enumerate()
             .map(|(i, options)| {
-                let test_id = &options.id;
-                let out_dir = out_dir.join(test_id);
+                let test_id = options.id.clone();
+                let out_dir = out_dir.join(&test_id);
 
                 dbg!(&options);
 
+                let mut state_monitor: Option<StateMonitorOptions> =
+                    config.state_monitor.clone().map(Into::into);
+                let coordinator_port = if parallel {
+                    let coordinator_port = ports.next().expect("expected a free port");
+                    let state_monitor_port = ports.next().expect("expected a free port");
+                    if let Some(state_monitor) = &mut state_monitor {
+                        state_monitor.address.set_port(state_monitor_port);
+                    }
+                    coordinator_port
+                } else {
+                    DEFAULT_COORDINATOR_PORT
+                };
+
                 // The first test uses the keep_repos and no_prereqs
                 // option. Subsequent tests do not clean, and do not
                 // attempt to install prerequisites.
@@ -134,10 +472,17 @@ impl Specification {
                         verifiers: options.verifiers,
                         out_dir,
                         environment: options.environment,
-                        state_monitor: config.state_monitor.clone().map(Into::into),
+                        state_monitor,
                         timout: options.timout.map(Duration::from_secs),
                         aleo_setup_repo: config.aleo_setup_repo.clone(),
                         aleo_setup_coordinator_repo: config.aleo_setup_coordinator_repo.clone(),
+                        stand_ins: config.stand_ins.clone(),
+                        coordinator_port,
+                        coordinator: options.coordinator.clone().unwrap_or_default(),
+                        verify_transcript: options.verify_transcript,
+                        network_proxy: options.network_proxy,
+                        resource_sample_interval: config.resource_sample_interval,
+                        processes: config.processes.merge(&options.processes),
                         rounds: options.rounds.clone(),
                     }
                 } else {
@@ -150,68 +495,395 @@ impl Specification {
                         verifiers: options.verifiers,
                         out_dir,
                         environment: options.environment,
-                        state_monitor: config.state_monitor.clone().map(Into::into),
+                        state_monitor,
                         timout: options.timout.map(Duration::from_secs),
                         aleo_setup_repo: config.aleo_setup_repo.clone(),
                         aleo_setup_coordinator_repo: config.aleo_setup_coordinator_repo.clone(),
+                        stand_ins: config.stand_ins.clone(),
+                        coordinator_port,
+                        coordinator: options.coordinator.clone().unwrap_or_default(),
+                        verify_transcript: options.verify_transcript,
+                        network_proxy: options.network_proxy,
+                        resource_sample_interval: config.resource_sample_interval,
+                        processes: config.processes.merge(&options.processes),
                         rounds: options.rounds.clone(),
                     }
                 };
 
-                (test_id, test_options)
-            })
-            .map(|(id, options)| {
-                let span = tracing::error_span!("test", id=%id);
-                let _guard = span.enter();
-
-                tracing::info!("Running integration test with id {:?}", id);
-
-                integration_test(&options, log_writer)
-                    .map(|test_results| {
-                        let test_results_str =
-                            ron::ser::to_string_pretty(&test_results, Default::default())
-                                .expect("Unable to serialize test results");
-                        tracing::info!("Test results: \n {}", test_results_str);
-                    })
-                    .wrap_err_with(|| {
-                        eyre::eyre!("Error while running individual test with id: {:?}", id)
-                    })
+                let retries = options.retries.unwrap_or(config.retries);
+
+                (test_id, test_options, retries)
             })
-            .filter(Result::is_err)
-            .map(Result::unwrap_err)
-            .map(|error| {
-                // Display error message for each error that occurs during individual tests.
-                tracing::error!("{:?}", error);
-                error
+            .collect();
+
+        let mut reports: Vec<TestReport> = if parallel {
+            run_parallel(
+                tests,
+                config.parallelism,
+                config.fail_fast,
+                &out_dir,
+                log_writer,
+            )?
+        } else {
+            let mut reports = Vec::new();
+            let mut tests = tests.into_iter();
+            for (id, options, retries) in &mut tests {
+                let report = run_test(&id, &options, retries, Some(log_writer));
+                let failed = report.outcome.error().is_some();
+                reports.push(report);
+
+                if failed && config.fail_fast {
+                    break;
+                }
+            }
+            reports.extend(tests.map(|(id, _, _)| TestReport::skipped(id, FAIL_FAST_SKIP_REASON)));
+            reports
+        };
+
+        for report in reports.iter().filter(|report| report.is_flaky()) {
+            let failures: Vec<String> = report
+                .failed_attempts
+                .iter()
+                .map(|failed| format!("  attempt {}: {:#}", failed.attempt, failed.error))
+                .collect();
+            tracing::warn!(
+                "Test {:?} is flaky, it passed on attempt {} after failing:\n{}",
+                report.id,
+                report.attempts,
+                failures.join("\n")
+            );
+        }
+
+        // Report the tests in the order that they appear in the
+        // specification.
+        reports.extend(skipped_reports);
+        reports.sort_by_key(|report| {
+            self.tests
+                .iter()
+                .position(|options| options.id == report.id)
+        });
+
+        if let Some(name) = &config.compare_baseline {
+            self.compare_baseline(config, name, &mut reports)?;
+        }
+
+        if let Some(name) = &config.save_baseline {
+            for report in &reports {
+                if let (TestOutcome::Passed, Some(results)) = (&report.outcome, &report.results) {
+                    save_baseline(&config.baselines_dir, name, &report.id, results)?;
+                }
+            }
+            tracing::info!(
+                "Saved baseline {:?} in {:?}",
+                name,
+                config.baselines_dir.join(name)
+            );
+        }
+
+        if let Some(junit_path) = &config.junit {
+            write_junit_report_file(junit_path, &reports)?;
+        }
+
+        write_error_files(&out_dir, &reports)?;
+
+        let summary = format_summary_table(&reports);
+        let summary_path = out_dir.join(SUMMARY_FILE_NAME);
+        std::fs::write(&summary_path, format!("{}\n", summary))
+            .wrap_err_with(|| eyre::eyre!("Error writing test summary {:?}", summary_path))?;
+
+        apply_retention(&config.retention, &out_dir, &reports)?;
+
+        let n_tests = reports.len();
+        let mut failed: Vec<(TestId, eyre::Error)> = reports
+            .into_iter()
+            .filter_map(|report| match report.outcome {
+                TestOutcome::Failed(error) | TestOutcome::TimedOut(error) => {
+                    Some((report.id, error))
+                }
+                _ => None,
             })
             .collect();
 
-        let n_errors = errors.len();
+        if failed.is_empty() {
+            tracing::info!("Summary of the integration tests:\n{}", summary);
+            return Ok(());
+        }
+        tracing::error!("Summary of the integration tests:\n{}", summary);
+
+        let note = || {
+            format!(
+                "The summary of the tests has been written to {:?}, and the full error of each \
+                test which failed to `{}` in the test's out directory.",
+                summary_path, ERROR_FILE_NAME
+            )
+        };
+
+        if failed.len() == 1 {
+            let (_, error) = failed.pop().expect("expected one failed test");
+            return Err(error)
+                .wrap_err_with(|| eyre::eyre!("Error during one of the integration tests"))
+                .with_note(note);
+        }
+
+        let failed_ids: Vec<TestId> = failed.into_iter().map(|(id, _)| id).collect();
+        Err(eyre::eyre!(
+            "{} of the {} integration tests failed: {}",
+            failed_ids.len(),
+            n_tests,
+            failed_ids.join(", ")
+        ))
+        .with_note(note)
+    }
+
+    /// Compare the results of the tests which passed against the
+    /// baseline `name`, writing the comparison table to
+    /// [BASELINE_COMPARISON_FILE_NAME] in the out directory. Tests
+    /// with durations which regress beyond the test's
+    /// [SingleTestOptions::baseline_tolerance] are marked as failed.
+    fn compare_baseline(
+        &self,
+        config: &Config,
+        name: &str,
+        reports: &mut [TestReport],
+    ) -> eyre::Result<()> {
+        let mut comparisons: Vec<Comparison> = Vec::new();
+
+        for report in reports.iter_mut() {
+            let results = match (&report.outcome, &report.results) {
+                (TestOutcome::Passed, Some(results)) => results,
+                _ => continue,
+            };
+
+            let baseline = match load_baseline(&config.baselines_dir, name, &report.id)? {
+                Some(baseline) => baseline,
+                None => {
+                    tracing::warn!(
+                        "Baseline {:?} contains no results for test {:?}",
+                        name,
+                        report.id
+                    );
+                    continue;
+                }
+            };
+
+            let tolerance = self
+                .tests
+                .iter()
+                .find(|options| options.id == report.id)
+                .map(|options| options.baseline_tolerance)
+                .unwrap_or_default();
 
-        // Grab the last error which will be the one actually returned by this method.
-        let last_error = errors.pop();
+            let test_comparisons = compare_results(&report.id, &baseline, results, &tolerance);
 
-        let result = match last_error {
-            Some(error) => Err(error),
-            None => Ok(()),
+            if test_comparisons
+                .iter()
+                .any(|comparison| comparison.status == ComparisonStatus::Fail)
+            {
+                report.outcome = TestOutcome::Failed(eyre::eyre!(
+                    "Durations of test {:?} regressed beyond the tolerance of baseline {:?}:\n{}",
+                    report.id,
+                    name,
+                    format_comparison_table(&test_comparisons)
+                ));
+            }
+
+            comparisons.extend(test_comparisons);
+        }
+
+        let table = format_comparison_table(&comparisons);
+        let table_path = config.out_dir.join(BASELINE_COMPARISON_FILE_NAME);
+        std::fs::write(&table_path, format!("{}\n", table))
+            .wrap_err_with(|| eyre::eyre!("Error writing baseline comparison {:?}", table_path))?;
+
+        if comparisons
+            .iter()
+            .any(|comparison| comparison.status != ComparisonStatus::Ok)
+        {
+            tracing::warn!("Comparison against baseline {:?}:\n{}", name, table);
+        } else {
+            tracing::info!("Comparison against baseline {:?}:\n{}", name, table);
+        }
+
+        Ok(())
+    }
+}
+
+/// Run an individual test, retrying it up to `retries` times if it
+/// fails. See [integration_test()] for the `log_writer` argument.
+///
+/// If the test can be retried, each attempt is run in its own
+/// `attempt-<n>` directory within [TestOptions::out_dir]. The
+/// components are only prepared for the first attempt.
+fn run_test(
+    id: &str,
+    options: &TestOptions,
+    retries: u32,
+    log_writer: Option<&LogFileWriter>,
+) -> TestReport {
+    let start_time = Instant::now();
+    let mut failed_attempts: Vec<FailedAttempt> = Vec::new();
+    let mut attempt_options = options.clone();
+    let mut attempt: u32 = 1;
+
+    loop {
+        let attempt_start_time = Instant::now();
+        let result = if retries > 0 {
+            attempt_options.out_dir = options.out_dir.join(format!("attempt-{}", attempt));
+            create_dir_if_not_exists(&options.out_dir)
+                .and_then(|_| run_test_result(id, &attempt_options, log_writer))
+        } else {
+            run_test_result(id, &attempt_options, log_writer)
         };
 
-        match n_errors {
-            1 => {
-                result.wrap_err_with(|| eyre::eyre!("Error during one of the integration tests"))
+        let (outcome, results) = match result {
+            Ok(results) => (TestOutcome::Passed, Some(results)),
+            Err(error) if attempt <= retries => {
+                tracing::warn!(
+                    "Attempt {} of {} for test {:?} failed, retrying.",
+                    attempt,
+                    retries + 1,
+                    id
+                );
+                failed_attempts.push(FailedAttempt {
+                    attempt,
+                    duration: attempt_start_time.elapsed(),
+                    error,
+                });
+
+                attempt_options.build = false;
+                attempt_options.install_prerequisites = false;
+                attempt_options.keep_repos = true;
+                attempt += 1;
+                continue;
             }
-            _ => {
-                result.wrap_err_with(|| eyre::eyre!("Errors during {} of the integration tests", n_errors))
-                    .with_note(||
-                        format!("{} errors have occurred. This error shows the trace for the last error that occurred. \
-                        Check the stdout log for ERROR trace messages for other errors.", n_errors))
+            Err(error) if error.downcast_ref::<TimeLimitExceeded>().is_some() => {
+                (TestOutcome::TimedOut(error), None)
             }
-        }
+            Err(error) => (TestOutcome::Failed(error), None),
+        };
+
+        return TestReport {
+            id: id.to_string(),
+            duration: start_time.elapsed(),
+            outcome,
+            results,
+            attempts: attempt,
+            failed_attempts,
+        };
     }
 }
 
+fn run_test_result(
+    id: &str,
+    options: &TestOptions,
+    log_writer: Option<&LogFileWriter>,
+) -> eyre::Result<TestResults> {
+    let span = tracing::error_span!("test", id=%id);
+    let _guard = span.enter();
+
+    tracing::info!("Running integration test with id {:?}", id);
+
+    integration_test(options, log_writer)
+        .inspect(|test_results| {
+            let test_results_str = ron::ser::to_string_pretty(test_results, Default::default())
+                .expect("Unable to serialize test results");
+            tracing::info!("Test results: \n {}", test_results_str);
+        })
+        .wrap_err_with(|| eyre::eyre!("Error while running individual test with id: {:?}", id))
+        .map_err(|error| {
+            // Display error message for each error that occurs during individual tests.
+            tracing::error!("{:?}", error);
+            error
+        })
+}
+
+/// Run the `tests` with up to `parallelism` tests running at the same
+/// time. The components are prepared (cloned/built) using the
+/// options of the first test before any tests are started. Returns
+/// the reports for the tests (in the order that they completed).
+///
+/// If `fail_fast` is enabled, no more tests are started after a test
+/// fails, and the tests which were not started are reported as
+/// skipped.
+fn run_parallel(
+    mut tests: Vec<(TestId, TestOptions, u32)>,
+    parallelism: usize,
+    fail_fast: bool,
+    out_dir: &Path,
+    log_writer: &LogFileWriter,
+) -> eyre::Result<Vec<TestReport>> {
+    log_writer.set_out_file(out_dir.join(LOG_FILE_NAME))?;
+
+    if let Some((_, first_options, _)) = tests.first_mut() {
+        prepare_components(first_options)?;
+        first_options.build = false;
+        first_options.install_prerequisites = false;
+    }
+
+    tracing::info!(
+        "Running {} tests with up to {} tests in parallel.",
+        tests.len(),
+        parallelism
+    );
+
+    // Write the log output of each test to its own out directory as
+    // well, as the output of the tests is interleaved in the log in
+    // `out_dir`.
+    log_writer.set_tests_out_dir(Some(out_dir));
+
+    let n_workers = parallelism.min(tests.len());
+    let queue: Arc<Mutex<VecDeque<(TestId, TestOptions, u32)>>> =
+        Arc::new(Mutex::new(tests.into_iter().collect()));
+    let reports: Arc<Mutex<Vec<TestReport>>> = Arc::new(Mutex::new(Vec::new()));
+    let failed = Arc::new(AtomicBool::new(false));
+
+    let workers: Vec<JoinHandle<()>> = (0..n_workers)
+        .map(|_| {
+            let queue = queue.clone();
+            let reports = reports.clone();
+            let failed = failed.clone();
+            std::thread::spawn(move || loop {
+                if fail_fast && failed.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let next_test = queue.lock().expect("error obtaining lock").pop_front();
+                let (id, options, retries) = match next_test {
+                    Some(test) => test,
+                    None => break,
+                };
+
+                let report = run_test(&id, &options, retries, None);
+                if report.outcome.error().is_some() {
+                    failed.store(true, Ordering::SeqCst);
+                }
+                reports.lock().expect("error obtaining lock").push(report);
+            })
+        })
+        .collect();
+
+    let join_result = workers.into_iter().try_for_each(|worker| {
+        worker
+            .join()
+            .map_err(|_| eyre::eyre!("Test worker thread panicked"))
+    });
+    log_writer.set_tests_out_dir(None);
+    join_result?;
+
+    let mut reports = std::mem::take(&mut *reports.lock().expect("error obtaining lock"));
+    let not_started = std::mem::take(&mut *queue.lock().expect("error obtaining lock"));
+    reports.extend(
+        not_started
+            .into_iter()
+            .map(|(id, _, _)| TestReport::skipped(id, FAIL_FAST_SKIP_REASON)),
+    );
+    Ok(reports)
+}
+
 #[cfg(test)]
 mod test {
+    use std::path::PathBuf;
+
     use super::Specification;
 
     /// Test deserializing `example-config.ron` to [Specification].
@@ -222,4 +894,40 @@ mod test {
         let _example: Specification =
             ron::from_str(&example_string).expect("Error while deserializing example-config.ron");
     }
+
+    /// Test loading the specification files in a directory.
+    #[test]
+    fn test_load_specification_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let test = |id: &str| {
+            format!(
+                r#"(
+                    tests: [
+                        (
+                            id: "{}",
+                            verifiers: 1,
+                            environment: development,
+                            rounds: [(contributors: 1)],
+                        ),
+                    ],
+                )"#,
+                id
+            )
+        };
+        std::fs::write(dir.path().join("b.ron"), test("b")).unwrap();
+        std::fs::write(dir.path().join("a.ron"), test("a")).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a specification").unwrap();
+
+        let specification = Specification::load(&[dir.path().to_owned()]).unwrap();
+        let ids: Vec<&str> = specification
+            .tests
+            .iter()
+            .map(|options| options.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["a", "b"]);
+
+        // The same test id in more than one file is an error.
+        let duplicate: PathBuf = dir.path().join("a.ron");
+        assert!(Specification::load(&[dir.path().to_owned(), duplicate]).is_err());
+    }
 }
@@ -0,0 +1,341 @@
+//! Shared functionality for the stand-in `aleo-setup-coordinator`,
+//! `setup1-contributor`, `setup1-verifier`, `view-key` and
+//! `verify-transcript` binaries (`stand-in-coordinator`,
+//! `stand-in-contributor`, `stand-in-verifier`, `stand-in-view-key`
+//! and `stand-in-verify-transcript`) which are shipped with this
+//! crate in order to test the integration test harness itself. See
+//! [crate::config::Config::stand_ins].
+//!
+//! The stand-ins communicate over a simple line based TCP protocol
+//! (see [Request] and [Response]), and print the same log lines that
+//! the integration test expects from the real binaries.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    io::{BufRead, BufReader, Write},
+    net::TcpStream,
+    str::FromStr,
+    time::Duration,
+};
+
+use eyre::Context;
+
+use crate::{AleoPublicKey, Environment};
+
+/// Name of the stand-in for the `aleo-setup-coordinator` binary.
+pub const COORDINATOR_BIN: &str = "stand-in-coordinator";
+/// Name of the stand-in for the `setup1-contributor` binary.
+pub const CONTRIBUTOR_BIN: &str = "stand-in-contributor";
+/// Name of the stand-in for the `setup1-verifier` binary.
+pub const VERIFIER_BIN: &str = "stand-in-verifier";
+/// Name of the stand-in for the `view-key` binary.
+pub const VIEW_KEY_BIN: &str = "stand-in-view-key";
+/// Name of the stand-in for the `verify-transcript` binary.
+pub const VERIFY_TRANSCRIPT_BIN: &str = "stand-in-verify-transcript";
+
+/// Name of the file containing the aggregated output of a round,
+/// written by the stand-in coordinator to each round directory in
+/// the transcript.
+pub const AGGREGATED_ROUND_FILE: &str = "round.verified";
+
+/// Time the stand-in contributor spends "computing" each
+/// contribution. This needs to be long enough that a contributor can
+/// be killed by the integration test before it makes its next
+/// contribution.
+pub const CONTRIBUTION_DURATION: Duration = Duration::from_millis(400);
+/// Time the stand-in verifier spends "verifying" each contribution.
+pub const VERIFICATION_DURATION: Duration = Duration::from_millis(50);
+/// Time the stand-in coordinator spends "aggregating" each round.
+pub const AGGREGATION_DURATION: Duration = Duration::from_millis(500);
+/// Delay between polls for new tasks by the stand-in participants.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The number of chunks in a round for the specified `environment`.
+pub fn number_of_chunks(environment: Environment) -> u64 {
+    match environment {
+        Environment::Development => 12,
+        Environment::Inner | Environment::Outer | Environment::Universal => 16,
+    }
+}
+
+/// The type of participant joining the stand-in ceremony.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticipantType {
+    Contributor,
+    Verifier,
+}
+
+impl std::fmt::Display for ParticipantType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParticipantType::Contributor => f.write_str("contributor"),
+            ParticipantType::Verifier => f.write_str("verifier"),
+        }
+    }
+}
+
+impl FromStr for ParticipantType {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "contributor" => Ok(Self::Contributor),
+            "verifier" => Ok(Self::Verifier),
+            _ => Err(eyre::eyre!("unknown participant type: {:?}", s)),
+        }
+    }
+}
+
+/// A request sent by a stand-in participant to the stand-in
+/// coordinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    /// Join the ceremony queue.
+    Join {
+        participant_type: ParticipantType,
+        address: AleoPublicKey,
+    },
+    /// Request the next task for the participant.
+    Next,
+    /// A contributor has completed its contribution to a chunk.
+    Contributed { chunk: u64 },
+    /// A verifier has verified a contribution to a chunk.
+    Verified {
+        chunk: u64,
+        contributor: AleoPublicKey,
+    },
+}
+
+impl std::fmt::Display for Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Request::Join {
+                participant_type,
+                address,
+            } => write!(f, "JOIN {} {}", participant_type, address),
+            Request::Next => f.write_str("NEXT"),
+            Request::Contributed { chunk } => write!(f, "CONTRIBUTED {}", chunk),
+            Request::Verified { chunk, contributor } => {
+                write!(f, "VERIFIED {} {}", chunk, contributor)
+            }
+        }
+    }
+}
+
+impl FromStr for Request {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["JOIN", participant_type, address] => Ok(Self::Join {
+                participant_type: participant_type.parse()?,
+                address: address.parse()?,
+            }),
+            ["NEXT"] => Ok(Self::Next),
+            ["CONTRIBUTED", chunk] => Ok(Self::Contributed {
+                chunk: chunk.parse()?,
+            }),
+            ["VERIFIED", chunk, contributor] => Ok(Self::Verified {
+                chunk: chunk.parse()?,
+                contributor: contributor.parse()?,
+            }),
+            _ => Err(eyre::eyre!("unable to parse request {:?}", s)),
+        }
+    }
+}
+
+/// A response sent by the stand-in coordinator to a stand-in
+/// participant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    /// The request was accepted.
+    Ok,
+    /// There is currently no task for the participant.
+    Wait,
+    /// The contributor should contribute to the specified chunk.
+    Contribute { chunk: u64 },
+    /// The verifier should verify the contribution to the specified
+    /// chunk.
+    Verify {
+        chunk: u64,
+        contributor: AleoPublicKey,
+    },
+}
+
+impl std::fmt::Display for Response {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Response::Ok => f.write_str("OK"),
+            Response::Wait => f.write_str("WAIT"),
+            Response::Contribute { chunk } => write!(f, "CONTRIBUTE {}", chunk),
+            Response::Verify { chunk, contributor } => {
+                write!(f, "VERIFY {} {}", chunk, contributor)
+            }
+        }
+    }
+}
+
+impl FromStr for Response {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["OK"] => Ok(Self::Ok),
+            ["WAIT"] => Ok(Self::Wait),
+            ["CONTRIBUTE", chunk] => Ok(Self::Contribute {
+                chunk: chunk.parse()?,
+            }),
+            ["VERIFY", chunk, contributor] => Ok(Self::Verify {
+                chunk: chunk.parse()?,
+                contributor: contributor.parse()?,
+            }),
+            _ => Err(eyre::eyre!("unable to parse response {:?}", s)),
+        }
+    }
+}
+
+/// Characters used to generate the stand-in aleo addresses.
+const ADDRESS_ALPHABET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Deterministically generate a (fake) aleo address from a `seed`.
+pub fn address_from_seed(seed: &str) -> AleoPublicKey {
+    let mut address = String::from("aleo1");
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+
+    while address.len() < 63 {
+        let state = hasher.finish();
+        address.push(ADDRESS_ALPHABET[(state % 32) as usize] as char);
+        state.hash(&mut hasher);
+    }
+
+    AleoPublicKey::from_str(&address).expect("generated address should be valid")
+}
+
+/// Compute the (fake) aggregated output of a round from the
+/// aggregated output of the previous round (empty for the first
+/// round) and the round's `state.json`, chaining the rounds of the
+/// transcript together.
+pub fn aggregated_round_output(previous_output: &str, state: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    previous_output.hash(&mut hasher);
+    state.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Generate a unique seed based on the current time and process.
+pub fn unique_seed() -> String {
+    format!("{:?}-{}", std::time::SystemTime::now(), std::process::id())
+}
+
+/// Convert a coordinator api url (e.g. `http://localhost:9000`) to a
+/// socket address that can be used with [TcpStream::connect()].
+pub fn api_url_to_address(api_url: &str) -> String {
+    let address = api_url
+        .trim_start_matches("http://")
+        .trim_start_matches("https://");
+    address.trim_end_matches('/').to_string()
+}
+
+/// A connection from a stand-in participant to the stand-in
+/// coordinator.
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    /// Connect to the stand-in coordinator at `api_url`, and join the
+    /// ceremony. Retries until the connection succeeds.
+    pub fn join(api_url: &str, participant_type: ParticipantType, address: &AleoPublicKey) -> Self {
+        let socket_address = api_url_to_address(api_url);
+
+        loop {
+            match Self::try_join(&socket_address, participant_type, address) {
+                Ok(connection) => return connection,
+                Err(error) => {
+                    println!("Unable to join ceremony at {}: {}", api_url, error);
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+        }
+    }
+
+    fn try_join(
+        socket_address: &str,
+        participant_type: ParticipantType,
+        address: &AleoPublicKey,
+    ) -> eyre::Result<Self> {
+        let writer = TcpStream::connect(socket_address)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        let mut connection = Self { reader, writer };
+
+        let request = Request::Join {
+            participant_type,
+            address: address.clone(),
+        };
+        match connection.request(&request)? {
+            Response::Ok => Ok(connection),
+            unexpected => Err(eyre::eyre!("unexpected response to join: {}", unexpected)),
+        }
+    }
+
+    /// Send a request to the coordinator and wait for the response.
+    pub fn request(&mut self, request: &Request) -> eyre::Result<Response> {
+        writeln!(self.writer, "{}", request).wrap_err("error sending request")?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(eyre::eyre!("connection closed by coordinator"));
+        }
+        line.trim().parse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{address_from_seed, Request, Response};
+
+    #[test]
+    fn test_address_from_seed() {
+        let address = address_from_seed("seed");
+        assert_eq!(address, address_from_seed("seed"));
+        assert_ne!(address, address_from_seed("other seed"));
+    }
+
+    #[test]
+    fn test_protocol_round_trip() {
+        let address = address_from_seed("seed");
+        let requests = vec![
+            Request::Join {
+                participant_type: super::ParticipantType::Verifier,
+                address: address.clone(),
+            },
+            Request::Next,
+            Request::Contributed { chunk: 3 },
+            Request::Verified {
+                chunk: 3,
+                contributor: address.clone(),
+            },
+        ];
+        for request in requests {
+            assert_eq!(request, request.to_string().parse::<Request>().unwrap());
+        }
+
+        let responses = vec![
+            Response::Ok,
+            Response::Wait,
+            Response::Contribute { chunk: 1 },
+            Response::Verify {
+                chunk: 1,
+                contributor: address,
+            },
+        ];
+        for response in responses {
+            assert_eq!(response, response.to_string().parse::<Response>().unwrap());
+        }
+    }
+}
@@ -3,6 +3,7 @@
 use crate::{
     process::{
         default_parse_exit_status, fallible_monitor, run_monitor_process, MonitorProcessJoin,
+        ProcessConfig,
     },
     CeremonyMessage,
 };
@@ -30,6 +31,9 @@ pub struct StateMonitorConfig {
     /// the state monitor will be stored (and the current working
     /// directory for the process).
     pub out_dir: PathBuf,
+    /// Extra environment variables, arguments and the log filter for
+    /// the state monitor process.
+    pub process: ProcessConfig,
 }
 
 /// Starts the `aleo-setup-state-monitor` server.
@@ -55,6 +59,7 @@ pub fn run_state_monitor(
         .arg(config.transcript_dir)
         .arg("--address")
         .arg(config.address.to_string());
+    let exec = config.process.apply(exec, None);
 
     let log_file_path = config.out_dir.join("state_monitor.log");
 
@@ -0,0 +1,177 @@
+//! The summary of the outcomes of the tests in a
+//! [crate::specification::Specification] at the end of a run, and the
+//! files containing the errors of the tests which failed.
+
+use std::{path::Path, time::Duration};
+
+use eyre::Context;
+use humantime::format_duration;
+
+use crate::{
+    reporting::{format_table, strip_ansi_escapes},
+    specification::{TestOutcome, TestReport},
+};
+
+/// The name of the file in the out directory that the summary table
+/// is written to.
+pub const SUMMARY_FILE_NAME: &str = "summary.txt";
+
+/// The name of the file in a test's out directory that the full error
+/// of the test is written to, if it failed.
+pub const ERROR_FILE_NAME: &str = "error.txt";
+
+/// A short description of the outcome of a test, for the summary.
+pub fn status(report: &TestReport) -> &'static str {
+    match &report.outcome {
+        TestOutcome::Passed if report.is_flaky() => "flaky",
+        TestOutcome::Passed => "passed",
+        TestOutcome::Failed(_) => "failed",
+        TestOutcome::TimedOut(_) => "timed out",
+        TestOutcome::Skipped(_) => "skipped",
+    }
+}
+
+/// The first line of the message of `error`'s root cause, which is
+/// usually the most specific description of what went wrong.
+fn error_summary(error: &eyre::Error) -> String {
+    strip_ansi_escapes(&error.root_cause().to_string())
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Format a table with a line for each of the `reports`, containing
+/// the test's id, status, duration, the number of attempts, and a
+/// summary of its error (or the reason that it was skipped).
+pub fn format_summary_table(reports: &[TestReport]) -> String {
+    let header = ["test", "status", "duration", "attempts", "error"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+    let rows: Vec<Vec<String>> = std::iter::once(header)
+        .chain(reports.iter().map(|report| {
+            let details = match &report.outcome {
+                TestOutcome::Passed => report
+                    .failed_attempts
+                    .last()
+                    .map(|failed| {
+                        format!(
+                            "(attempt {}) {}",
+                            failed.attempt,
+                            error_summary(&failed.error)
+                        )
+                    })
+                    .unwrap_or_default(),
+                TestOutcome::Failed(error) | TestOutcome::TimedOut(error) => error_summary(error),
+                TestOutcome::Skipped(reason) => reason.clone(),
+            };
+
+            vec![
+                report.id.clone(),
+                status(report).to_string(),
+                // Round to milliseconds to keep the table readable.
+                format_duration(Duration::from_millis(report.duration.as_millis() as u64))
+                    .to_string(),
+                report.attempts.to_string(),
+                details,
+            ]
+        }))
+        .collect();
+
+    format_table(&rows)
+}
+
+/// Write the full error of each test in `reports` which failed to
+/// [ERROR_FILE_NAME] in the test's directory in `out_dir`, followed
+/// by the errors of any earlier attempts.
+pub fn write_error_files(out_dir: &Path, reports: &[TestReport]) -> eyre::Result<()> {
+    for report in reports {
+        let error = match report.outcome.error() {
+            Some(error) => error,
+            None => continue,
+        };
+
+        let mut contents = format!("{:?}\n", error);
+        for failed in &report.failed_attempts {
+            contents.push_str(&format!(
+                "\nAttempt {}:\n{:?}\n",
+                failed.attempt, failed.error
+            ));
+        }
+
+        let test_dir = out_dir.join(&report.id);
+        std::fs::create_dir_all(&test_dir)
+            .wrap_err_with(|| eyre::eyre!("Error while creating path {:?}", test_dir))?;
+        let error_path = test_dir.join(ERROR_FILE_NAME);
+        std::fs::write(&error_path, strip_ansi_escapes(&contents).as_bytes())
+            .wrap_err_with(|| eyre::eyre!("Error writing test error {:?}", error_path))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{format_summary_table, write_error_files, ERROR_FILE_NAME};
+    use crate::specification::{TestOutcome, TestReport};
+
+    fn report(id: &str, outcome: TestOutcome) -> TestReport {
+        TestReport {
+            id: id.to_string(),
+            duration: Duration::from_millis(1500),
+            outcome,
+            results: None,
+            attempts: 1,
+            failed_attempts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_format_summary_table() {
+        let reports = vec![
+            report("passed", TestOutcome::Passed),
+            report(
+                "failed",
+                TestOutcome::Failed(
+                    eyre::eyre!("contributor dropped\nmore details")
+                        .wrap_err("Error while running individual test"),
+                ),
+            ),
+            report(
+                "time-limit",
+                TestOutcome::TimedOut(eyre::eyre!("Time limit exceeded")),
+            ),
+            report(
+                "skipped",
+                TestOutcome::Skipped("Test was not selected to run".to_string()),
+            ),
+        ];
+
+        let table = format_summary_table(&reports);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("test        status"));
+        assert_eq!(lines[1], "passed      passed     1s 500ms  1");
+        assert!(lines[2].ends_with("failed     1s 500ms  1         contributor dropped"));
+        assert!(lines[3].contains("timed out"));
+        assert!(lines[4].ends_with("Test was not selected to run"));
+    }
+
+    #[test]
+    fn test_write_error_files() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let reports = vec![
+            report("passed", TestOutcome::Passed),
+            report("failed", TestOutcome::Failed(eyre::eyre!("round 1 failed"))),
+        ];
+        write_error_files(out_dir.path(), &reports).unwrap();
+
+        assert!(!out_dir.path().join("passed").join(ERROR_FILE_NAME).exists());
+        let error =
+            std::fs::read_to_string(out_dir.path().join("failed").join(ERROR_FILE_NAME)).unwrap();
+        assert!(error.contains("round 1 failed"));
+    }
+}
@@ -4,18 +4,36 @@
 use crate::{
     ceremony_waiter::spawn_contribution_waiter,
     contributor::{generate_contributor_key, run_contributor, Contributor, ContributorConfig},
-    coordinator::{check_participants_in_round, run_coordinator, CoordinatorConfig},
-    drop_participant::{monitor_drops, DropContributorConfig, MonitorDropsConfig},
+    coordinator::{
+        check_participants_in_round, check_round_state, run_coordinator, CoordinatorConfig,
+        CoordinatorConfigOverrides, CoordinatorMessage, ExpectRoundState,
+        ExpectedRoundParticipants,
+    },
+    coordinator_fault::{restart_coordinator, CoordinatorFaultConfig},
+    drop_participant::{
+        drop_verifier, monitor_drops, DropContributorConfig, DropVerifierConfig, MonitorDropsConfig,
+    },
+    event_recorder::{record_events, EVENTS_FILE_NAME},
+    fault::{FaultTarget, FaultTrigger},
     git::{clone_git_repository, LocalGitRepo, RemoteGitRepo},
     join::{join_multiple, JoinLater, JoinMultiple, MultiJoinable},
-    reporting::LogFileWriter,
+    network_proxy::{inject_network_fault, NetworkFaultConfig, NetworkProxy, NetworkProxyHandle},
+    pause::{pause_participant, PauseConfig, ProcessIds},
+    process::{MonitorProcessMessage, ProcessesConfig},
+    reporting::{LogFileWriter, LOG_FILE_NAME},
+    resources::{monitor_resources, ResourceMonitorHandle, ResourceUsage, RESOURCES_FILE_NAME},
+    round_outcome::{monitor_round_outcome, RoundOutcome},
+    round_timing::{monitor_round_timing, RoundTiming, RoundTimingHandle},
     rust::{build_rust_crate, install_rust_toolchain, RustToolchain},
+    stand_in,
     state_monitor::{run_state_monitor, StateMonitorConfig},
-    time_limit::ceremony_time_limit,
+    time_limit::{ceremony_time_limit, TimeLimitExceeded},
+    transcript::{verify_transcript, TranscriptVerification},
     util::create_dir_if_not_exists,
-    verifier::{generate_verifier_key, run_verifier, Verifier},
+    validate::{validate_stand_in_only, validate_test, validation_result},
+    verifier::{generate_verifier_key, run_verifier, Verifier, VerifierConfig},
     waiter::{MessageWaiter, WaiterJoinCondition},
-    CeremonyMessage, ContributorRef, Environment, ShutdownReason,
+    CeremonyMessage, ContributorRef, Environment, ParticipantRef, ShutdownReason,
 };
 
 use eyre::Context;
@@ -24,11 +42,21 @@ use mpmc_bus::{Bus, Receiver, Sender};
 use serde::{Deserialize, Serialize};
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     net::SocketAddr,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
+/// How long to wait for the verifiers to include their participant id
+/// (and therefore their address) in their log after they have been
+/// started.
+const VERIFIER_ADDRESS_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Code repository to be used during a test.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type")]
@@ -54,7 +82,7 @@ impl Repo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StartAfterRoundContributions {
     /// See [StartAfterContributions].
-    after_round_contributions: u64,
+    pub after_round_contributions: u64,
 }
 
 /// The configuration for when a contributor will be started
@@ -88,6 +116,17 @@ pub struct TestRound {
     #[serde(default)]
     pub contributor_drops: Vec<DropContributorConfig>,
 
+    /// (Optional) Check that the tasks of each contributor dropped
+    /// via [Self::contributor_drops] were assigned to a replacement
+    /// contributor, which then contributed to the chunks that were
+    /// missing. Requires at least as many replacement contributors as
+    /// contributor drops. The assignment is detected from a line in
+    /// the log of the `stand-in-coordinator` which the
+    /// `aleo-setup-coordinator` does not print, so this check is only
+    /// supported with the stand-ins. Default: `false`.
+    #[serde(default)]
+    pub expect_replacements: bool,
+
     /// (Optional) Configure when contributors will start. A
     /// contributor from [Self::contributors] is assigned
     /// automatically to each specified config. The number of configs
@@ -97,6 +136,52 @@ pub struct TestRound {
     /// [].
     #[serde(default)]
     pub contributor_starts: Vec<ContributorStartConfig>,
+
+    /// (Optional) The expected outcome of the round. If
+    /// [RoundOutcome::Restart] then the round is expected to be
+    /// restarted by the coordinator because no contributors remain
+    /// (e.g. due to [Self::contributor_drops]), and the next
+    /// configured round will be run with the same round number.
+    /// Default: [RoundOutcome::Finish].
+    #[serde(default)]
+    pub expect: RoundOutcome,
+
+    /// (Optional) Configure expected verifier drops. A verifier which
+    /// is still running is assigned automatically to each specified
+    /// config. A dropped verifier does not participate in subsequent
+    /// rounds. The number of configs should be less than the number
+    /// of running verifiers. [DropVerifierConfig::AfterVerifications]
+    /// is only supported with the stand-ins (see
+    /// [TestOptions::stand_ins]), because the verifications are
+    /// counted by matching the stand-in coordinator's log output.
+    /// Default: [].
+    #[serde(default)]
+    pub verifier_drops: Vec<DropVerifierConfig>,
+
+    /// (Optional) Expectations for the state of the round in the
+    /// transcript, which are checked after the round has finished.
+    /// If `None` then the state is not checked. Default: `None`.
+    #[serde(default)]
+    pub expect_state: Option<ExpectRoundState>,
+
+    /// (Optional) Faults to inject into the network connections
+    /// between participants and the coordinator during this round.
+    /// Enables [TestOptions::network_proxy]. Default: [].
+    #[serde(default)]
+    pub network_faults: Vec<NetworkFaultConfig>,
+
+    /// (Optional) Restart the coordinator during this round, to test
+    /// that it recovers its state and completes the round with the
+    /// same participants. Default: [].
+    #[serde(default)]
+    pub coordinator_faults: Vec<CoordinatorFaultConfig>,
+
+    /// (Optional) Pause the processes of participants during this
+    /// round, to simulate participants whose computers go to sleep.
+    /// A participant paused for longer than the coordinator's
+    /// timeouts is expected to be dropped. Default: [].
+    #[serde(default)]
+    pub pauses: Vec<PauseConfig>,
 }
 
 impl Default for TestRound {
@@ -104,13 +189,20 @@ impl Default for TestRound {
         Self {
             contributors: 1,
             contributor_drops: Default::default(),
+            expect_replacements: Default::default(),
             contributor_starts: Default::default(),
+            expect: Default::default(),
+            verifier_drops: Default::default(),
+            expect_state: Default::default(),
+            network_faults: Default::default(),
+            coordinator_faults: Default::default(),
+            pauses: Default::default(),
         }
     }
 }
 
 /// Command line options for running the Aleo Setup integration test.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TestOptions {
     /// Remove any artifacts created during a previous integration
     /// test run before starting.
@@ -158,12 +250,95 @@ pub struct TestOptions {
     /// The code repository for the `aleo-setup-coordinator` project.
     pub aleo_setup_coordinator_repo: Repo,
 
+    /// Directory containing the stand-in binaries to use instead of
+    /// the `aleo-setup` and `aleo-setup-coordinator` binaries. See
+    /// [crate::stand_in].
+    pub stand_ins: Option<PathBuf>,
+
+    /// The port that the coordinator will listen on, and that the
+    /// contributors and verifiers will connect to.
+    pub coordinator_port: u16,
+
+    /// Overrides for the generated `aleo-setup-coordinator`
+    /// configuration.
+    pub coordinator: CoordinatorConfigOverrides,
+
+    /// Whether to verify the transcript with the `verify-transcript`
+    /// binary after each round has finished. This is only supported
+    /// with the stand-ins, see [crate::transcript].
+    pub verify_transcript: bool,
+
+    /// Whether to connect the contributors and verifiers to the
+    /// coordinator via a [NetworkProxy]. See
+    /// [TestOptions::network_proxy_enabled()].
+    pub network_proxy: bool,
+
+    /// The interval at which the resource usage of the processes
+    /// started during the test is sampled, see [crate::resources].
+    #[serde(with = "humantime_serde")]
+    pub resource_sample_interval: std::time::Duration,
+
+    /// Extra environment variables, arguments and log filters for the
+    /// processes of the components of the ceremony.
+    pub processes: ProcessesConfig,
+
     /// Configuration for each round of the ceremony that will be tested.
     pub rounds: Vec<TestRound>,
 }
 
+impl TestOptions {
+    /// The binaries of the components to be tested.
+    pub fn binaries(&self) -> ComponentBinaries {
+        match &self.stand_ins {
+            Some(stand_ins_dir) => ComponentBinaries::stand_ins(stand_ins_dir),
+            None => ComponentBinaries::from_repos(
+                self.aleo_setup_coordinator_repo.dir(),
+                self.aleo_setup_repo.dir(),
+            ),
+        }
+    }
+
+    /// URL used by the contributors and verifiers to connect to the
+    /// coordinator.
+    pub fn coordinator_api_url(&self) -> String {
+        format!("http://localhost:{}", self.coordinator_port)
+    }
+
+    /// Whether the contributors and verifiers connect to the
+    /// coordinator via a [NetworkProxy], either because it has been
+    /// enabled with [TestOptions::network_proxy], or because network
+    /// faults have been configured for a round.
+    pub fn network_proxy_enabled(&self) -> bool {
+        self.network_proxy
+            || self
+                .rounds
+                .iter()
+                .any(|round| !round.network_faults.is_empty())
+    }
+
+    /// URL used by the participant with the specified `id` to connect
+    /// to the coordinator. If [TestOptions::network_proxy_enabled()]
+    /// then a [NetworkProxy] is bound for the participant and added
+    /// to `network_proxies`.
+    fn participant_api_url(
+        &self,
+        id: &str,
+        network_proxies: &mut HashMap<String, NetworkProxy>,
+    ) -> eyre::Result<String> {
+        if !self.network_proxy_enabled() {
+            return Ok(self.coordinator_api_url());
+        }
+
+        let upstream = SocketAddr::from(([127, 0, 0, 1], self.coordinator_port));
+        let proxy = NetworkProxy::bind(id, upstream)?;
+        let api_url = proxy.api_url()?;
+        network_proxies.insert(id.to_string(), proxy);
+        Ok(api_url)
+    }
+}
+
 /// Options for running the `aleo-setup-state-monitor`
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct StateMonitorOptions {
     /// The code repository for the `aleo-setup-state-monitor` project.
     pub repo: Repo,
@@ -172,8 +347,10 @@ pub struct StateMonitorOptions {
     pub address: SocketAddr,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoundResults {
+    /// The outcome of the round.
+    pub outcome: RoundOutcome,
     /// The time between the start of the round, and the end of the
     /// round.
     #[serde(with = "humantime_serde")]
@@ -181,23 +358,89 @@ pub struct RoundResults {
     /// The time taken to perform aggregation at the end of a round.
     #[serde(with = "humantime_serde")]
     pub aggregation_duration: std::time::Duration,
+    /// A breakdown of where the time was spent during the round.
+    pub timing: RoundTiming,
+    /// The number of successful contributions made by each
+    /// contributor during the round, indexed by the contributor's
+    /// address.
+    pub contributions: BTreeMap<String, u64>,
+    /// The replacement contributor assigned the tasks of each dropped
+    /// contributor during the round, indexed by the dropped
+    /// contributor's address.
+    pub replacements: BTreeMap<String, String>,
+    /// The results of verifying the transcript at the end of the
+    /// round, if [TestOptions::verify_transcript] is enabled.
+    pub transcript_verification: Option<TranscriptVerification>,
+    /// The CPU, memory and disk usage of each process during the
+    /// round, indexed by the process id (e.g. `coordinator`).
+    pub resources: BTreeMap<String, ResourceUsage>,
+}
+
+/// The port used by the coordinator when tests are not run in
+/// parallel.
+pub const DEFAULT_COORDINATOR_PORT: u16 = 9000;
+
+/// Paths to the binaries of the components being tested.
+#[derive(Debug, Clone)]
+pub struct ComponentBinaries {
+    /// The `aleo-setup-coordinator` binary.
+    pub coordinator: PathBuf,
+    /// The `setup1-contributor` binary.
+    pub contributor: PathBuf,
+    /// The `setup1-verifier` binary.
+    pub verifier: PathBuf,
+    /// The `view-key` binary from `setup1-cli-tools`.
+    pub view_key: PathBuf,
+    /// The binary used to verify the transcript, if there is one for
+    /// these components, see [crate::transcript].
+    pub verify_transcript: Option<PathBuf>,
 }
 
-/// URL used by the contributors and verifiers to connect to the
-/// coordinator.
-const COORDINATOR_API_URL: &str = "http://localhost:9000";
+impl ComponentBinaries {
+    /// Binaries built in the `aleo-setup-coordinator` and
+    /// `aleo-setup` repositories.
+    pub fn from_repos(coordinator_dir: impl AsRef<Path>, setup_dir: impl AsRef<Path>) -> Self {
+        let setup_build_output_dir = setup_dir.as_ref().join("target/release");
+        Self {
+            coordinator: coordinator_dir
+                .as_ref()
+                .join("target/release")
+                .join("aleo-setup-coordinator"),
+            contributor: setup_build_output_dir.join("setup1-contributor"),
+            verifier: setup_build_output_dir.join("setup1-verifier"),
+            view_key: setup_build_output_dir.join("view-key"),
+            verify_transcript: None,
+        }
+    }
+
+    /// Stand-in binaries located in `stand_ins_dir`, see
+    /// [crate::stand_in].
+    pub fn stand_ins(stand_ins_dir: impl AsRef<Path>) -> Self {
+        let dir = stand_ins_dir.as_ref();
+        Self {
+            coordinator: dir.join(stand_in::COORDINATOR_BIN),
+            contributor: dir.join(stand_in::CONTRIBUTOR_BIN),
+            verifier: dir.join(stand_in::VERIFIER_BIN),
+            view_key: dir.join(stand_in::VIEW_KEY_BIN),
+            verify_transcript: Some(dir.join(stand_in::VERIFY_TRANSCRIPT_BIN)),
+        }
+    }
+}
 
 /// Clone the git repos for `aleo-setup` and `aleo-setup-coordinator`.
 pub fn clone_git_repos(options: &TestOptions) -> eyre::Result<()> {
-    tracing::info!("Cloning aleo-setup-coordinator git repository.");
-    if let Repo::Remote(repo) = &options.aleo_setup_coordinator_repo {
-        clone_git_repository(repo)
-            .wrap_err("Error while cloning `aleo-setup-coordinator` git repository.")?;
-    }
+    if options.stand_ins.is_none() {
+        tracing::info!("Cloning aleo-setup-coordinator git repository.");
+        if let Repo::Remote(repo) = &options.aleo_setup_coordinator_repo {
+            clone_git_repository(repo)
+                .wrap_err("Error while cloning `aleo-setup-coordinator` git repository.")?;
+        }
 
-    tracing::info!("Cloning aleo-setup git repository.");
-    if let Repo::Remote(repo) = &options.aleo_setup_repo {
-        clone_git_repository(repo).wrap_err("Error while cloning `aleo-setup` git repository.")?;
+        tracing::info!("Cloning aleo-setup git repository.");
+        if let Repo::Remote(repo) = &options.aleo_setup_repo {
+            clone_git_repository(repo)
+                .wrap_err("Error while cloning `aleo-setup` git repository.")?;
+        }
     }
 
     if let Some(state_monitor_options) = options.state_monitor.as_ref() {
@@ -211,9 +454,11 @@ pub fn clone_git_repos(options: &TestOptions) -> eyre::Result<()> {
     Ok(())
 }
 
-#[derive(Serialize)]
+/// The results of an individual test.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestResults {
-    round_results: Vec<RoundResults>,
+    /// The results of each round, in the order they were tested.
+    pub round_results: Vec<RoundResults>,
 }
 
 fn state_monitor_bin_path(repo_dir: impl AsRef<Path>) -> PathBuf {
@@ -223,15 +468,116 @@ fn state_monitor_bin_path(repo_dir: impl AsRef<Path>) -> PathBuf {
         .join("aleo-setup-state-monitor")
 }
 
-// TODO: add some kind of check that all specified rounds completed successfully.
+/// Clone the repositories, install the prerequisites and build the
+/// components required to run the test described by `options`.
+pub fn prepare_components(options: &TestOptions) -> eyre::Result<()> {
+    let rust_stable = RustToolchain::Stable;
+
+    if let Some(stand_ins_dir) = &options.stand_ins {
+        tracing::info!("Using stand-in binaries from {:?}.", stand_ins_dir);
+    }
+
+    // Attempt to clone the git repos if they don't already exist.
+    clone_git_repos(options)?;
+
+    let coordinator_dir = options.aleo_setup_coordinator_repo.dir();
+    let setup_dir = options.aleo_setup_repo.dir();
+
+    if options.install_prerequisites {
+        // Install a specific version of the rust toolchain needed to be
+        // able to compile `aleo-setup`.
+        install_rust_toolchain(&rust_stable).wrap_err_with(|| {
+            eyre::eyre!("error while installing rust toolchain {}", rust_stable)
+        })?;
+    }
+
+    if options.build && options.stand_ins.is_none() {
+        // Build the setup coordinator Rust project.
+        build_rust_crate(coordinator_dir, &rust_stable)
+            .wrap_err("error while building aleo-setup-coordinator crate")?;
+
+        // Build the setup1-contributor Rust project.
+        build_rust_crate(setup_dir.join("setup1-contributor"), &rust_stable)
+            .wrap_err("error while building setup1-contributor crate")?;
+
+        // Build the setup1-verifier Rust project.
+        build_rust_crate(setup_dir.join("setup1-verifier"), &rust_stable)
+            .wrap_err("error while building setup1-verifier crate")?;
+
+        // Build the setup1-cli-tools Rust project.
+        build_rust_crate(setup_dir.join("setup1-cli-tools"), &rust_stable)
+            .wrap_err("error while building setup1-verifier crate")?;
+    }
+
+    if options.build {
+        if let Some(state_monitor_options) = &options.state_monitor {
+            // Build the aleo-setup-state-monitor Rust project.
+            build_rust_crate(state_monitor_options.repo.dir(), &RustToolchain::Stable)
+                .wrap_err("error while building aleo-setup-state-monitor server crate")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Run the integration test described by `options`. If `log_writer`
+/// is `Some`, the log output during the test is written to
+/// `integration-test.log` in the test's out directory, otherwise it
+/// is left to the caller to manage.
+///
+/// If the test exceeded its time limit ([TestOptions::timout]), the
+/// error is wrapped with [TimeLimitExceeded], which can be detected
+/// with [eyre::Report::downcast_ref()]. Other errors can be returned
+/// before the time limit's own error as the ceremony is shut down.
 pub fn integration_test(
     options: &TestOptions,
-    log_writer: &LogFileWriter,
+    log_writer: Option<&LogFileWriter>,
 ) -> eyre::Result<TestResults> {
-    log_writer.set_no_out_file();
+    let time_limit_exceeded = Arc::new(AtomicBool::new(false));
+
+    run_integration_test(options, log_writer, time_limit_exceeded.clone()).map_err(|error| {
+        match options.timout {
+            Some(duration)
+                if time_limit_exceeded.load(Ordering::SeqCst)
+                    && error.downcast_ref::<TimeLimitExceeded>().is_none() =>
+            {
+                error.wrap_err(TimeLimitExceeded { duration })
+            }
+            _ => error,
+        }
+    })
+}
+
+/// Run the integration test for [integration_test()].
+/// `time_limit_exceeded` is set if the test exceeds its time limit.
+// TODO: add some kind of check that all specified rounds completed successfully.
+fn run_integration_test(
+    options: &TestOptions,
+    log_writer: Option<&LogFileWriter>,
+    time_limit_exceeded: Arc<AtomicBool>,
+) -> eyre::Result<TestResults> {
+    if let Some(log_writer) = log_writer {
+        log_writer.set_no_out_file();
+    }
 
     tracing::info!("Running integration test with options:\n{:#?}", &options);
 
+    let mut errors = validate_test(
+        None,
+        options.verifiers,
+        options.replacement_contributors,
+        &options.coordinator,
+        &options.processes,
+        &options.rounds,
+    );
+    errors.extend(validate_stand_in_only(
+        None,
+        options.stand_ins.is_some(),
+        options.verify_transcript,
+        &options.rounds,
+    ));
+    validation_result(errors)?;
+
     // Perfom the clean action if required.
     if options.clean {
         tracing::info!("Cleaning integration test.");
@@ -263,7 +609,9 @@ pub fn integration_test(
 
     // Create the log file, and write out the options that were used to run this test.
     create_dir_if_not_exists(&options.out_dir)?;
-    log_writer.set_out_file(&options.out_dir.join("integration-test.log"))?;
+    if let Some(log_writer) = log_writer {
+        log_writer.set_out_file(&options.out_dir.join(LOG_FILE_NAME))?;
+    }
     let test_config_path = options.out_dir.join("test_config.ron");
     std::fs::write(
         test_config_path,
@@ -273,58 +621,16 @@ pub fn integration_test(
     // Directory to store the contributor and verifier keys.
     let keys_dir_path = create_dir_if_not_exists(options.out_dir.join("keys"))?;
 
-    let rust_stable = RustToolchain::Stable;
-
-    // Attempt to clone the git repos if they don't already exist.
-    clone_git_repos(options)?;
+    prepare_components(options)?;
 
     let coordinator_dir = options.aleo_setup_coordinator_repo.dir();
-    let coordinator_bin_path = coordinator_dir
-        .join("target/release")
-        .join("aleo-setup-coordinator");
-
-    let setup_dir = options.aleo_setup_repo.dir();
-
-    if options.install_prerequisites {
-        // Install a specific version of the rust toolchain needed to be
-        // able to compile `aleo-setup`.
-        install_rust_toolchain(&rust_stable).wrap_err_with(|| {
-            eyre::eyre!("error while installing rust toolchain {}", rust_stable)
-        })?;
-    }
-
-    if options.build {
-        // Build the setup coordinator Rust project.
-        build_rust_crate(coordinator_dir, &rust_stable)
-            .wrap_err("error while building aleo-setup-coordinator crate")?;
-
-        // Build the setup1-contributor Rust project.
-        build_rust_crate(setup_dir.join("setup1-contributor"), &rust_stable)
-            .wrap_err("error while building setup1-contributor crate")?;
-
-        // Build the setup1-verifier Rust project.
-        build_rust_crate(setup_dir.join("setup1-verifier"), &rust_stable)
-            .wrap_err("error while building setup1-verifier crate")?;
-
-        // Build the setup1-cli-tools Rust project.
-        build_rust_crate(setup_dir.join("setup1-cli-tools"), &rust_stable)
-            .wrap_err("error while building setup1-verifier crate")?;
-
-        if let Some(state_monitor_options) = &options.state_monitor {
-            // Build the aleo-setup-state-monitor Rust project.
-            build_rust_crate(state_monitor_options.repo.dir(), &RustToolchain::Stable)
-                .wrap_err("error while building aleo-setup-state-monitor server crate")?;
-        }
-    }
+    let binaries = options.binaries();
 
-    // Output directory for setup1-verifier and setup1-contributor
-    // projects.
-    let setup_build_output_dir = setup_dir.join("target/release");
-    let contributor_bin_path = setup_build_output_dir.join("setup1-contributor");
-    let view_key_bin_path = setup_build_output_dir.join("view-key");
+    let contributor_bin_path = &binaries.contributor;
+    let view_key_bin_path = &binaries.view_key;
 
     // Create the verifiers, generate their keys.
-    let verifiers: Vec<Verifier> = (1..=options.verifiers)
+    let mut verifiers: Vec<Verifier> = (1..=options.verifiers)
         .into_iter()
         .map(|i| {
             let id = format!("verifier{}", i);
@@ -332,57 +638,118 @@ pub fn integration_test(
             let _span_guard = span.enter();
 
             let view_key_path = keys_dir_path.join(format!("{}.key", id));
-            generate_verifier_key(&view_key_bin_path, &view_key_path)?;
+            let address = generate_verifier_key(view_key_bin_path, &view_key_path)?;
 
-            Ok(Verifier { id, view_key_path })
+            Ok(Verifier {
+                id,
+                view_key_path,
+                address,
+            })
         })
         .collect::<eyre::Result<Vec<Verifier>>>()?;
 
-    // Construct the configuration for each round.
-    let round_configs: Vec<RoundConfig> = options
-        .rounds
+    // Network proxies for the participants (if enabled), indexed by
+    // participant id. These are run once the ceremony has started.
+    let mut network_proxies: HashMap<String, NetworkProxy> = HashMap::new();
+
+    // URLs used by the verifiers to connect to the coordinator,
+    // indexed by verifier id.
+    let verifier_api_urls: HashMap<String, String> = verifiers
         .iter()
-        .enumerate()
-        .map(|(round_index, round)| {
-            let round_number = (round_index + 1) as u64;
-            let span = tracing::error_span!("round_config", round = round_number);
-            let _span_guard = span.enter();
+        .map(|verifier| {
+            let api_url = options.participant_api_url(&verifier.id, &mut network_proxies)?;
+            Ok((verifier.id.clone(), api_url))
+        })
+        .collect::<eyre::Result<HashMap<String, String>>>()?;
 
-            if round.contributor_starts.len() > round.contributors as usize {
-                return Err(eyre::eyre!(
-                    "Invalid `contributor_starts` for round {}. Its length ({}) \
-                        should not exceed the number of contributors ({}).",
-                    round_number,
-                    round.contributor_starts.len(),
-                    round.contributors,
-                ));
-            }
+    // Create the replacement contributors, generate their keys.
+    let replacement_contributors: Vec<(Contributor, ContributorConfig)> = (1..=options
+        .replacement_contributors)
+        .into_iter()
+        .map(|i| {
+            let id = format!("replacement_contributor{}", i);
+            let contributor_key_file_name = format!("{}-key.json", id);
+            let key_file = keys_dir_path.join(contributor_key_file_name);
 
-            if round.contributor_drops.len() > round.contributors as usize {
-                return Err(eyre::eyre!(
-                    "Invalid `contributor_drops` for round {}. Its length ({}) \
-                        should not exceed the number of contributors ({}).",
-                    round_number,
-                    round.contributor_drops.len(),
-                    round.contributors,
-                ));
-            }
+            let contributor_key = generate_contributor_key(
+                contributor_bin_path,
+                &key_file,
+                options.processes.contributor_passphrase(),
+            )
+            .wrap_err_with(|| format!("Error generating contributor {} key.", id))?;
 
+            let contributor = Contributor {
+                id: id.clone(),
+                key_file,
+                address: contributor_key.address,
+            };
+
+            // Run the `setup1-contributor`.
+            let contributor_out_dir = create_dir_if_not_exists(options.out_dir.join(&id))?;
+            let coordinator_api_url = options.participant_api_url(&id, &mut network_proxies)?;
+            let contributor_config = ContributorConfig {
+                process: options.processes.contributor(&id),
+                id,
+                contributor_ref: contributor.as_contributor_ref(),
+                contributor_bin_path: contributor_bin_path.clone(),
+                key_file_path: contributor.key_file.clone(),
+                passphrase: options.processes.contributor_passphrase().to_string(),
+                environment: options.environment,
+                coordinator_api_url,
+                out_dir: contributor_out_dir,
+                drop: None,
+                start: ContributorStartConfig::CeremonyStart,
+            };
+
+            Ok((contributor, contributor_config))
+        })
+        .collect::<eyre::Result<Vec<(Contributor, ContributorConfig)>>>()?;
+
+    let replacement_contributor_refs: Vec<ContributorRef> = replacement_contributors
+        .iter()
+        .map(|c| c.0.as_contributor_ref())
+        .collect();
+
+    let coordinator_config = CoordinatorConfig {
+        crate_dir: coordinator_dir.to_owned(),
+        setup_coordinator_bin: binaries.coordinator.clone(),
+        environment: options.environment,
+        out_dir: create_dir_if_not_exists(options.out_dir.join("coordinator"))?,
+        port: options.coordinator_port,
+        replacement_contributors: replacement_contributor_refs,
+        overrides: options.coordinator.clone(),
+        process: options.processes.coordinator.clone(),
+    };
+
+    // Create the contributors of each configured round, generate
+    // their keys. This is done before the network proxies are started
+    // because each contributor may have its own proxy. The
+    // contributors of each configured round are identified by the
+    // round's index in `options.rounds` (starting at 1) rather than
+    // its round number, so that a restarted round and the round which
+    // follows it have distinct contributors and keys.
+    let round_contributors: Vec<RoundContributors> = options
+        .rounds
+        .iter()
+        .zip(1..)
+        .map(|(round, round_index): (_, u64)| {
             // Create the contributors, generate their keys.
             let contributors: Vec<Contributor> = (1..=round.contributors)
                 .into_iter()
                 .map(|i| {
-                    let id = format!("contributor{}-{}", round_number, i);
+                    let id = format!("contributor{}-{}", round_index, i);
                     let span = tracing::error_span!("create", contributor = %id);
                     let _span_guard = span.enter();
 
                     let contributor_key_file_name = format!("{}-key.json", id);
                     let key_file = keys_dir_path.join(contributor_key_file_name);
 
-                    let contributor_key =
-                        generate_contributor_key(&contributor_bin_path, &key_file).wrap_err_with(
-                            || format!("Error generating contributor {} key.", id),
-                        )?;
+                    let contributor_key = generate_contributor_key(
+                        contributor_bin_path,
+                        &key_file,
+                        options.processes.contributor_passphrase(),
+                    )
+                    .wrap_err_with(|| format!("Error generating contributor {} key.", id))?;
 
                     Ok(Contributor {
                         id,
@@ -401,9 +768,10 @@ pub fn integration_test(
                         .get(i)
                         .ok_or_else(|| {
                             eyre::eyre!(
-                            "There is no contributor corresponding to the drop config at index {}",
-                            i
-                        )
+                                "There is no contributor corresponding to the drop config at \
+                                    index {}",
+                                i
+                            )
                         })?
                         .as_contributor_ref();
                     Ok((contributor_ref, drop_config.clone()))
@@ -430,25 +798,19 @@ pub fn integration_test(
                         .cloned()
                         .unwrap_or(ContributorStartConfig::RoundStart);
 
-                    if let ContributorStartConfig::CeremonyStart = &start {
-                        return Err(eyre::eyre!(
-                            "Invalid contributor_starts for round {}. {:?} \
-                                is not a valid start config for a normal contributor.",
-                            round_number,
-                            start
-                        ));
-                    }
-
                     Ok(ContributorConfig {
                         id: contributor.id.clone(),
                         contributor_ref: contributor.as_contributor_ref(),
                         contributor_bin_path: contributor_bin_path.clone(),
                         key_file_path: contributor.key_file.clone(),
+                        passphrase: options.processes.contributor_passphrase().to_string(),
                         environment: options.environment,
-                        coordinator_api_url: COORDINATOR_API_URL.to_string(),
+                        coordinator_api_url: options
+                            .participant_api_url(&contributor.id, &mut network_proxies)?,
                         out_dir: contributor_out_dir,
                         drop,
                         start,
+                        process: options.processes.contributor(&contributor.id),
                     })
                 })
                 .zip(contributors.iter())
@@ -458,63 +820,12 @@ pub fn integration_test(
                 })
                 .collect::<eyre::Result<Vec<(Contributor, ContributorConfig)>>>()?;
 
-            Ok(RoundConfig {
-                round_number,
+            Ok(RoundContributors {
                 contributors,
                 contributor_drops,
-                verifiers: verifiers.clone(),
             })
         })
-        .collect::<eyre::Result<Vec<RoundConfig>>>()?;
-
-    // Create the replacement contributors, generate their keys.
-    let replacement_contributors: Vec<(Contributor, ContributorConfig)> = (1..=options
-        .replacement_contributors)
-        .into_iter()
-        .map(|i| {
-            let id = format!("replacement_contributor{}", i);
-            let contributor_key_file_name = format!("{}-key.json", id);
-            let key_file = keys_dir_path.join(contributor_key_file_name);
-
-            let contributor_key = generate_contributor_key(&contributor_bin_path, &key_file)
-                .wrap_err_with(|| format!("Error generating contributor {} key.", id))?;
-
-            let contributor = Contributor {
-                id: id.clone(),
-                key_file,
-                address: contributor_key.address,
-            };
-
-            // Run the `setup1-contributor`.
-            let contributor_out_dir = create_dir_if_not_exists(options.out_dir.join(&id))?;
-            let contributor_config = ContributorConfig {
-                id,
-                contributor_ref: contributor.as_contributor_ref(),
-                contributor_bin_path: contributor_bin_path.clone(),
-                key_file_path: contributor.key_file.clone(),
-                environment: options.environment,
-                coordinator_api_url: COORDINATOR_API_URL.to_string(),
-                out_dir: contributor_out_dir,
-                drop: None,
-                start: ContributorStartConfig::CeremonyStart,
-            };
-
-            Ok((contributor, contributor_config))
-        })
-        .collect::<eyre::Result<Vec<(Contributor, ContributorConfig)>>>()?;
-
-    let replacement_contributor_refs: Vec<ContributorRef> = replacement_contributors
-        .iter()
-        .map(|c| c.0.as_contributor_ref())
-        .collect();
-
-    let coordinator_config = CoordinatorConfig {
-        crate_dir: coordinator_dir.to_owned(),
-        setup_coordinator_bin: coordinator_bin_path,
-        environment: options.environment,
-        out_dir: create_dir_if_not_exists(options.out_dir.join("coordinator"))?,
-        replacement_contributors: replacement_contributor_refs,
-    };
+        .collect::<eyre::Result<Vec<RoundContributors>>>()?;
 
     // Create some mpmc channels for communicating between the various
     // components that run during the integration test.
@@ -524,9 +835,40 @@ pub fn integration_test(
 
     let mut process_joins: Vec<Box<dyn MultiJoinable>> = Vec::new();
 
-    let time_limit_join = options
-        .timout
-        .map(|timeout| ceremony_time_limit(timeout, ceremony_rx.clone(), ceremony_tx.clone()));
+    // Start the network proxies between the participants and the
+    // coordinator.
+    let network_proxy_handles: HashMap<String, NetworkProxyHandle> = network_proxies
+        .into_iter()
+        .map(|(id, proxy)| {
+            let handle = proxy.handle();
+            process_joins.push(Box::new(proxy.run(ceremony_rx.clone())));
+            (id, handle)
+        })
+        .collect();
+
+    // Record the messages sent during the ceremony to a timeline.
+    let event_recorder_join =
+        record_events(options.out_dir.join(EVENTS_FILE_NAME), ceremony_rx.clone())?;
+
+    // Sample the resource usage of the processes started during the
+    // test.
+    let (resource_monitor, resource_monitor_join) = monitor_resources(
+        options.resource_sample_interval,
+        options.out_dir.join(RESOURCES_FILE_NAME),
+        ceremony_rx.clone(),
+    )?;
+
+    // Record the timing of the events during each round.
+    let (round_timing, round_timing_join) = monitor_round_timing(ceremony_rx.clone());
+
+    let time_limit_join = options.timout.map(|timeout| {
+        ceremony_time_limit(
+            timeout,
+            time_limit_exceeded,
+            ceremony_rx.clone(),
+            ceremony_tx.clone(),
+        )
+    });
 
     // Construct MessageWaiters which wait for specific messages
     // during the ceremony before joining.
@@ -537,7 +879,7 @@ pub fn integration_test(
     );
 
     // Run the coordinator.
-    let coordinator_join = run_coordinator(
+    let (coordinator_join, coordinator_tx) = run_coordinator(
         &coordinator_config,
         ceremony_tx.clone(),
         ceremony_rx.clone(),
@@ -551,6 +893,7 @@ pub fn integration_test(
             transcript_dir: coordinator_config.transcript_dir(),
             out_dir: options.out_dir.clone(),
             address: state_monitor_options.address,
+            process: options.processes.state_monitor.clone(),
         };
 
         let state_monitor_join = run_state_monitor(
@@ -575,39 +918,206 @@ pub fn integration_test(
         );
     }
 
-    for (_, contributor_config) in replacement_contributors {
-        let contributor_join =
-            run_contributor(contributor_config, ceremony_tx.clone(), ceremony_rx.clone())?;
+    // The ids of the participant processes, used to pause them.
+    let process_ids: ProcessIds = ProcessIds::default();
+
+    for (_, contributor_config) in &replacement_contributors {
+        let id = contributor_config.id.clone();
+        let contributor_join = run_contributor(
+            contributor_config.clone(),
+            ceremony_tx.clone(),
+            ceremony_rx.clone(),
+        )?;
+        register_process_id(&process_ids, id, contributor_join.pid());
         process_joins.push(Box::new(contributor_join));
     }
 
+    // Senders used to terminate the verifier processes, indexed by
+    // verifier id.
+    let mut verifier_monitors: HashMap<String, Sender<MonitorProcessMessage>> = HashMap::new();
+    // Receivers for the addresses of the verifiers, in the same order
+    // as `verifiers`.
+    let mut verifier_address_rxs = Vec::new();
+
     for verifier in &verifiers {
         // Run the `setup1-verifier`.
-        let verifier_bin_path = setup_build_output_dir.join("setup1-verifier");
-        let verifier_out_dir = create_dir_if_not_exists(options.out_dir.join(&verifier.id))?;
-        let verifier_join = run_verifier(
-            &verifier.id,
-            verifier_bin_path,
-            COORDINATOR_API_URL,
-            &verifier.view_key_path,
-            ceremony_tx.clone(),
-            ceremony_rx.clone(),
-            verifier_out_dir,
-        )?;
+        let verifier_config = VerifierConfig {
+            id: verifier.id.clone(),
+            verifier_bin_path: binaries.verifier.clone(),
+            coordinator_api_url: verifier_api_urls[&verifier.id].clone(),
+            view_key_path: verifier.view_key_path.clone(),
+            out_dir: create_dir_if_not_exists(options.out_dir.join(&verifier.id))?,
+            process: options.processes.verifier(&verifier.id),
+        };
+        let (verifier_join, verifier_monitor_tx, verifier_address_rx) =
+            run_verifier(verifier_config, ceremony_tx.clone(), ceremony_rx.clone())?;
+        register_process_id(&process_ids, verifier.id.clone(), verifier_join.pid());
         process_joins.push(Box::new(verifier_join));
+        verifier_monitors.insert(verifier.id.clone(), verifier_monitor_tx);
+        verifier_address_rxs.push(verifier_address_rx);
     }
 
+    // If the addresses of the verifiers were not output when their
+    // keys were generated, and they are required to drop verifiers,
+    // wait for the verifiers to report their addresses in their logs.
+    let verifier_addresses_required = options
+        .rounds
+        .iter()
+        .any(|round| !round.verifier_drops.is_empty());
+    let verifier_address_deadline = Instant::now() + VERIFIER_ADDRESS_TIMEOUT;
+    for (verifier, address_rx) in verifiers.iter_mut().zip(verifier_address_rxs) {
+        if verifier.address.is_some() || !verifier_addresses_required {
+            continue;
+        }
+        let timeout = verifier_address_deadline.saturating_duration_since(Instant::now());
+        match address_rx.recv_timeout(timeout) {
+            Ok(address) => verifier.address = Some(address),
+            Err(_) => tracing::warn!(
+                "The address of verifier {} is unknown, it did not appear in the verifier's \
+                    log within {}.",
+                verifier.id,
+                format_duration(VERIFIER_ADDRESS_TIMEOUT)
+            ),
+        }
+    }
+
+    // The timeouts after which the coordinator drops participants,
+    // used to determine whether a paused participant will be dropped.
+    let (contributor_seen_timeout, participant_lock_timeout) =
+        coordinator_config.participant_timeouts();
+
+    // Verifiers which have not been dropped during a previous round.
+    let mut running_verifiers: Vec<Verifier> = verifiers.clone();
+
+    // The number of the next round of the ceremony. A round which is
+    // expected to restart is followed by a round with the same
+    // number.
+    let mut next_round_number: u64 = 1;
+
+    // Construct the configuration for each round.
+    let round_configs: Vec<RoundConfig> = options
+        .rounds
+        .iter()
+        .zip(round_contributors)
+        .map(|(round, round_contributors)| {
+            let RoundContributors {
+                contributors,
+                contributor_drops,
+            } = round_contributors;
+            let round_number = next_round_number;
+            if round.expect == RoundOutcome::Finish {
+                next_round_number += 1;
+            }
+            let span = tracing::error_span!("round_config", round = round_number);
+            let _span_guard = span.enter();
+
+            // The verifiers participating in this round, and the drop
+            // configuration for those which will be dropped during
+            // the round.
+            let round_verifiers = running_verifiers.clone();
+            let verifier_drops: Vec<(Verifier, DropVerifierConfig)> = round
+                .verifier_drops
+                .iter()
+                .zip(running_verifiers.drain(..round.verifier_drops.len()))
+                .map(|(drop_config, verifier)| {
+                    if verifier.address.is_none() {
+                        return Err(eyre::eyre!(
+                            "Unable to drop verifier {} in round {} because its \
+                                address is unknown.",
+                            verifier.id,
+                            round_number
+                        ));
+                    }
+                    Ok((verifier, drop_config.clone()))
+                })
+                .collect::<eyre::Result<Vec<(Verifier, DropVerifierConfig)>>>()?;
+
+            // Find the participants targeted by the network faults.
+            let network_faults = round
+                .network_faults
+                .iter()
+                .map(|fault_config| {
+                    let (id, participant) = find_fault_target(
+                        round_number,
+                        "network_faults",
+                        &fault_config.target,
+                        &fault_config.trigger,
+                        &contributors,
+                        &replacement_contributors,
+                        &verifiers,
+                    )?;
+                    Ok((id, participant, fault_config.clone()))
+                })
+                .collect::<eyre::Result<Vec<(String, Option<ParticipantRef>, NetworkFaultConfig)>>>(
+                )?;
+
+            // Find the participants targeted by the pauses, and those
+            // which are expected to be dropped because of them.
+            let mut pause_drops: HashSet<ParticipantRef> = HashSet::new();
+            let pauses = round
+                .pauses
+                .iter()
+                .map(|pause_config| {
+                    let (id, participant) = find_fault_target(
+                        round_number,
+                        "pauses",
+                        &pause_config.target,
+                        &pause_config.trigger,
+                        &contributors,
+                        &replacement_contributors,
+                        &verifiers,
+                    )?;
+
+                    let expect_drop = pause_config
+                        .expect_drop(contributor_seen_timeout, participant_lock_timeout)
+                        .wrap_err_with(|| format!("Invalid `pauses` for round {}", round_number))?;
+                    if expect_drop {
+                        let participant = participant.clone().ok_or_else(|| {
+                            eyre::eyre!(
+                                "Invalid `pauses` for round {}. Unable to expect {} to be \
+                                    dropped because its address is unknown.",
+                                round_number,
+                                id
+                            )
+                        })?;
+                        pause_drops.insert(participant);
+                    }
+
+                    Ok((id, participant, pause_config.clone()))
+                })
+                .collect::<eyre::Result<Vec<(String, Option<ParticipantRef>, PauseConfig)>>>()?;
+
+            Ok(RoundConfig {
+                round_number,
+                contributors,
+                contributor_drops,
+                expect_replacements: round.expect_replacements,
+                verifiers: round_verifiers,
+                verifier_drops,
+                expect: round.expect,
+                expect_state: round.expect_state.clone(),
+                network_faults,
+                coordinator_faults: round.coordinator_faults.clone(),
+                pauses,
+                pause_drops,
+            })
+        })
+        .collect::<eyre::Result<Vec<RoundConfig>>>()?;
+
+    let round_context = RoundContext {
+        coordinator_config: &coordinator_config,
+        options,
+        coordinator_tx: &coordinator_tx,
+        verifier_monitors: &verifier_monitors,
+        network_proxies: &network_proxy_handles,
+        process_ids: &process_ids,
+        resource_monitor: &resource_monitor,
+        round_timing: &round_timing,
+    };
+
     let round_results = round_configs
         .into_iter()
-        .map(|round_config| {
-            test_round(
-                round_config,
-                &coordinator_config,
-                options,
-                &ceremony_tx,
-                &ceremony_rx,
-            )
-        })
+        .map(|round_config| test_round(round_config, &round_context, &ceremony_tx, &ceremony_rx))
         .collect::<eyre::Result<Vec<RoundResults>>>()?;
 
     // Tell the other threads to shutdown, safely terminating their
@@ -617,6 +1127,21 @@ pub fn integration_test(
     // Wait for threads to close after being told to shut down.
     join_multiple(process_joins).expect("Error while joining monitor threads.");
 
+    tracing::debug!("Waiting for event recorder to join");
+    event_recorder_join
+        .join()
+        .expect("error while joining event recorder thread")?;
+
+    tracing::debug!("Waiting for round timing monitor to join");
+    round_timing_join
+        .join()
+        .expect("error while joining round timing thread")?;
+
+    tracing::debug!("Waiting for resource monitor to join");
+    resource_monitor_join
+        .join()
+        .expect("error while joining resource monitor thread")?;
+
     if let Some(handle) = time_limit_join {
         tracing::debug!("Waiting for time limit to join");
         if let Err(error) = handle
@@ -631,6 +1156,88 @@ pub fn integration_test(
     Ok(TestResults { round_results })
 }
 
+/// Record the id of a participant's process (if it is known) in
+/// `process_ids`, so that it can be paused.
+fn register_process_id(process_ids: &ProcessIds, id: String, pid: Option<u32>) {
+    match pid {
+        Some(pid) => {
+            process_ids
+                .lock()
+                .expect("error obtaining lock")
+                .insert(id, pid);
+        }
+        None => tracing::warn!("The process id of {} is unknown.", id),
+    }
+}
+
+/// Find the participant targeted by a fault configured in the
+/// `field` of a round. Returns the id of the participant, and a
+/// reference to the participant if its address is known (which is
+/// required to count its contributions for
+/// [FaultTrigger::AfterContributions]).
+fn find_fault_target(
+    round_number: u64,
+    field: &str,
+    target: &FaultTarget,
+    trigger: &FaultTrigger,
+    contributors: &[(Contributor, ContributorConfig)],
+    replacement_contributors: &[(Contributor, ContributorConfig)],
+    verifiers: &[Verifier],
+) -> eyre::Result<(String, Option<ParticipantRef>)> {
+    let index = |i: u8| (i as usize).checked_sub(1);
+    let contributor_target = |(contributor, _): &(Contributor, ContributorConfig)| {
+        let contributor_ref = contributor.as_contributor_ref();
+        (
+            contributor.id.clone(),
+            Some(ParticipantRef::Contributor(contributor_ref)),
+        )
+    };
+    let found = match target {
+        FaultTarget::Contributor(i) => index(*i)
+            .and_then(|i| contributors.get(i))
+            .map(contributor_target),
+        FaultTarget::ReplacementContributor(i) => index(*i)
+            .and_then(|i| replacement_contributors.get(i))
+            .map(contributor_target),
+        FaultTarget::Verifier(i) => index(*i).and_then(|i| verifiers.get(i)).map(|verifier| {
+            (
+                verifier.id.clone(),
+                verifier.as_verifier_ref().map(ParticipantRef::Verifier),
+            )
+        }),
+    };
+
+    match found {
+        None => Err(eyre::eyre!(
+            "Invalid `{}` for round {}. There is no participant \
+                corresponding to the target {:?}.",
+            field,
+            round_number,
+            target
+        )),
+        Some((id, None)) if matches!(trigger, FaultTrigger::AfterContributions(_)) => {
+            Err(eyre::eyre!(
+                "Invalid `{}` for round {}. Unable to count the \
+                    contributions of {} because its address is unknown.",
+                field,
+                round_number,
+                id
+            ))
+        }
+        Some(found) => Ok(found),
+    }
+}
+
+/// The contributors created for a configured round, before the
+/// [RoundConfig] is constructed.
+struct RoundContributors {
+    /// The contributors and their configurations.
+    contributors: Vec<(Contributor, ContributorConfig)>,
+    /// The drop configuration of the contributors which are to be
+    /// dropped during the round.
+    contributor_drops: HashMap<ContributorRef, DropContributorConfig>,
+}
+
 /// Configuration for running a round of the ceremony.
 pub struct RoundConfig {
     /// The number of the round in the ceremony.
@@ -642,9 +1249,55 @@ pub struct RoundConfig {
     /// configuration (if the contributor needs to be dropped during
     /// this round).
     contributor_drops: HashMap<ContributorRef, DropContributorConfig>,
+    /// Whether to check that the dropped contributors were replaced
+    /// by replacement contributors.
+    expect_replacements: bool,
     /// A vector of verifiers participating in this round. It is
     /// expected that the specified verifiers are already running.
     verifiers: Vec<Verifier>,
+    /// Verifiers (from [RoundConfig::verifiers]) which are to be
+    /// dropped during this round, and the relevant drop
+    /// configuration.
+    verifier_drops: Vec<(Verifier, DropVerifierConfig)>,
+    /// The expected outcome of the round.
+    expect: RoundOutcome,
+    /// Expectations for the state of the round in the transcript,
+    /// checked after the round has finished.
+    expect_state: Option<ExpectRoundState>,
+    /// Faults to inject into the network connections of participants
+    /// during this round, along with the id of the participant (used
+    /// to find its [NetworkProxy]), and a reference to the
+    /// participant if its address is known.
+    network_faults: Vec<(String, Option<ParticipantRef>, NetworkFaultConfig)>,
+    /// Restarts of the coordinator during this round.
+    coordinator_faults: Vec<CoordinatorFaultConfig>,
+    /// Pauses of the processes of participants during this round,
+    /// along with the id of the participant (used to find its process
+    /// in [ProcessIds]), and a reference to the participant if its
+    /// address is known.
+    pauses: Vec<(String, Option<ParticipantRef>, PauseConfig)>,
+    /// Participants which are expected to be dropped by the
+    /// coordinator during this round because they were paused for
+    /// longer than the coordinator's timeouts.
+    pause_drops: HashSet<ParticipantRef>,
+}
+
+/// The configuration and handles of the running test which are
+/// shared by each round run with [test_round()].
+struct RoundContext<'a> {
+    coordinator_config: &'a CoordinatorConfig,
+    options: &'a TestOptions,
+    /// Used to send messages to the coordinator process (e.g. to
+    /// restart it).
+    coordinator_tx: &'a Sender<CoordinatorMessage>,
+    /// Used to send messages to the monitors of the verifier
+    /// processes, by verifier id.
+    verifier_monitors: &'a HashMap<String, Sender<MonitorProcessMessage>>,
+    /// The network proxies of the participants, by participant id.
+    network_proxies: &'a HashMap<String, NetworkProxyHandle>,
+    process_ids: &'a ProcessIds,
+    resource_monitor: &'a ResourceMonitorHandle,
+    round_timing: &'a RoundTimingHandle,
 }
 
 /// Test an individual round of the ceremony. It is expected that the
@@ -652,22 +1305,116 @@ pub struct RoundConfig {
 /// running before this function is called.
 fn test_round(
     round_config: RoundConfig,
-    coordinator_config: &CoordinatorConfig,
-    options: &TestOptions,
+    context: &RoundContext,
     ceremony_tx: &Sender<CeremonyMessage>,
     ceremony_rx: &Receiver<CeremonyMessage>,
 ) -> eyre::Result<RoundResults> {
+    let RoundContext {
+        coordinator_config,
+        options,
+        coordinator_tx,
+        verifier_monitors,
+        network_proxies,
+        process_ids,
+        resource_monitor,
+        round_timing,
+    } = *context;
     let span = tracing::error_span!("test_round", round = round_config.round_number);
     let _span_guard = span.enter();
 
     let mut process_joins: Vec<Box<dyn MultiJoinable>> = Vec::new();
 
+    resource_monitor.start_round(round_config.round_number);
+
     // Monitor the ceremony for dropped participants
     let drops_config = MonitorDropsConfig {
         contributor_drops: round_config.contributor_drops.clone(),
+        verifier_drops: round_config
+            .verifier_drops
+            .iter()
+            .filter_map(|(verifier, drop_config)| {
+                verifier
+                    .as_verifier_ref()
+                    .map(|verifier_ref| (verifier_ref, drop_config.clone()))
+            })
+            .collect(),
+        expect_replacements: round_config.expect_replacements,
+        pause_drops: round_config.pause_drops.clone(),
     };
     let monitor_drops_join = monitor_drops(drops_config, ceremony_rx.clone(), ceremony_tx.clone());
 
+    // Drop the verifiers configured to be dropped during this round.
+    let verifier_drop_joins = round_config
+        .verifier_drops
+        .iter()
+        .map(|(verifier, drop_config)| {
+            let verifier_ref = verifier
+                .as_verifier_ref()
+                .ok_or_else(|| eyre::eyre!("Address for verifier {} is unknown", verifier.id))?;
+            let verifier_monitor_tx = verifier_monitors
+                .get(&verifier.id)
+                .ok_or_else(|| eyre::eyre!("Verifier {} is not running", verifier.id))?
+                .clone();
+            Ok(drop_verifier(
+                round_config.round_number,
+                verifier_ref,
+                drop_config.clone(),
+                verifier_monitor_tx,
+                ceremony_rx.clone(),
+            ))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    // Inject the network faults configured for this round.
+    let network_fault_joins = round_config
+        .network_faults
+        .iter()
+        .map(|(id, participant, fault_config)| {
+            let proxy = network_proxies
+                .get(id)
+                .ok_or_else(|| eyre::eyre!("There is no network proxy for {}", id))?
+                .clone();
+            Ok(inject_network_fault(
+                round_config.round_number,
+                fault_config.clone(),
+                participant.clone(),
+                proxy,
+                ceremony_rx.clone(),
+            ))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    // Restart the coordinator as configured for this round.
+    let coordinator_fault_joins: Vec<_> = round_config
+        .coordinator_faults
+        .iter()
+        .map(|fault_config| {
+            restart_coordinator(
+                round_config.round_number,
+                fault_config.clone(),
+                coordinator_config.clone(),
+                coordinator_tx.clone(),
+                ceremony_rx.clone(),
+            )
+        })
+        .collect();
+
+    // Pause the participants as configured for this round.
+    let pause_joins: Vec<_> = round_config
+        .pauses
+        .iter()
+        .map(|(id, participant, pause_config)| {
+            pause_participant(
+                round_config.round_number,
+                pause_config.clone(),
+                id.clone(),
+                participant.clone(),
+                process_ids.clone(),
+                ceremony_rx.clone(),
+            )
+        })
+        .collect();
+
     // Construct MessageWaiters which wait for specific messages
     // during the ceremony before joining.
     let round_started = MessageWaiter::spawn_expected(
@@ -675,22 +1422,39 @@ fn test_round(
         || Ok(()),
         ceremony_rx.clone(),
     );
-    let round_aggregation_started = MessageWaiter::spawn_expected(
-        vec![CeremonyMessage::RoundStartedAggregation(
-            round_config.round_number,
-        )],
-        || Ok(()),
-        ceremony_rx.clone(),
-    );
-    let round_finished = MessageWaiter::spawn_expected(
-        vec![CeremonyMessage::RoundFinished(round_config.round_number)],
+    // Aggregation only takes place for rounds which are expected to
+    // finish.
+    let round_aggregation = match round_config.expect {
+        RoundOutcome::Finish => {
+            let round_aggregation_started = MessageWaiter::spawn_expected(
+                vec![CeremonyMessage::RoundStartedAggregation(
+                    round_config.round_number,
+                )],
+                || Ok(()),
+                ceremony_rx.clone(),
+            );
+            let round_aggregated = MessageWaiter::spawn_expected(
+                vec![CeremonyMessage::RoundAggregated(round_config.round_number)],
+                || Ok(()),
+                ceremony_rx.clone(),
+            );
+            Some((round_aggregation_started, round_aggregated))
+        }
+        RoundOutcome::Restart => None,
+    };
+    let round_ended = MessageWaiter::spawn_expected(
+        vec![match round_config.expect {
+            RoundOutcome::Finish => CeremonyMessage::RoundFinished(round_config.round_number),
+            RoundOutcome::Restart => CeremonyMessage::RoundRestarted(round_config.round_number),
+        }],
         || Ok(()),
         ceremony_rx.clone(),
     );
-    let round_aggregated = MessageWaiter::spawn_expected(
-        vec![CeremonyMessage::RoundAggregated(round_config.round_number)],
-        || Ok(()),
+    let round_outcome_join = monitor_round_outcome(
+        round_config.round_number,
+        round_config.expect,
         ceremony_rx.clone(),
+        ceremony_tx.clone(),
     );
 
     // Run the contributors which are to be present at the start of
@@ -707,6 +1471,7 @@ fn test_round(
                 ceremony_tx.clone(),
                 ceremony_rx.clone(),
             )?;
+            register_process_id(process_ids, contributor.id.clone(), contributor_join.pid());
             process_joins.push(Box::new(contributor_join));
             Ok(contributor.clone())
         })
@@ -725,14 +1490,17 @@ fn test_round(
                     let waiter_ceremony_tx = ceremony_tx.clone();
                     let waiter_ceremony_rx = ceremony_rx.clone();
                     let this_contributor_config = contributor_config.clone();
+                    let waiter_process_ids = process_ids.clone();
                     let waiter_join: Box<dyn MultiJoinable> = Box::new(spawn_contribution_waiter(
                         start_config.after_round_contributions,
                         move || {
+                            let id = this_contributor_config.id.clone();
                             let contributor_join = run_contributor(
                                 this_contributor_config,
                                 waiter_ceremony_tx,
                                 waiter_ceremony_rx,
                             )?;
+                            register_process_id(&waiter_process_ids, id, contributor_join.pid());
                             waiter_process_join.register(contributor_join);
                             Ok(())
                         },
@@ -774,40 +1542,48 @@ fn test_round(
         }
     }
 
-    round_aggregation_started
-        .join()
-        .wrap_err("Error while waiting for round aggregation to start")?
-        .on_messages_received(|| {
-            tracing::info!(
-                "Round contributions and verifications complete. Aggregation has started."
-            )
-        });
-
-    let aggregation_start_time = std::time::Instant::now();
-
-    let aggregation_duration = match round_aggregated
-        .join()
-        .wrap_err("Error while waiting for round to aggregate.")?
-    {
-        WaiterJoinCondition::Shutdown => None,
-        WaiterJoinCondition::MessagesReceived => {
-            tracing::info!("Round aggregated.");
-            let aggregation_duration = aggregation_start_time.elapsed();
-            tracing::info!(
-                "Aggregation time: {}",
-                format_duration(aggregation_duration)
-            );
-            Some(aggregation_duration)
+    let aggregation_duration = match round_aggregation {
+        Some((round_aggregation_started, round_aggregated)) => {
+            round_aggregation_started
+                .join()
+                .wrap_err("Error while waiting for round aggregation to start")?
+                .on_messages_received(|| {
+                    tracing::info!(
+                        "Round contributions and verifications complete. Aggregation has started."
+                    )
+                });
+
+            let aggregation_start_time = std::time::Instant::now();
+
+            match round_aggregated
+                .join()
+                .wrap_err("Error while waiting for round to aggregate.")?
+            {
+                WaiterJoinCondition::Shutdown => None,
+                WaiterJoinCondition::MessagesReceived => {
+                    tracing::info!("Round aggregated.");
+                    let aggregation_duration = aggregation_start_time.elapsed();
+                    tracing::info!(
+                        "Aggregation time: {}",
+                        format_duration(aggregation_duration)
+                    );
+                    Some(aggregation_duration)
+                }
+            }
         }
+        None => None,
     };
 
-    let total_round_duration = match round_finished
+    let total_round_duration = match round_ended
         .join()
-        .wrap_err("Error while waiting for round to finish.")?
+        .wrap_err("Error while waiting for round to end.")?
     {
         WaiterJoinCondition::Shutdown => None,
         WaiterJoinCondition::MessagesReceived => {
-            tracing::info!("Round finished.");
+            match round_config.expect {
+                RoundOutcome::Finish => tracing::info!("Round finished."),
+                RoundOutcome::Restart => tracing::info!("Round restarted (as expected)."),
+            }
             let total_round_duration = round_start_time.elapsed();
             tracing::info!(
                 "Total round time: {}",
@@ -817,13 +1593,129 @@ fn test_round(
         }
     };
 
+    // Check the state of the round in the transcript (expect_state
+    // is only permitted for rounds which are expected to finish).
+    if let (Some(expect_state), Some(_)) = (&round_config.expect_state, total_round_duration) {
+        let remaining_contributors: Vec<Contributor> = starting_contributors
+            .iter()
+            .filter(|contributor| {
+                let contributor_ref = contributor.as_contributor_ref();
+                !round_config
+                    .contributor_drops
+                    .contains_key(&contributor_ref)
+                    && !round_config
+                        .pause_drops
+                        .contains(&ParticipantRef::Contributor(contributor_ref))
+            })
+            .cloned()
+            .collect();
+        let remaining_verifiers: Vec<Verifier> = round_config
+            .verifiers
+            .iter()
+            .filter(|verifier| {
+                !round_config
+                    .verifier_drops
+                    .iter()
+                    .any(|(dropped, _)| dropped.id == verifier.id)
+                    && !verifier.as_verifier_ref().map_or(false, |verifier_ref| {
+                        round_config
+                            .pause_drops
+                            .contains(&ParticipantRef::Verifier(verifier_ref))
+                    })
+            })
+            .cloned()
+            .collect();
+        let participants = ExpectedRoundParticipants {
+            contributors: &remaining_contributors,
+            verifiers: &remaining_verifiers,
+            contributors_dropped: !round_config.contributor_drops.is_empty()
+                || round_config
+                    .pause_drops
+                    .iter()
+                    .any(|participant| matches!(participant, ParticipantRef::Contributor(_))),
+        };
+
+        if let Err(error) = check_round_state(
+            coordinator_config,
+            round_config.round_number,
+            expect_state,
+            &participants,
+        ) {
+            ceremony_tx.broadcast(CeremonyMessage::Shutdown(ShutdownReason::Error))?;
+            round_errors.push(error);
+        }
+    }
+
+    // Verify the aggregated output of the round in the transcript.
+    let transcript_verification = match options.binaries().verify_transcript {
+        Some(verify_transcript_bin)
+            if options.verify_transcript
+                && round_config.expect == RoundOutcome::Finish
+                && total_round_duration.is_some() =>
+        {
+            let (verification, result) = verify_transcript(
+                &verify_transcript_bin,
+                options.environment,
+                coordinator_config.transcript_dir(),
+                round_config.round_number,
+                options.out_dir.join("verify-transcript.log"),
+            )?;
+
+            tracing::info!(
+                "Transcript verification time: {}",
+                format_duration(verification.duration)
+            );
+
+            if let Err(error) = result {
+                ceremony_tx.broadcast(CeremonyMessage::Shutdown(ShutdownReason::Error))?;
+                round_errors.push(error);
+            }
+
+            Some(verification)
+        }
+        _ => None,
+    };
+
     // Wait for threads to close after being told to shut down.
     join_multiple(process_joins).expect("Error while joining process monitor threads.");
     join_multiple(mid_round_contributor_joins)
         .expect("Error while joining mid round contributor join threads");
 
+    tracing::debug!("Waiting for drop_verifier threads to join.");
+    for verifier_drop_join in verifier_drop_joins {
+        verifier_drop_join
+            .join()
+            .expect("Error while joining drop verifier thread")?;
+    }
+
+    tracing::debug!("Waiting for network fault threads to join.");
+    for network_fault_join in network_fault_joins {
+        network_fault_join
+            .join()
+            .expect("Error while joining network fault thread")?;
+    }
+
+    tracing::debug!("Waiting for coordinator fault threads to join.");
+    for coordinator_fault_join in coordinator_fault_joins {
+        coordinator_fault_join
+            .join()
+            .expect("Error while joining coordinator fault thread")?;
+    }
+
+    tracing::debug!("Waiting for pause threads to join.");
+    for pause_join in pause_joins {
+        pause_join
+            .join()
+            .expect("Error while joining pause thread")?;
+    }
+
+    tracing::debug!("Waiting for round_outcome thread to join.");
+    round_outcome_join
+        .join()
+        .expect("Error while joining round outcome thread")?;
+
     tracing::debug!("Waiting for monitor_drops thread to join.");
-    monitor_drops_join
+    let drop_results = monitor_drops_join
         .join()
         .expect("Error while monitor drops thread")?;
 
@@ -832,6 +1724,9 @@ fn test_round(
         round_config.round_number
     );
 
+    let resources = resource_monitor.finish_round();
+    let timing = round_timing.round_timing(round_config.round_number);
+
     if !round_errors.is_empty() {
         tracing::error!("Round completed with errors.");
         for error in &round_errors {
@@ -844,10 +1739,24 @@ fn test_round(
     }
 
     let results = RoundResults {
+        outcome: round_config.expect,
         total_round_duration: total_round_duration
             .unwrap_or_else(|| std::time::Duration::from_secs(0)),
         aggregation_duration: aggregation_duration
             .unwrap_or_else(|| std::time::Duration::from_secs(0)),
+        timing,
+        contributions: drop_results
+            .contribution_counts
+            .into_iter()
+            .map(|(contributor, n_contributions)| (contributor.to_string(), n_contributions))
+            .collect(),
+        replacements: drop_results
+            .replacements
+            .into_iter()
+            .map(|(dropped, replacement)| (dropped.to_string(), replacement.to_string()))
+            .collect(),
+        transcript_verification,
+        resources,
     };
 
     std::fs::write(
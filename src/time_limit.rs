@@ -1,4 +1,8 @@
 use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     thread::JoinHandle,
     time::{Duration, Instant},
 };
@@ -8,11 +12,31 @@ use mpmc_bus::{Receiver, Sender, TryRecvError};
 
 use crate::{CeremonyMessage, ShutdownReason};
 
+/// The error returned by a test which exceeded its time limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeLimitExceeded {
+    /// The time limit of the test.
+    pub duration: Duration,
+}
+
+impl std::fmt::Display for TimeLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Time limit of {} for test has been exceeded.",
+            format_duration(self.duration)
+        )
+    }
+}
+
+impl std::error::Error for TimeLimitExceeded {}
+
 /// Run a time limit thread for the specified duration. If the
-/// ceremony exceeds the timer, then this will send a shutdown
-/// message.
+/// ceremony exceeds the timer, then this will set `exceeded`, send a
+/// shutdown message, and return a [TimeLimitExceeded] error.
 pub fn ceremony_time_limit(
     duration: std::time::Duration,
+    exceeded: Arc<AtomicBool>,
     mut ceremony_rx: Receiver<CeremonyMessage>,
     ceremony_tx: Sender<CeremonyMessage>,
 ) -> JoinHandle<eyre::Result<()>> {
@@ -29,11 +53,9 @@ pub fn ceremony_time_limit(
 
             if start_time.elapsed() > duration {
                 tracing::error!("Time limit exceeded, telling ceremony to shutdown.");
-                ceremony_tx.broadcast(CeremonyMessage::Shutdown(ShutdownReason::Error))?;
-                return Err(eyre::eyre!(
-                    "Time limit of {} for test has been exceeded.",
-                    &duration_formatted
-                ));
+                exceeded.store(true, Ordering::SeqCst);
+                ceremony_tx.broadcast(CeremonyMessage::Shutdown(ShutdownReason::TimeLimit))?;
+                return Err(TimeLimitExceeded { duration }.into());
             }
 
             match ceremony_rx.try_recv() {
@@ -51,3 +73,33 @@ pub fn ceremony_time_limit(
         }
     })
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use eyre::Context;
+
+    use super::TimeLimitExceeded;
+
+    #[test]
+    fn test_time_limit_exceeded_downcast() {
+        let time_limit = TimeLimitExceeded {
+            duration: Duration::from_secs(60),
+        };
+
+        let result: eyre::Result<()> = Err(eyre::eyre!("coordinator exited"));
+        let error = result
+            .wrap_err(time_limit)
+            .wrap_err("Error while running individual test")
+            .unwrap_err();
+        assert_eq!(error.downcast_ref::<TimeLimitExceeded>(), Some(&time_limit));
+
+        let error = eyre::Report::from(time_limit).wrap_err("Error while running individual test");
+        assert_eq!(error.downcast_ref::<TimeLimitExceeded>(), Some(&time_limit));
+
+        let error =
+            eyre::eyre!("coordinator exited").wrap_err("Error while running individual test");
+        assert!(error.downcast_ref::<TimeLimitExceeded>().is_none());
+    }
+}
@@ -0,0 +1,86 @@
+//! Functions for verifying the ceremony transcript written by the
+//! `aleo-setup-coordinator` with a `verify-transcript` binary.
+//!
+//! Neither `aleo-setup` nor `aleo-setup-coordinator` currently provide
+//! a binary for verifying the transcript, so this is only supported
+//! with the `stand-in-verify-transcript` (see [crate::stand_in]),
+//! which checks the transcript written by the `stand-in-coordinator`.
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use eyre::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{process::default_parse_exit_status, Environment};
+
+/// The results of verifying the transcript at the end of a round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptVerification {
+    /// Whether the transcript up to and including the round was
+    /// found to be valid.
+    pub valid: bool,
+    /// The time taken to verify the transcript.
+    #[serde(with = "humantime_serde")]
+    pub duration: Duration,
+}
+
+/// Use the `verify-transcript` binary at `verify_transcript_bin` to
+/// check that the aggregated output in `transcript_dir` is a valid
+/// chain of rounds up to and including `round`. The binary is run as
+/// `verify-transcript --environment <environment> --transcript-dir
+/// <transcript_dir> --round <round>`, which is the command line of the
+/// `stand-in-verify-transcript`. The output of the verifier is
+/// appended to `log_file_path`. Returns an error if the verifier could
+/// not be run, otherwise the result of the verification.
+pub fn verify_transcript(
+    verify_transcript_bin: impl AsRef<Path>,
+    environment: Environment,
+    transcript_dir: impl AsRef<Path>,
+    round: u64,
+    log_file_path: impl AsRef<Path>,
+) -> eyre::Result<(TranscriptVerification, eyre::Result<()>)> {
+    tracing::info!("Verifying transcript up to round {}.", round);
+
+    let start_time = Instant::now();
+    let capture = subprocess::Exec::cmd(verify_transcript_bin.as_ref())
+        .args(&["--environment", &environment.to_string()])
+        .arg("--transcript-dir")
+        .arg(transcript_dir.as_ref())
+        .args(&["--round", &round.to_string()])
+        .stderr(subprocess::Redirection::Merge)
+        .capture()
+        .wrap_err_with(|| {
+            eyre::eyre!(
+                "Error running transcript verifier {:?}",
+                verify_transcript_bin.as_ref()
+            )
+        })?;
+    let duration = start_time.elapsed();
+
+    let mut log_file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(log_file_path)
+        .wrap_err("unable to open log file")?;
+    log_file.write_all(capture.stdout_str().as_ref())?;
+
+    let result = default_parse_exit_status(capture.exit_status).wrap_err_with(|| {
+        eyre::eyre!(
+            "Transcript verification failed for round {}:\n{}",
+            round,
+            capture.stdout_str()
+        )
+    });
+
+    let verification = TranscriptVerification {
+        valid: result.is_ok(),
+        duration,
+    };
+
+    Ok((verification, result))
+}
@@ -1,4 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::{
+    net::TcpListener,
+    path::{Path, PathBuf},
+};
 
 use eyre::Context;
 
@@ -14,3 +17,20 @@ where
     }
     Ok(path.into())
 }
+
+/// Find `n` distinct TCP ports which are currently free on the local
+/// machine. The ports are not reserved, so there is a small chance
+/// that they will be taken by another process before they are used.
+pub fn free_ports(n: usize) -> eyre::Result<Vec<u16>> {
+    // The listeners are kept open until all ports have been found to
+    // ensure that the same port is not returned twice.
+    let listeners = (0..n)
+        .map(|_| TcpListener::bind("127.0.0.1:0"))
+        .collect::<std::io::Result<Vec<TcpListener>>>()
+        .wrap_err("Error while finding free ports")?;
+
+    listeners
+        .iter()
+        .map(|listener| Ok(listener.local_addr()?.port()))
+        .collect()
+}
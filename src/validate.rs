@@ -0,0 +1,461 @@
+//! Validation of test specifications and configuration, so that
+//! mistakes are reported all at once before any repositories are
+//! cloned, crates built, or keys generated.
+
+use std::fmt::Display;
+
+use crate::{
+    config::Config,
+    coordinator::CoordinatorConfigOverrides,
+    coordinator_fault::CoordinatorFaultTrigger,
+    drop_participant::DropVerifierConfig,
+    fault::FaultTarget,
+    process::ProcessesConfig,
+    round_outcome::RoundOutcome,
+    selection::glob_matches,
+    specification::TestId,
+    test::{ContributorStartConfig, Repo, TestRound},
+};
+
+/// A problem found while validating a test or the configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The id of the test with the problem, or `None` if the problem
+    /// is with the configuration (or the test's id is unknown).
+    pub test: Option<TestId>,
+    /// The index of the round with the problem in the test's
+    /// `rounds`, or `None` if the problem is with the test itself.
+    pub round: Option<usize>,
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.test, self.round) {
+            (Some(test), Some(round)) => {
+                write!(f, "test {:?} `rounds[{}]`: {}", test, round, self.message)
+            }
+            (Some(test), None) => write!(f, "test {:?}: {}", test, self.message),
+            (None, Some(round)) => write!(f, "`rounds[{}]`: {}", round, self.message),
+            (None, None) => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Convert the `errors` found during validation into a result, with
+/// an error listing all of the problems if there are any.
+pub fn validation_result(errors: Vec<ValidationError>) -> eyre::Result<()> {
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    let problems: Vec<String> = errors
+        .iter()
+        .map(|error| format!("  - {}", error))
+        .collect();
+    Err(eyre::eyre!(
+        "Found {} problem(s) during validation:\n{}",
+        errors.len(),
+        problems.join("\n")
+    ))
+}
+
+/// Validate the `config`.
+pub fn validate_config(config: &Config) -> Vec<ValidationError> {
+    let mut messages: Vec<String> = Vec::new();
+
+    if config.parallelism == 0 {
+        messages.push("Expected `parallelism` to be at least 1.".to_string());
+    }
+
+    if config.resource_sample_interval.as_nanos() == 0 {
+        messages.push("Expected `resource_sample_interval` to be greater than 0.".to_string());
+    }
+
+    if let Some(stand_ins) = &config.stand_ins {
+        if !stand_ins.is_dir() {
+            messages.push(format!(
+                "The `stand_ins` directory {:?} does not exist.",
+                stand_ins
+            ));
+        }
+    }
+
+    // The repositories are not used when running with the stand-ins.
+    let repos = if config.stand_ins.is_none() {
+        vec![
+            ("aleo_setup_repo", &config.aleo_setup_repo),
+            (
+                "aleo_setup_coordinator_repo",
+                &config.aleo_setup_coordinator_repo,
+            ),
+        ]
+    } else {
+        Vec::new()
+    };
+    for (field, repo) in repos {
+        if let Repo::Local(repo) = repo {
+            if !repo.dir.is_dir() {
+                messages.push(format!(
+                    "The local repository directory {:?} for `{}` does not exist.",
+                    repo.dir, field
+                ));
+            }
+        }
+    }
+
+    messages
+        .into_iter()
+        .map(|message| ValidationError {
+            test: None,
+            round: None,
+            message,
+        })
+        .collect()
+}
+
+/// Validate the options of a test with the specified `id` (if known).
+/// Checks everything which can be checked before the test is run.
+pub fn validate_test(
+    id: Option<&TestId>,
+    verifiers: u8,
+    replacement_contributors: u8,
+    coordinator: &CoordinatorConfigOverrides,
+    processes: &ProcessesConfig,
+    rounds: &[TestRound],
+) -> Vec<ValidationError> {
+    let mut errors: Vec<ValidationError> = Vec::new();
+    let mut error = |round: Option<usize>, message: String| {
+        errors.push(ValidationError {
+            test: id.cloned(),
+            round,
+            message,
+        })
+    };
+
+    if verifiers == 0 {
+        error(None, "Expected `verifiers` to be at least 1.".to_string());
+    }
+
+    if rounds.is_empty() {
+        error(None, "Expected at least one round in `rounds`.".to_string());
+    }
+
+    if let Err(coordinator_error) = coordinator.validate() {
+        error(
+            None,
+            format!("Invalid `coordinator`: {}", coordinator_error),
+        );
+    }
+    let (contributor_seen_timeout, participant_lock_timeout) = coordinator.participant_timeouts();
+
+    // The number of verifiers which have not been dropped during a
+    // previous round.
+    let mut running_verifiers = verifiers as usize;
+
+    for (i, round) in rounds.iter().enumerate() {
+        let mut error = |message: String| error(Some(i), message);
+
+        if round.contributors == 0 {
+            error("Expected `contributors` to be at least 1.".to_string());
+        }
+
+        if round.contributor_starts.len() > round.contributors as usize {
+            error(format!(
+                "Invalid `contributor_starts`. Its length ({}) should not exceed the \
+                    number of contributors ({}).",
+                round.contributor_starts.len(),
+                round.contributors,
+            ));
+        }
+
+        for (start_index, start) in round.contributor_starts.iter().enumerate() {
+            if let ContributorStartConfig::CeremonyStart = start {
+                error(format!(
+                    "Invalid `contributor_starts[{}]`. {:?} is not a valid start config \
+                        for a normal contributor.",
+                    start_index, start
+                ));
+            }
+        }
+
+        if round.contributor_drops.len() > round.contributors as usize {
+            error(format!(
+                "Invalid `contributor_drops`. Its length ({}) should not exceed the \
+                    number of contributors ({}).",
+                round.contributor_drops.len(),
+                round.contributors,
+            ));
+        }
+
+        if round.expect == RoundOutcome::Restart && round.expect_state.is_some() {
+            error(
+                "Invalid `expect_state`. The state can only be checked for rounds which \
+                    are expected to finish."
+                    .to_string(),
+            );
+        }
+
+        if round.expect == RoundOutcome::Restart
+            && round.coordinator_faults.iter().any(|fault_config| {
+                matches!(fault_config.trigger, CoordinatorFaultTrigger::Aggregation)
+            })
+        {
+            error(
+                "Invalid `coordinator_faults`. The `Aggregation` trigger can only be used \
+                    for rounds which are expected to finish."
+                    .to_string(),
+            );
+        }
+
+        if round.expect_replacements
+            && round.contributor_drops.len() > replacement_contributors as usize
+        {
+            error(format!(
+                "Invalid `expect_replacements`. The number of `contributor_drops` ({}) \
+                    should not exceed the number of replacement contributors ({}).",
+                round.contributor_drops.len(),
+                replacement_contributors,
+            ));
+        }
+
+        if !round.verifier_drops.is_empty() && round.verifier_drops.len() >= running_verifiers {
+            error(format!(
+                "Invalid `verifier_drops`. Its length ({}) should be less than the number \
+                    of running verifiers ({}).",
+                round.verifier_drops.len(),
+                running_verifiers,
+            ));
+        }
+        running_verifiers = running_verifiers.saturating_sub(round.verifier_drops.len());
+
+        let targets = round
+            .network_faults
+            .iter()
+            .map(|fault_config| ("network_faults", &fault_config.target))
+            .chain(
+                round
+                    .pauses
+                    .iter()
+                    .map(|pause_config| ("pauses", &pause_config.target)),
+            );
+        for (field, target) in targets {
+            let (index, n_participants) = match target {
+                FaultTarget::Contributor(index) => (*index, round.contributors),
+                FaultTarget::ReplacementContributor(index) => (*index, replacement_contributors),
+                FaultTarget::Verifier(index) => (*index, verifiers),
+            };
+            if index == 0 || index > n_participants {
+                error(format!(
+                    "Invalid `{}`. There is no participant corresponding to the target {:?}.",
+                    field, target
+                ));
+            }
+        }
+
+        for pause_config in &round.pauses {
+            if let Err(pause_error) =
+                pause_config.expect_drop(contributor_seen_timeout, participant_lock_timeout)
+            {
+                error(format!("Invalid `pauses`: {}", pause_error));
+            }
+        }
+    }
+
+    // The ids of the participants which can be targeted by
+    // `processes.participants`, as they are created in
+    // [crate::test::integration_test()]. Contributors are identified by
+    // the index of their configured round, starting at 1.
+    let mut participant_ids: Vec<String> = (1..=verifiers)
+        .map(|i| format!("verifier{}", i))
+        .chain((1..=replacement_contributors).map(|i| format!("replacement_contributor{}", i)))
+        .collect();
+    for (round_index, round) in (1..).zip(rounds) {
+        participant_ids
+            .extend((1..=round.contributors).map(|i| format!("contributor{}-{}", round_index, i)));
+    }
+
+    for (pattern, _) in &processes.participants {
+        if !participant_ids.iter().any(|id| glob_matches(pattern, id)) {
+            error(
+                None,
+                format!(
+                    "Invalid `processes.participants`. {:?} does not match the id of any \
+                        participant.",
+                    pattern
+                ),
+            );
+        }
+    }
+
+    errors
+}
+
+/// Validate that the test with the specified `id` (if known) only
+/// uses the options which depend on output that only the stand-ins
+/// produce (see [crate::stand_in]) when it is run with the stand-ins
+/// (`stand_ins`).
+pub fn validate_stand_in_only(
+    id: Option<&TestId>,
+    stand_ins: bool,
+    verify_transcript: bool,
+    rounds: &[TestRound],
+) -> Vec<ValidationError> {
+    let mut errors: Vec<ValidationError> = Vec::new();
+    if stand_ins {
+        return errors;
+    }
+
+    if verify_transcript {
+        errors.push(ValidationError {
+            test: id.cloned(),
+            round: None,
+            message: "Invalid `verify_transcript`. There is no `verify-transcript` binary for \
+                the real components, transcript verification is only supported with the \
+                stand-ins."
+                .to_string(),
+        });
+    }
+
+    for (i, round) in rounds.iter().enumerate() {
+        let mut error = |message: String| {
+            errors.push(ValidationError {
+                test: id.cloned(),
+                round: Some(i),
+                message,
+            })
+        };
+
+        if round
+            .verifier_drops
+            .iter()
+            .any(|drop| matches!(drop, DropVerifierConfig::AfterVerifications(_)))
+        {
+            error(
+                "Invalid `verifier_drops`. `AfterVerifications` is only supported with the \
+                    stand-ins, use `AfterDelay` instead."
+                    .to_string(),
+            );
+        }
+
+        if round.expect_replacements {
+            error(
+                "Invalid `expect_replacements`. The replacement of dropped contributors is \
+                    detected from the `stand-in-coordinator` log output, so this check is only \
+                    supported with the stand-ins."
+                    .to_string(),
+            );
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod test {
+    use super::{validate_stand_in_only, validate_test, validation_result};
+    use crate::{
+        coordinator::CoordinatorConfigOverrides, process::ProcessesConfig, test::TestRound,
+    };
+
+    #[test]
+    fn test_validate_test() {
+        let rounds: Vec<TestRound> = ron::from_str(
+            r#"[
+                (contributors: 2),
+                (
+                    contributors: 1,
+                    contributor_starts: [CeremonyStart, RoundStart],
+                    contributor_drops: [(after_contributions: 1), (after_contributions: 2)],
+                ),
+                (
+                    contributors: 1,
+                    expect: Restart,
+                    expect_state: Some((chunks: Some(12))),
+                    verifier_drops: [AfterVerifications(1), AfterDelay("1s")],
+                    pauses: [(target: Verifier(3), trigger: AfterDelay("1s"), duration: "1s")],
+                ),
+            ]"#,
+        )
+        .unwrap();
+
+        let id = "test".to_string();
+        let errors = validate_test(
+            Some(&id),
+            2,
+            0,
+            &CoordinatorConfigOverrides::default(),
+            &ProcessesConfig::default(),
+            &rounds,
+        );
+        let rounds_with_errors: Vec<Option<usize>> =
+            errors.iter().map(|error| error.round).collect();
+        assert_eq!(
+            rounds_with_errors,
+            vec![Some(1), Some(1), Some(1), Some(2), Some(2), Some(2)]
+        );
+        assert!(errors.iter().all(|error| error.test.as_ref() == Some(&id)));
+
+        let message = format!("{}", validation_result(errors).unwrap_err());
+        assert!(message.contains("Found 6 problem(s)"));
+        assert!(message.contains(r#"test "test" `rounds[1]`: Invalid `contributor_starts`."#));
+        assert!(message.contains("`rounds[2]`: Invalid `verifier_drops`."));
+
+        assert!(validate_test(
+            Some(&id),
+            1,
+            0,
+            &CoordinatorConfigOverrides::default(),
+            &ProcessesConfig::default(),
+            &rounds[..1]
+        )
+        .is_empty());
+
+        let processes: ProcessesConfig = ron::from_str(
+            r#"(
+                participants: [
+                    ("contributor1-*", (log_filter: Some("trace"))),
+                    ("verifier3", (args: ["--verbose"])),
+                ],
+            )"#,
+        )
+        .unwrap();
+        let errors = validate_test(
+            Some(&id),
+            2,
+            0,
+            &CoordinatorConfigOverrides::default(),
+            &processes,
+            &rounds[..1],
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains(r#""verifier3" does not match"#));
+    }
+
+    #[test]
+    fn test_validate_stand_in_only() {
+        let rounds: Vec<TestRound> = ron::from_str(
+            r#"[
+                (contributors: 1, verifier_drops: [AfterDelay("1s")]),
+                (contributors: 1, verifier_drops: [AfterVerifications(1)]),
+                (
+                    contributors: 2,
+                    contributor_drops: [(after_contributions: 1)],
+                    expect_replacements: true,
+                ),
+            ]"#,
+        )
+        .unwrap();
+
+        assert!(validate_stand_in_only(None, true, true, &rounds).is_empty());
+
+        let errors = validate_stand_in_only(None, false, true, &rounds);
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].round, None);
+        assert!(errors[0].message.contains("`verify_transcript`"));
+        assert_eq!(errors[1].round, Some(1));
+        assert!(errors[1].message.contains("`AfterVerifications`"));
+        assert_eq!(errors[2].round, Some(2));
+        assert!(errors[2].message.contains("`expect_replacements`"));
+    }
+}
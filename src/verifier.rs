@@ -4,19 +4,33 @@
 use crate::{
     process::{
         default_parse_exit_status, fallible_monitor, run_monitor_process, MonitorProcessJoin,
+        MonitorProcessMessage, ProcessConfig,
     },
-    CeremonyMessage,
+    AleoPublicKey, CeremonyMessage, VerifierRef,
 };
 
 use eyre::Context;
 use mpmc_bus::{Receiver, Sender};
+use regex::Regex;
 
 use std::{
     fs::{File, OpenOptions},
     io::{BufRead, BufReader, Write},
     path::{Path, PathBuf},
+    str::FromStr,
+    sync::{mpsc, Mutex},
 };
 
+lazy_static::lazy_static! {
+    /// Matches the participant id of the verifier in its log. Participant
+    /// ids have the form `<address>.verifier`, the same form used by the
+    /// coordinator in its log (see `DROPPED_PARTICIPANT_RE` in
+    /// [crate::coordinator]).
+    static ref VERIFIER_ID_RE: Regex = Regex::new("(?P<address>aleo1[a-z0-9]{58})[.]verifier").unwrap();
+    /// Matches an Aleo address in the output of the `view-key` binary.
+    static ref ADDRESS_RE: Regex = Regex::new("aleo1[a-z0-9]{58}").unwrap();
+}
+
 pub struct VerifierViewKey(String);
 
 impl AsRef<str> for VerifierViewKey {
@@ -31,12 +45,41 @@ impl std::fmt::Display for VerifierViewKey {
     }
 }
 
-/// Use `setup1-contributor` to generate the contributor key file used
-/// in [run_contributor()].
+/// Parse the output of the `view-key` binary: the view key on the
+/// first line, which may be followed by the address corresponding to
+/// the view key.
+fn parse_view_key_output(
+    view_key_out: &str,
+) -> eyre::Result<(VerifierViewKey, Option<AleoPublicKey>)> {
+    let mut lines = view_key_out.split('\n');
+    let view_key = lines
+        .next()
+        .expect("Expected to be able to split view key output with \\n")
+        .trim();
+
+    if view_key.is_empty() {
+        return Err(eyre::eyre!(
+            "The `view-key` binary did not output a view key."
+        ));
+    }
+
+    let address = lines
+        .find_map(|line| ADDRESS_RE.find(line))
+        .map(|address| AleoPublicKey::from_str(address.as_str()))
+        .transpose()?;
+
+    Ok((VerifierViewKey(view_key.to_string()), address))
+}
+
+/// Use the `view-key` binary to generate the view key file used in
+/// [run_verifier()]. Returns the address of the verifier if the
+/// `view-key` binary outputs it after the view key, otherwise the
+/// address can only be obtained from the log of the verifier once it
+/// is running, see [run_verifier()].
 pub fn generate_verifier_key(
     view_key_bin_path: impl AsRef<Path> + std::fmt::Debug,
     view_key_path: impl AsRef<Path> + std::fmt::Debug,
-) -> eyre::Result<()> {
+) -> eyre::Result<Option<AleoPublicKey>> {
     tracing::info!("Generating verifier view key.");
 
     let capture = subprocess::Exec::cmd(view_key_bin_path.as_ref())
@@ -45,20 +88,12 @@ pub fn generate_verifier_key(
 
     default_parse_exit_status(capture.exit_status)?;
 
-    let view_key_out = capture.stdout_str();
-    let view_key = view_key_out
-        .split('\n')
-        .next()
-        .expect("Expected to be able to split view key output with \\n");
-
-    assert!(!view_key.is_empty());
-    tracing::info!("Generated view key: {}", view_key);
-
-    let key = VerifierViewKey(view_key.to_string());
+    let (key, address) = parse_view_key_output(&capture.stdout_str())?;
+    tracing::info!("Generated view key: {}", key);
 
     std::fs::write(view_key_path, key.as_ref()).wrap_err("error writing view key for verifier")?;
 
-    Ok(())
+    Ok(address)
 }
 
 /// Data relating to a verifier.
@@ -69,53 +104,103 @@ pub struct Verifier {
     pub id: String,
     /// This verifier's view key.
     pub view_key_path: PathBuf,
+    /// Aleo address, if it is known. This is obtained from the output
+    /// of the `view-key` binary when the key is generated (see
+    /// [generate_verifier_key()]), or otherwise from the log of the
+    /// verifier once it is running (see [run_verifier()]).
+    pub address: Option<AleoPublicKey>,
+}
+
+impl Verifier {
+    /// Obtains the [VerifierRef] referring to this [Verifier] (if its
+    /// address is known).
+    pub fn as_verifier_ref(&self) -> Option<VerifierRef> {
+        self.address.clone().map(|address| VerifierRef { address })
+    }
+
+    /// The id used to reference this verifier by the coordinator, and
+    /// within the ceremony transcript (if its address is known).
+    pub fn id_on_coordinator(&self) -> Option<String> {
+        self.as_verifier_ref()
+            .map(|verifier_ref| verifier_ref.id_on_coordinator())
+    }
+}
+
+/// Configuration for running a verifier.
+#[derive(Debug, Clone)]
+pub struct VerifierConfig {
+    /// An identifier for this verifier, used only by the integration
+    /// test.
+    pub id: String,
+    /// The path to the `setup1-verifier` binary.
+    pub verifier_bin_path: PathBuf,
+    /// The url to connect to the coordinator.
+    pub coordinator_api_url: String,
+    /// The path to the view key file used by this verifier.
+    pub view_key_path: PathBuf,
+    /// The working directory for this verifier.
+    pub out_dir: PathBuf,
+    /// Extra environment variables, arguments and the log filter for
+    /// the verifier process.
+    pub process: ProcessConfig,
 }
 
-/// Run the `setup1-verifier`.
+/// Run the `setup1-verifier`. Returns the join handle for the
+/// verifier process, a sender which can be used to terminate it, and
+/// a receiver for the address of the verifier, which is sent when the
+/// verifier first includes its participant id in its log.
 pub fn run_verifier(
-    id: &str,
-    verifier_bin_path: impl AsRef<Path>,
-    coordinator_api_url: &str,
-    view_key_path: impl AsRef<Path>,
+    config: VerifierConfig,
     ceremony_tx: Sender<CeremonyMessage>,
     ceremony_rx: Receiver<CeremonyMessage>,
-    out_dir: PathBuf,
-) -> eyre::Result<MonitorProcessJoin> {
-    let view_key_path: PathBuf = view_key_path.as_ref().canonicalize()?;
+) -> eyre::Result<(
+    MonitorProcessJoin,
+    Sender<MonitorProcessMessage>,
+    mpsc::Receiver<AleoPublicKey>,
+)> {
+    let view_key_path: PathBuf = config.view_key_path.canonicalize()?;
     let view_key: String = std::fs::read_to_string(&view_key_path)?;
-    let span = tracing::error_span!("verifier", id = id, view_key = %view_key);
+    let span = tracing::error_span!("verifier", id = %config.id, view_key = %view_key);
     let _guard = span.enter();
 
     tracing::info!("Running verifier.");
 
-    let exec = subprocess::Exec::cmd(verifier_bin_path.as_ref().canonicalize()?)
-        .cwd(&out_dir)
-        .env("RUST_LOG", "debug,hyper=warn")
-        .env("RUST_BACKTRACE", "1")
-        .args(&["--api-url", coordinator_api_url])
+    let exec = subprocess::Exec::cmd(config.verifier_bin_path.canonicalize()?)
+        .cwd(&config.out_dir)
+        .args(&["--api-url", &config.coordinator_api_url])
         .arg("--view-key")
         .arg(view_key_path);
+    let exec = config.process.apply(exec, Some("debug,hyper=warn"));
 
-    let log_file_path = out_dir.join("verifier.log");
+    let log_file_path = config.out_dir.join("verifier.log");
+    let (address_tx, address_rx) = mpsc::channel();
+    // The monitor closure is required to be `Sync`.
+    let address_tx = Mutex::new(Some(address_tx));
 
-    let (join, _) = run_monitor_process(
-        id.to_string(),
+    let (join, monitor_tx) = run_monitor_process(
+        config.id.clone(),
         exec,
         default_parse_exit_status,
         ceremony_tx,
         ceremony_rx,
         fallible_monitor(move |stdout, _ceremony_tx, _monitor_tx| {
-            verifier_monitor(stdout, &log_file_path)
+            let address_tx = address_tx.lock().expect("error obtaining lock").take();
+            verifier_monitor(stdout, &log_file_path, address_tx)
         }),
     )
-    .wrap_err_with(|| format!("Error running verifier {:?}", verifier_bin_path.as_ref()))?;
+    .wrap_err_with(|| format!("Error running verifier {:?}", config.verifier_bin_path))?;
 
-    Ok(join)
+    Ok((join, monitor_tx, address_rx))
 }
 
-/// Monitors the `setup1-contributor`, logs output to `log_file_path`
-/// file and `tracing::debug!()`.
-fn verifier_monitor(stdout: File, log_file_path: impl AsRef<Path>) -> eyre::Result<()> {
+/// Monitors the `setup1-verifier`, logs output to `log_file_path`
+/// file, and sends the address of the verifier to `address_tx` when
+/// its participant id first appears in the output.
+fn verifier_monitor(
+    stdout: File,
+    log_file_path: impl AsRef<Path>,
+    mut address_tx: Option<mpsc::Sender<AleoPublicKey>>,
+) -> eyre::Result<()> {
     let buf_pipe = BufReader::new(stdout);
 
     let mut log_file = OpenOptions::new()
@@ -129,6 +214,16 @@ fn verifier_monitor(stdout: File, log_file_path: impl AsRef<Path>) -> eyre::Resu
     for line_result in buf_pipe.lines() {
         match line_result {
             Ok(line) => {
+                if let Some(captures) = VERIFIER_ID_RE.captures(&line) {
+                    if let Some(address_tx) = address_tx.take() {
+                        let address = AleoPublicKey::from_str(&captures["address"])?;
+                        tracing::debug!("Verifier address is {}.", address);
+                        // The receiver is dropped if the test has
+                        // stopped waiting for the address.
+                        let _ = address_tx.send(address);
+                    }
+                }
+
                 // Write to log file.
                 log_file.write_all(line.as_ref())?;
                 log_file.write_all("\n".as_ref())?;
@@ -142,3 +237,25 @@ fn verifier_monitor(stdout: File, log_file_path: impl AsRef<Path>) -> eyre::Resu
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::parse_view_key_output;
+    use crate::stand_in::address_from_seed;
+
+    #[test]
+    fn test_parse_view_key_output() {
+        let address = address_from_seed("verifier1");
+
+        let (view_key, parsed_address) =
+            parse_view_key_output(&format!("AViewKey1abc\n{}\n", address)).unwrap();
+        assert_eq!(view_key.to_string(), "AViewKey1abc");
+        assert_eq!(parsed_address, Some(address));
+
+        let (view_key, parsed_address) = parse_view_key_output("AViewKey1abc\n").unwrap();
+        assert_eq!(view_key.to_string(), "AViewKey1abc");
+        assert_eq!(parsed_address, None);
+
+        assert!(parse_view_key_output("\n").is_err());
+    }
+}
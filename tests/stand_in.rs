@@ -0,0 +1,897 @@
+//! Tests for the integration test harness itself, using the stand-in
+//! binaries in place of the real `aleo-setup` and
+//! `aleo-setup-coordinator` binaries.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use aleo_setup_integration_test::{
+    baseline::load_baseline,
+    config::Config,
+    fuzz::{generate_specification, FuzzLimits},
+    reporting::LogFileWriter,
+    retention::RetentionPolicy,
+    round_outcome::RoundOutcome,
+    selection::TestSelection,
+    specification::Specification,
+    stand_in::number_of_chunks,
+    summary::{ERROR_FILE_NAME, SUMMARY_FILE_NAME},
+    test::{RoundResults, TestResults},
+    Environment,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// The baseline that the results of the tests which pass are saved
+/// to by [stand_in_config()], so that they can be checked with
+/// [test_results()].
+const RESULTS_BASELINE: &str = "results";
+
+/// Held while running ceremonies, because the coordinator listens on
+/// the same port when the tests in a specification are not run in
+/// parallel.
+static COORDINATOR_PORT: Mutex<()> = Mutex::new(());
+
+/// Wait until no other test is running ceremonies. The lock is not
+/// poisoned by a test which failed while holding it.
+fn lock_coordinator_port() -> MutexGuard<'static, ()> {
+    COORDINATOR_PORT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The directory containing the stand-in binaries built by cargo for
+/// this test.
+fn stand_ins_dir() -> PathBuf {
+    Path::new(env!("CARGO_BIN_EXE_stand-in-coordinator"))
+        .parent()
+        .expect("expected stand-in binary to have a parent directory")
+        .to_owned()
+}
+
+/// Configuration for running the tests with the stand-in binaries,
+/// with the out directory and the baselines in `dir`.
+fn stand_in_config(dir: &Path, parallelism: usize) -> Config {
+    let config_str = format!(
+        r#"(
+            clean: true,
+            build: false,
+            keep_repos: true,
+            install_prerequisites: false,
+            state_monitor: None,
+            out_dir: {:?},
+            stand_ins: Some({:?}),
+            parallelism: {},
+            resource_sample_interval: "200ms",
+        )"#,
+        dir.join("out"),
+        stand_ins_dir(),
+        parallelism,
+    );
+    let mut config: Config =
+        ron::from_str(&config_str).expect("Error while deserializing stand-in config");
+    config.baselines_dir = dir.join("baselines");
+    config.save_baseline = Some(RESULTS_BASELINE.to_string());
+    config
+}
+
+/// The results of the test `id`, which is expected to have passed
+/// with the `config` from [stand_in_config()].
+fn test_results(config: &Config, id: &str) -> TestResults {
+    load_baseline(&config.baselines_dir, RESULTS_BASELINE, id)
+        .unwrap()
+        .unwrap_or_else(|| panic!("expected results for test {:?}", id))
+}
+
+/// The number of contributions made by each contributor during the
+/// round, in ascending order.
+fn contribution_counts(round: &RoundResults) -> Vec<u64> {
+    let mut counts: Vec<u64> = round.contributions.values().copied().collect();
+    counts.sort_unstable();
+    counts
+}
+
+/// The names of the directories in `dir`, in ascending order.
+fn dir_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .filter(|entry| entry.file_type().unwrap().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+/// An element of a JUnit report, with its attributes.
+#[derive(Debug)]
+struct JUnitElement {
+    name: String,
+    attributes: HashMap<String, String>,
+}
+
+/// A `<testcase>` element of a JUnit report, with its child
+/// elements.
+#[derive(Debug)]
+struct JUnitTestCase {
+    element: JUnitElement,
+    children: Vec<JUnitElement>,
+}
+
+/// The `<testsuite>` element of a JUnit report, with its test cases.
+#[derive(Debug)]
+struct JUnitTestSuite {
+    element: JUnitElement,
+    test_cases: Vec<JUnitTestCase>,
+}
+
+lazy_static! {
+    static ref JUNIT_ELEMENT_RE: Regex =
+        Regex::new(r#"<(?P<name>[a-zA-Z]+)(?P<attributes>(\s+[a-zA-Z]+="[^"]*")*)\s*/?>"#).unwrap();
+    static ref JUNIT_ATTRIBUTE_RE: Regex =
+        Regex::new(r#"(?P<name>[a-zA-Z]+)="(?P<value>[^"]*)""#).unwrap();
+}
+
+/// Parse the single test suite in the JUnit report `xml`. The text
+/// content of the elements is ignored (it is escaped by the writer,
+/// so it does not contain any elements).
+fn parse_junit(xml: &str) -> JUnitTestSuite {
+    let mut test_suite: Option<JUnitTestSuite> = None;
+
+    for captures in JUNIT_ELEMENT_RE.captures_iter(xml) {
+        let name = &captures["name"];
+        let element = JUnitElement {
+            name: name.to_string(),
+            attributes: JUNIT_ATTRIBUTE_RE
+                .captures_iter(&captures["attributes"])
+                .map(|attribute| {
+                    (
+                        attribute["name"].to_string(),
+                        attribute["value"].to_string(),
+                    )
+                })
+                .collect(),
+        };
+
+        if name == "testsuites" {
+            continue;
+        }
+
+        if name == "testsuite" {
+            assert!(test_suite.is_none(), "expected a single test suite");
+            test_suite = Some(JUnitTestSuite {
+                element,
+                test_cases: Vec::new(),
+            });
+            continue;
+        }
+
+        let test_cases = &mut test_suite
+            .as_mut()
+            .expect("expected elements to be inside the test suite")
+            .test_cases;
+        if name == "testcase" {
+            test_cases.push(JUnitTestCase {
+                element,
+                children: Vec::new(),
+            });
+        } else {
+            test_cases
+                .last_mut()
+                .expect("expected elements to be inside a test case")
+                .children
+                .push(element);
+        }
+    }
+
+    test_suite.expect("expected a test suite in the JUnit report")
+}
+
+/// Runs a single ceremony with the stand-ins, with extra options for
+/// the processes.
+#[test]
+fn test_stand_in_ceremony() {
+    let _port_guard = lock_coordinator_port();
+    let dir = tempfile::tempdir().unwrap();
+    let config = stand_in_config(dir.path(), 1);
+    let log_writer = LogFileWriter::new();
+
+    let specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "single",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    processes: (
+                        contributors: (env: {"RUST_BACKTRACE": "full"}),
+                        participants: [("verifier1", (log_filter: Some("trace")))],
+                    ),
+                    rounds: [(contributors: 1)],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .expect("Expected stand-in ceremony to succeed");
+
+    let results = test_results(&config, "single");
+    assert_eq!(results.round_results.len(), 1);
+    assert_eq!(results.round_results[0].outcome, RoundOutcome::Finish);
+    assert_eq!(
+        contribution_counts(&results.round_results[0]),
+        vec![number_of_chunks(Environment::Development)]
+    );
+}
+
+/// Runs ceremonies with the stand-ins where contributors are dropped,
+/// replaced or start late.
+#[test]
+fn test_stand_in_contributor_drops() {
+    let _port_guard = lock_coordinator_port();
+    let dir = tempfile::tempdir().unwrap();
+    let config = stand_in_config(dir.path(), 1);
+    let log_writer = LogFileWriter::new();
+
+    let specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "drop",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [
+                        (
+                            contributors: 2,
+                            contributor_drops: [(after_contributions: 5)],
+                            expect_state: Some((chunks: Some(12))),
+                        ),
+                    ],
+                ),
+                (
+                    id: "late-start",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [
+                        (
+                            contributors: 2,
+                            contributor_starts: [
+                                RoundStart,
+                                AfterRoundContributions((after_round_contributions: 3)),
+                            ],
+                        ),
+                    ],
+                ),
+                (
+                    id: "replacement",
+                    verifiers: 1,
+                    replacement_contributors: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [
+                        (
+                            contributors: 2,
+                            contributor_drops: [(after_contributions: 2)],
+                            expect_replacements: true,
+                            expect_state: Some((chunks: Some(12))),
+                        ),
+                    ],
+                ),
+                (
+                    id: "round-restart",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [
+                        (
+                            contributors: 1,
+                            contributor_drops: [(after_contributions: 2)],
+                            expect: Restart,
+                        ),
+                        (contributors: 1, expect_state: Some((chunks: Some(12)))),
+                    ],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .expect("Expected stand-in ceremonies to succeed");
+
+    let chunks = number_of_chunks(Environment::Development);
+
+    // The dropped contributor's remaining chunks are not contributed
+    // to by anyone else.
+    let drop = test_results(&config, "drop");
+    assert_eq!(contribution_counts(&drop.round_results[0]), vec![5, chunks]);
+    assert!(drop.round_results[0].replacements.is_empty());
+
+    assert_eq!(test_results(&config, "late-start").round_results.len(), 1);
+
+    // The replacement contributes to the chunks that the dropped
+    // contributor did not.
+    let replacement = test_results(&config, "replacement");
+    let round = &replacement.round_results[0];
+    assert_eq!(contribution_counts(round), vec![2, chunks - 2, chunks]);
+    assert_eq!(round.replacements.len(), 1);
+    let (dropped, replacement) = round.replacements.iter().next().unwrap();
+    assert_eq!(round.contributions[dropped], 2);
+    assert_eq!(round.contributions[replacement], chunks - 2);
+
+    let round_restart = test_results(&config, "round-restart");
+    let outcomes: Vec<RoundOutcome> = round_restart
+        .round_results
+        .iter()
+        .map(|round| round.outcome)
+        .collect();
+    assert_eq!(outcomes, vec![RoundOutcome::Restart, RoundOutcome::Finish]);
+    assert_eq!(
+        contribution_counts(&round_restart.round_results[0]),
+        vec![2]
+    );
+    assert_eq!(
+        contribution_counts(&round_restart.round_results[1]),
+        vec![chunks]
+    );
+}
+
+/// Runs ceremonies with the stand-ins over multiple rounds with
+/// multiple verifiers, some of which are dropped, and with the
+/// transcript verified after each round.
+#[test]
+fn test_stand_in_verifiers() {
+    let _port_guard = lock_coordinator_port();
+    let dir = tempfile::tempdir().unwrap();
+    let config = stand_in_config(dir.path(), 1);
+    let log_writer = LogFileWriter::new();
+
+    let specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "two-rounds",
+                    verifiers: 2,
+                    environment: development,
+                    timout: Some(60),
+                    verify_transcript: true,
+                    rounds: [
+                        (contributors: 1),
+                        (contributors: 2, expect_state: Some((chunks: Some(12)))),
+                    ],
+                ),
+                (
+                    id: "verifier-drop",
+                    verifiers: 3,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [
+                        (
+                            contributors: 1,
+                            verifier_drops: [AfterVerifications(2), AfterDelay("1s")],
+                            expect_state: Some((verifiers: true)),
+                        ),
+                        (contributors: 1),
+                    ],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .expect("Expected stand-in ceremonies to succeed");
+
+    let two_rounds = test_results(&config, "two-rounds");
+    assert_eq!(two_rounds.round_results.len(), 2);
+    for round in &two_rounds.round_results {
+        let verification = round
+            .transcript_verification
+            .as_ref()
+            .expect("expected the transcript to have been verified");
+        assert!(verification.valid);
+    }
+
+    let verifier_drop = test_results(&config, "verifier-drop");
+    assert_eq!(verifier_drop.round_results.len(), 2);
+    assert!(verifier_drop
+        .round_results
+        .iter()
+        .all(|round| round.transcript_verification.is_none()));
+}
+
+/// Runs ceremonies with the stand-ins with network faults, restarts of
+/// the coordinator and paused participants.
+#[test]
+fn test_stand_in_faults() {
+    let _port_guard = lock_coordinator_port();
+    let dir = tempfile::tempdir().unwrap();
+    let config = stand_in_config(dir.path(), 1);
+    let log_writer = LogFileWriter::new();
+
+    let specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "network-faults",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [
+                        (
+                            contributors: 2,
+                            network_faults: [
+                                (
+                                    target: Contributor(1),
+                                    trigger: AfterDelay("0s"),
+                                    fault: Latency("50ms"),
+                                ),
+                                (
+                                    target: Contributor(2),
+                                    trigger: AfterContributions(2),
+                                    fault: Stall,
+                                    duration: Some("1s"),
+                                ),
+                                (
+                                    target: Verifier(1),
+                                    trigger: AfterRoundContributions(4),
+                                    fault: Bandwidth(1000),
+                                ),
+                            ],
+                        ),
+                    ],
+                ),
+                (
+                    id: "coordinator-restart",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [
+                        (
+                            contributors: 2,
+                            coordinator_faults: [(trigger: AfterContributions(4))],
+                            expect_state: Some((chunks: Some(12))),
+                        ),
+                        (
+                            contributors: 1,
+                            coordinator_faults: [(trigger: Aggregation, signal: Term)],
+                        ),
+                        (
+                            contributors: 1,
+                            coordinator_faults: [(trigger: RoundStart)],
+                            expect_state: Some((chunks: Some(12))),
+                        ),
+                    ],
+                ),
+                (
+                    id: "pause",
+                    verifiers: 2,
+                    replacement_contributors: 1,
+                    environment: development,
+                    timout: Some(60),
+                    coordinator: Some((
+                        environment_parameters: (
+                            contributor_seen_timeout: Some(3),
+                            participant_lock_timeout: Some(2),
+                        ),
+                    )),
+                    rounds: [
+                        (
+                            contributors: 2,
+                            pauses: [
+                                (
+                                    target: Contributor(1),
+                                    trigger: AfterContributions(2),
+                                    duration: "500ms",
+                                ),
+                                (
+                                    target: Contributor(2),
+                                    trigger: AfterContributions(2),
+                                    duration: "5s",
+                                ),
+                                (
+                                    target: Verifier(1),
+                                    trigger: AfterRoundContributions(3),
+                                    duration: "500ms",
+                                ),
+                            ],
+                            expect_state: Some((chunks: Some(12))),
+                        ),
+                    ],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .expect("Expected stand-in ceremonies to succeed");
+
+    for (id, n_rounds) in &[
+        ("network-faults", 1),
+        ("coordinator-restart", 3),
+        ("pause", 1),
+    ] {
+        let results = test_results(&config, id);
+        assert_eq!(results.round_results.len(), *n_rounds);
+        assert!(results
+            .round_results
+            .iter()
+            .all(|round| round.outcome == RoundOutcome::Finish));
+    }
+
+    // The contributor paused for longer than the coordinator's
+    // timeouts is replaced.
+    assert_eq!(
+        test_results(&config, "pause").round_results[0]
+            .replacements
+            .len(),
+        1
+    );
+}
+
+/// Runs a specification with the stand-ins where the first test
+/// exceeds its time limit, and the remaining tests are skipped, and
+/// checks the JUnit report and summary.
+#[test]
+fn test_stand_in_time_limit() {
+    let _port_guard = lock_coordinator_port();
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = stand_in_config(dir.path(), 1);
+    let log_writer = LogFileWriter::new();
+
+    let specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "time-limit",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(2),
+                    retries: Some(1),
+                    rounds: [(contributors: 1)],
+                ),
+                (
+                    id: "skipped",
+                    verifiers: 1,
+                    environment: development,
+                    skip: true,
+                    rounds: [(contributors: 1)],
+                ),
+                (
+                    id: "excluded",
+                    verifiers: 1,
+                    environment: development,
+                    tags: ["slow"],
+                    rounds: [(contributors: 1)],
+                ),
+                (
+                    id: "after-failure",
+                    verifiers: 1,
+                    environment: development,
+                    rounds: [(contributors: 1)],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    let junit_path = dir.path().join("junit.xml");
+    config.junit = Some(junit_path.clone());
+    config.fail_fast = true;
+
+    let selection = TestSelection {
+        exclude_tags: vec!["slow".to_string()],
+        ..TestSelection::default()
+    };
+    assert!(specification.run(&config, &selection, &log_writer).is_err());
+
+    let junit = parse_junit(&std::fs::read_to_string(&junit_path).unwrap());
+    let suite_attributes = &junit.element.attributes;
+    assert_eq!(suite_attributes["tests"], "4");
+    assert_eq!(suite_attributes["failures"], "1");
+    assert_eq!(suite_attributes["skipped"], "3");
+
+    let test_case_names: Vec<&str> = junit
+        .test_cases
+        .iter()
+        .map(|test_case| test_case.element.attributes["name"].as_str())
+        .collect();
+    assert_eq!(
+        test_case_names,
+        vec!["time-limit", "skipped", "excluded", "after-failure"]
+    );
+
+    // The test failed on both attempts, and the failure of the first
+    // attempt is recorded as a rerun.
+    let time_limit: Vec<(&str, &str)> = junit.test_cases[0]
+        .children
+        .iter()
+        .map(|child| (child.name.as_str(), child.attributes["type"].as_str()))
+        .collect();
+    assert_eq!(
+        time_limit,
+        vec![("failure", "timeout"), ("rerunFailure", "error")]
+    );
+    assert!(junit.test_cases[0].children[1].attributes["message"].starts_with("attempt 1: "));
+
+    for test_case in &junit.test_cases[1..] {
+        assert_eq!(test_case.children.len(), 1);
+        assert_eq!(test_case.children[0].name, "skipped");
+    }
+    assert!(junit.test_cases[3].children[0].attributes["message"]
+        .starts_with("Not run because an earlier test failed"));
+
+    let summary = std::fs::read_to_string(config.out_dir.join(SUMMARY_FILE_NAME)).unwrap();
+    assert!(summary
+        .lines()
+        .any(|line| line.starts_with("time-limit") && line.contains("timed out")));
+
+    // Each attempt at the test which was retried has its own
+    // directory.
+    let time_limit_dir = config.out_dir.join("time-limit");
+    assert!(time_limit_dir.join(ERROR_FILE_NAME).exists());
+    assert_eq!(dir_names(&time_limit_dir), vec!["attempt-1", "attempt-2"]);
+}
+
+/// Runs a specification with the stand-ins where one test passes and
+/// the other fails, and checks that only the out directory of the
+/// test which failed is kept.
+#[test]
+fn test_stand_in_retention() {
+    let _port_guard = lock_coordinator_port();
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = stand_in_config(dir.path(), 1);
+    config.retention.policy = RetentionPolicy::KeepFailures;
+    let log_writer = LogFileWriter::new();
+
+    let specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "pass",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [(contributors: 1)],
+                ),
+                (
+                    id: "fail",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(2),
+                    rounds: [(contributors: 1)],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    assert!(specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .is_err());
+
+    assert_eq!(dir_names(&config.out_dir), vec!["fail"]);
+    assert!(config.out_dir.join("fail").join(ERROR_FILE_NAME).exists());
+}
+
+/// Checks that an invalid specification is rejected before any
+/// ceremonies are run.
+#[test]
+fn test_stand_in_invalid_specification() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = stand_in_config(dir.path(), 1);
+    let log_writer = LogFileWriter::new();
+
+    let specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "invalid",
+                    verifiers: 1,
+                    environment: development,
+                    rounds: [
+                        (contributors: 1),
+                        (
+                            contributors: 1,
+                            contributor_starts: [CeremonyStart],
+                            contributor_drops: [(after_contributions: 1), (after_contributions: 2)],
+                        ),
+                    ],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    assert_eq!(specification.validate(&config).len(), 2);
+    let error = specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .unwrap_err();
+    assert!(format!("{}", error).contains(r#"test "invalid" `rounds[1]`"#));
+    assert!(!config.out_dir.exists());
+}
+
+/// Runs ceremonies generated with the stand-ins, and checks that the
+/// limits from the fuzz configuration were applied to the generated
+/// tests and to the coordinator.
+#[test]
+fn test_stand_in_fuzz() {
+    let _port_guard = lock_coordinator_port();
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = stand_in_config(dir.path(), 1);
+    config.fuzz = ron::from_str(
+        r#"(
+            coordinator: Some((
+                environment_parameters: (
+                    minimum_contributors_per_round: Some(1),
+                    maximum_contributors_per_round: Some(2),
+                ),
+            )),
+        )"#,
+    )
+    .unwrap();
+    let log_writer = LogFileWriter::new();
+
+    let fuzz_limits = FuzzLimits {
+        time_limit: 60,
+        ..FuzzLimits::new(&config.fuzz, true).unwrap()
+    };
+    assert_eq!(fuzz_limits.min_contributors, 1);
+    assert_eq!(fuzz_limits.max_contributors, 2);
+    assert_eq!(
+        fuzz_limits.chunks,
+        number_of_chunks(Environment::Development)
+    );
+
+    let specification = generate_specification(1, 3, &fuzz_limits);
+    for test in &specification.tests {
+        assert_eq!(test.timout, Some(60));
+        assert!(test.verifiers >= 1 && test.verifiers <= fuzz_limits.max_verifiers);
+        assert!(test.replacement_contributors <= fuzz_limits.max_replacement_contributors);
+        for round in &test.rounds {
+            assert!(
+                round.contributors >= fuzz_limits.min_contributors
+                    && round.contributors <= fuzz_limits.max_contributors
+            );
+            assert!(round
+                .contributor_drops
+                .iter()
+                .all(|drop| drop.after_contributions < fuzz_limits.chunks));
+        }
+    }
+
+    specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .expect("Expected generated stand-in ceremonies to succeed");
+
+    for test in &specification.tests {
+        // The coordinator was configured with the overrides that the
+        // limits were taken from.
+        let coordinator_config: toml::Value = std::fs::read_to_string(
+            config
+                .out_dir
+                .join(&test.id)
+                .join("coordinator")
+                .join("config.toml"),
+        )
+        .unwrap()
+        .parse()
+        .unwrap();
+        let environment_parameters = &coordinator_config["environment_parameters"];
+        assert_eq!(
+            environment_parameters["minimum_contributors_per_round"].as_integer(),
+            Some(1)
+        );
+        assert_eq!(
+            environment_parameters["maximum_contributors_per_round"].as_integer(),
+            Some(2)
+        );
+
+        let results = test_results(&config, &test.id);
+        assert_eq!(results.round_results.len(), test.rounds.len());
+        for round in &results.round_results {
+            assert!(
+                round.contributions.len()
+                    <= (fuzz_limits.max_contributors + test.replacement_contributors) as usize
+            );
+        }
+    }
+}
+
+/// Runs multiple ceremonies with the stand-ins in parallel.
+#[test]
+fn test_stand_in_parallel_ceremonies() {
+    let _port_guard = lock_coordinator_port();
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = stand_in_config(dir.path(), 3);
+    config.save_baseline = Some("base".to_string());
+    let log_writer = LogFileWriter::new();
+
+    let specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "1",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [(contributors: 1)],
+                ),
+                (
+                    id: "2",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [(contributors: 2)],
+                ),
+                (
+                    id: "3",
+                    verifiers: 2,
+                    environment: development,
+                    timout: Some(60),
+                    rounds: [(contributors: 1)],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .expect("Expected parallel stand-in ceremonies to succeed");
+
+    for id in &["1", "2", "3"] {
+        assert!(config.out_dir.join(id).join("results.ron").exists());
+        assert!(config.out_dir.join(id).join("events.ndjson").exists());
+        assert!(config.out_dir.join(id).join("resources.csv").exists());
+
+        let results = std::fs::read_to_string(config.out_dir.join(id).join("results.ron")).unwrap();
+        assert!(results.contains(r#""coordinator": ("#));
+        assert!(results.contains("time_to_first_contribution: Some("));
+        assert!(config
+            .baselines_dir
+            .join("base")
+            .join(format!("{}.ron", id))
+            .exists());
+    }
+
+    let compare_specification: Specification = ron::from_str(
+        r#"(
+            tests: [
+                (
+                    id: "1",
+                    verifiers: 1,
+                    environment: development,
+                    timout: Some(60),
+                    baseline_tolerance: (warn: 10.0, fail: 100.0),
+                    rounds: [(contributors: 1)],
+                ),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    config.save_baseline = None;
+    config.compare_baseline = Some("base".to_string());
+
+    compare_specification
+        .run(&config, &TestSelection::default(), &log_writer)
+        .expect("Expected comparison against the baseline to succeed");
+
+    let comparison =
+        std::fs::read_to_string(config.out_dir.join("baseline-comparison.txt")).unwrap();
+    assert!(comparison.contains("aggregation"));
+}